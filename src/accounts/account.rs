@@ -1,11 +1,20 @@
-use crate::ledger::{
-    general::{PbKey, Result},
-    txn::{Txn, TxnType},
+use crate::{
+    accounts::{error::AccountError, multisig::MultisigPolicy},
+    ledger::{
+        general::{PbKey, Result},
+        txn::{Txn, TxnType},
+    },
+    utils::{
+        address::{decode_check, encode_check, AddressError, ADDRESS_VERSION},
+        encoding::CanonicalEncoder,
+        hash::BlakeHash,
+    },
 };
 use {
     anyhow::ensure,
     secp256k1::PublicKey,
     serde::{Deserialize, Serialize},
+    std::{fmt, str::FromStr},
 };
 
 /// ## Representation of a single on-chain account.
@@ -19,7 +28,52 @@ pub struct Account {
     balance: u128,
 }
 
-pub type AccountId = [u8; 32];
+/// ## On-chain account identifier.
+/// For single-key accounts this is the owner's public key; multisig accounts derive it from their policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct AccountId(pub [u8; 32]);
+impl From<[u8; 32]> for AccountId {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+impl From<PbKey> for AccountId {
+    fn from(value: PbKey) -> Self {
+        Self(value.0)
+    }
+}
+impl From<&PbKey> for AccountId {
+    fn from(value: &PbKey) -> Self {
+        Self(value.0)
+    }
+}
+impl From<AccountId> for PbKey {
+    fn from(value: AccountId) -> Self {
+        Self(value.0)
+    }
+}
+impl PartialEq<[u8; 32]> for AccountId {
+    fn eq(&self, other: &[u8; 32]) -> bool {
+        &self.0 == other
+    }
+}
+/// Shares the address format with `PbKey`, so a key and its account read the same.
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&encode_check(ADDRESS_VERSION, &self.0))
+    }
+}
+impl FromStr for AccountId {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self(decode_check(ADDRESS_VERSION, s)?))
+    }
+}
+
+/// Canonical encoding version for `Account` (see `Account::serialize()`).
+pub const ACCOUNT_VERSION: u8 = 1;
+
 pub type AccountPbkey = PublicKey;
 pub type AccountMapKey = AccountId;
 
@@ -34,10 +88,7 @@ impl Account {
             None => 0u128,
         };
 
-        Self {
-            id: id.clone(),
-            balance,
-        }
+        Self { id: *id, balance }
     }
     /////////////////////////////////////////////////////////////////////
     ////////////////////////////// GETTERS //////////////////////////////
@@ -45,10 +96,11 @@ impl Account {
         &self.id
     }
     pub fn id_pbkey(&self) -> PbKey {
-        PbKey(self.id().to_owned())
+        self.id.into()
     }
+    /// Get the account id in its text address format.
     pub fn id_str(&self) -> String {
-        String::from_utf8(self.id().to_vec()).unwrap()
+        self.id.to_string()
     }
     /// Get the lookup key for Account ID in hash maps throughout the application.
     pub fn id_key(&self) -> AccountMapKey {
@@ -101,11 +153,60 @@ impl Account {
 
         Ok(self.balance)
     }
+    /// ### Add to the balance.
+    pub fn credit(&mut self, amt: u128) -> u128 {
+        self.balance += amt;
+
+        self.balance
+    }
+    /// ### Take from the balance - fails, leaving the balance untouched, if the balance is too low.
+    pub fn debit(&mut self, amt: u128) -> std::result::Result<u128, AccountError> {
+        self.balance = self
+            .balance
+            .checked_sub(amt)
+            .ok_or(AccountError::InsufficientBalance {
+                balance: self.balance,
+                required: amt,
+            })?;
+
+        Ok(self.balance)
+    }
     ////////////////////////////// SETTERS //////////////////////////////
     /////////////////////////////////////////////////////////////////////
 
     /////////////////////////////////////////////////////////////////////
     /////////////////////////////// UTILS ///////////////////////////////
+    /// ### Serialize account to bytes.
+    ///
+    /// Canonical encoding (see `CanonicalEncoder`), version `ACCOUNT_VERSION`. Field order:
+    /// 1. `id` - bytes
+    /// 1. `balance` - u128
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut encoder = CanonicalEncoder::new(ACCOUNT_VERSION);
+        encoder.put_bytes(&self.id.0).put_u128(self.balance);
+
+        encoder.finish()
+    }
+    /// ### Hash of the account's state - id and balance.
+    pub fn state_hash(&self) -> BlakeHash {
+        blake3::hash(&self.serialize()).into()
+    }
+    /// ### Hash of the account's leaf in the state tree (see `Accounts::state_root()`).
+    ///
+    /// The account's `state_hash()` - for multi-signature accounts, hashed with the hash of their `policy`
+    /// (see `MultisigPolicy::account_id()`), so the state root commits to who controls the account.
+    pub fn leaf_hash(&self, policy: Option<&MultisigPolicy>) -> BlakeHash {
+        match policy {
+            None => self.state_hash(),
+            Some(policy) => {
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(self.state_hash().as_bytes());
+                hasher.update(&policy.account_id().0);
+
+                hasher.finalize().into()
+            }
+        }
+    }
     /////////////////////////////// UTILS ///////////////////////////////
     /////////////////////////////////////////////////////////////////////
 
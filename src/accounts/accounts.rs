@@ -1,4 +1,17 @@
-use crate::accounts::account::{Account, AccountMapKey};
+use crate::{
+    accounts::{
+        account::{Account, AccountId, AccountMapKey},
+        error::AccountError,
+        multisig::{MultisigPolicy, MultisigPolicyMap},
+    },
+    ledger::{
+        chain_params::ChainParams,
+        general::Result,
+        receipt::{BalanceDelta, ReceiptStatus, TxnEvent, TxnReceipt},
+        txn::Txn,
+    },
+    utils::{hash::BlakeHash, sparse_merkle::SparseMerkleTree},
+};
 use {
     serde::{Deserialize, Serialize},
     std::collections::BTreeMap,
@@ -6,9 +19,20 @@ use {
 
 pub type AccountMap = BTreeMap<AccountMapKey, Account>;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// ## Accounts and multisig policies added or changed between two states - see `Accounts::changes_since()`.
+///
+/// Accounts are never removed, so applying the changes to the earlier state gives the later one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountChanges {
+    pub accounts: Vec<Account>,
+    pub multisig_policies: Vec<MultisigPolicy>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Accounts {
     accounts: AccountMap,
+    /// Signing policies for multi-signature accounts, keyed by account id.
+    multisig_policies: MultisigPolicyMap,
 }
 
 impl Accounts {
@@ -16,7 +40,10 @@ impl Accounts {
     pub fn new() -> Self {
         let accounts = AccountMap::new();
 
-        Self { accounts }
+        Self {
+            accounts,
+            multisig_policies: MultisigPolicyMap::new(),
+        }
     }
 
     /////////////////////////////////////////////////////////////////////
@@ -64,6 +91,11 @@ impl Accounts {
             .or_insert(Account::new(pbkey_bytes, None))
     }
 
+    /// Get the signing policy for an account, if it is a multi-signature account.
+    pub fn multisig_policy(&self, acct_map_key: &AccountMapKey) -> Option<&MultisigPolicy> {
+        self.multisig_policies.get(acct_map_key)
+    }
+
     pub fn len(&self) -> usize {
         self.accounts.len()
     }
@@ -84,13 +116,147 @@ impl Accounts {
     pub fn add_acct(&mut self, account: Account) -> &mut Account {
         self.accounts.entry(account.id_key()).or_insert(account)
     }
+    /// ## Create a multi-signature account controlled by `policy`.
+    ///
+    /// The account id is derived from the policy (see `MultisigPolicy::account_id()`).\
+    /// If the account already exists, the existing account is returned and the policy is left untouched.
+    pub fn add_multisig_acct(
+        &mut self,
+        policy: MultisigPolicy,
+        balance: Option<u128>,
+    ) -> &mut Account {
+        let acct_id = policy.account_id();
+        self.multisig_policies.entry(acct_id).or_insert(policy);
+
+        self.accounts
+            .entry(acct_id)
+            .or_insert(Account::new(&acct_id, balance))
+    }
     pub fn update_acct(&mut self) {}
+    /// ## Execute a `transfer` txn, returning its receipt.
+    ///
+    /// 1. Charge the fee (see `ChainParams::fee()`) to the sender and burn it -
+    ///    the txn is rejected (error) if the sender doesn't exist or can't pay the fee;
+    /// 1. Move the amount to the receiver, creating its account if needed -
+    ///    if the sender can't cover the amount, nothing moves and the receipt status is `InsufficientBalance`.
+    ///
+    /// Does not check authorization - see `Blockchain::is_txn_authorized()`.
+    pub fn execute_txn(&mut self, txn: &Txn, params: &ChainParams) -> Result<TxnReceipt> {
+        let send_id: AccountId = txn.pbkey_send().into();
+        let recv_id: AccountId = txn.pbkey_recv().into();
+        let balances_before = [self.acct_balance(&send_id), self.acct_balance(&recv_id)];
+        let gas_used = params.gas_cost(txn.txn_type());
+        let fee = params.fee(txn.txn_type())?;
+        let mut events = vec![];
+
+        // charge the fee
+        let acct_send = self
+            .get_acct_mut(&send_id)
+            .ok_or(AccountError::UnknownAccount(send_id))?;
+        acct_send.debit(fee)?;
+        if fee > 0 {
+            events.push(TxnEvent::FeeBurned {
+                payer: send_id,
+                amt: fee,
+            });
+        }
+
+        // move the amount
+        let status = match acct_send.debit(*txn.amt()) {
+            Ok(_) => {
+                if self.get_acct(&recv_id).is_none() {
+                    events.push(TxnEvent::AccountCreated { acct_id: recv_id });
+                }
+                self.get_or_init_acct(&recv_id).credit(*txn.amt());
+                events.push(TxnEvent::Transfer {
+                    from: send_id,
+                    to: recv_id,
+                    amt: *txn.amt(),
+                });
+                ReceiptStatus::Success
+            }
+            Err(_) => ReceiptStatus::InsufficientBalance,
+        };
+
+        // record balance changes, once per account
+        let mut balance_deltas: Vec<BalanceDelta> = [send_id, recv_id]
+            .into_iter()
+            .zip(balances_before)
+            .map(|(acct_id, before)| BalanceDelta {
+                acct_id,
+                before,
+                after: self.acct_balance(&acct_id),
+            })
+            .filter(|delta| delta.before != delta.after)
+            .collect();
+        balance_deltas.sort_by_key(|delta| delta.acct_id);
+        balance_deltas.dedup_by_key(|delta| delta.acct_id);
+
+        Ok(TxnReceipt::new(
+            *txn.id(),
+            status,
+            gas_used,
+            fee,
+            balance_deltas,
+            events,
+        ))
+    }
+
+    /// ## Apply changes taken from `changes_since()`, replacing the changed accounts.
+    pub fn apply_changes(&mut self, changes: AccountChanges) {
+        for account in changes.accounts {
+            self.accounts.insert(account.id_key(), account);
+        }
+        for policy in changes.multisig_policies {
+            self.multisig_policies.insert(policy.account_id(), policy);
+        }
+    }
 
     ////////////////////////////// SETTERS //////////////////////////////
     /////////////////////////////////////////////////////////////////////
 
     /////////////////////////////////////////////////////////////////////
     /////////////////////////////// UTILS ///////////////////////////////
+    /// ## Get the accounts and multisig policies added or changed since `prev`.
+    ///
+    /// An account has changed if its `state_hash()` differs.
+    pub fn changes_since(&self, prev: &Accounts) -> AccountChanges {
+        let accounts = self
+            .accounts
+            .iter()
+            .filter(|(acct_id, acct)| match prev.accounts.get(acct_id) {
+                Some(prev_acct) => prev_acct.state_hash() != acct.state_hash(),
+                None => true,
+            })
+            .map(|(_, acct)| *acct)
+            .collect();
+        let multisig_policies = self
+            .multisig_policies
+            .iter()
+            .filter(|(acct_id, policy)| prev.multisig_policies.get(acct_id) != Some(policy))
+            .map(|(_, policy)| policy.clone())
+            .collect();
+
+        AccountChanges {
+            accounts,
+            multisig_policies,
+        }
+    }
+    /// ## Build the state tree: a sparse Merkle tree keyed by account id.
+    ///
+    /// Each leaf is the account's `leaf_hash()` - id and balance, and the signing policy of multisig accounts.
+    pub fn state_tree(&self) -> SparseMerkleTree {
+        SparseMerkleTree::new(self.accounts.iter().map(|(acct_id, acct)| {
+            (
+                acct_id.0,
+                acct.leaf_hash(self.multisig_policies.get(acct_id)),
+            )
+        }))
+    }
+    /// ## Calculate the state root committed to in `BlockHeader.state_root`.
+    pub fn state_root(&self) -> BlakeHash {
+        self.state_tree().root()
+    }
     /////////////////////////////// UTILS ///////////////////////////////
     /////////////////////////////////////////////////////////////////////
 
@@ -0,0 +1,17 @@
+use crate::accounts::account::AccountId;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AccountError {
+    #[error(
+        "AccountError::InvalidThreshold- threshold {threshold} is invalid for {signers} signers"
+    )]
+    InvalidThreshold { threshold: u8, signers: usize },
+    #[error("AccountError::TooManySigners- {0} signers exceeds the maximum allowed")]
+    TooManySigners(usize),
+    #[error("AccountError::DuplicateSigner- Signer listed more than once in policy")]
+    DuplicateSigner,
+    #[error("AccountError::UnknownAccount- Account {0} does not exist")]
+    UnknownAccount(AccountId),
+    #[error("AccountError::InsufficientBalance- balance {balance} is less than {required}")]
+    InsufficientBalance { balance: u128, required: u128 },
+}
@@ -1,2 +1,5 @@
 pub mod account;
 pub mod accounts;
+pub mod error;
+pub mod multisig;
+pub mod proof;
@@ -0,0 +1,83 @@
+use crate::{
+    accounts::{account::AccountId, error::AccountError},
+    ledger::general::PbKey,
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::collections::{BTreeMap, BTreeSet},
+};
+
+/// Domain separator for deriving multisig account ids.
+pub const MULTISIG_ACCT_CTX: &[u8; 11] = b"multisig-v0";
+/// Upper bound on the number of keys in a single policy.
+pub const MULTISIG_MAX_SIGNERS: usize = 16;
+
+pub type MultisigPolicyMap = BTreeMap<AccountId, MultisigPolicy>;
+
+/// ## Threshold (M-of-N) signing policy for a multi-signature account.
+///
+/// A transfer out of the account is authorized once `threshold` distinct
+/// signers from `signers` have attached a valid signature to the txn.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigPolicy {
+    /// Minimum number of distinct signatures required (M)
+    threshold: u8,
+    /// Public keys allowed to sign for the account (N) - ordered
+    signers: BTreeSet<PbKey>,
+}
+
+impl MultisigPolicy {
+    /// ### Create a new M-of-N policy.
+    ///
+    /// Threshold must be in `1..=signers.len()`, and signers must be unique.
+    pub fn new(threshold: u8, signers: Vec<PbKey>) -> Result<Self, AccountError> {
+        let signer_ct = signers.len();
+        if signer_ct > MULTISIG_MAX_SIGNERS {
+            return Err(AccountError::TooManySigners(signer_ct));
+        }
+        if threshold == 0 || threshold as usize > signer_ct {
+            return Err(AccountError::InvalidThreshold {
+                threshold,
+                signers: signer_ct,
+            });
+        }
+        let signers: BTreeSet<PbKey> = signers.into_iter().collect();
+        if signers.len() != signer_ct {
+            return Err(AccountError::DuplicateSigner);
+        }
+
+        Ok(Self { threshold, signers })
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `MultisigPolicy.threshold`.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+    /// ### Get property `MultisigPolicy.signers`.
+    pub fn signers(&self) -> &BTreeSet<PbKey> {
+        &self.signers
+    }
+    /// ### Check if a key is one of the policy's signers.
+    pub fn is_signer(&self, pbkey: &PbKey) -> bool {
+        self.signers.contains(pbkey)
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Derive the account id controlled by this policy.
+    ///
+    /// Hash of the threshold and the ordered signer set, so the same policy
+    /// always maps to the same account regardless of input order.
+    pub fn account_id(&self) -> AccountId {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(MULTISIG_ACCT_CTX);
+        hasher.update(&[self.threshold]);
+        for signer in self.signers.iter() {
+            hasher.update(&signer.0);
+        }
+
+        AccountId(*hasher.finalize().as_bytes())
+    }
+}
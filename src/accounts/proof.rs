@@ -0,0 +1,71 @@
+use crate::{
+    accounts::{
+        account::{Account, AccountId},
+        accounts::Accounts,
+        multisig::MultisigPolicy,
+    },
+    ledger::block::block_header::BlockHeader,
+    utils::sparse_merkle::SparseMerkleProof,
+};
+use serde::{Deserialize, Serialize};
+
+/// ## Proof of an account's state at a given block.
+///
+/// Proves that the account has a given balance and signing policy - or that it does not exist - and is verified against
+/// the block header's `state_root` alone, so the client doesn't need to trust the node that produced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountProof {
+    acct_id: AccountId,
+    /// The account's state, `None` if it does not exist
+    account: Option<Account>,
+    /// The account's signing policy, `None` unless it is a multi-signature account
+    multisig_policy: Option<MultisigPolicy>,
+    proof: SparseMerkleProof,
+}
+
+impl AccountProof {
+    /// ### Create a proof for an account from the full account state.
+    pub fn new(accounts: &Accounts, acct_id: &AccountId) -> Self {
+        Self {
+            acct_id: *acct_id,
+            account: accounts.get_acct(acct_id).copied(),
+            multisig_policy: accounts.multisig_policy(acct_id).cloned(),
+            proof: accounts.state_tree().proof(&acct_id.0),
+        }
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `AccountProof.acct_id`.
+    pub fn acct_id(&self) -> &AccountId {
+        &self.acct_id
+    }
+    /// ### Get property `AccountProof.account`.
+    pub fn account(&self) -> Option<&Account> {
+        self.account.as_ref()
+    }
+    /// ### Get property `AccountProof.multisig_policy`.
+    pub fn multisig_policy(&self) -> Option<&MultisigPolicy> {
+        self.multisig_policy.as_ref()
+    }
+    /// ### Get the proven balance - `None` if the account does not exist.
+    /// Only meaningful once the proof is verified.
+    pub fn balance(&self) -> Option<u128> {
+        self.account.map(|acct| acct.balance())
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Check the proof against a block header's state root.
+    pub fn verify(&self, header: &BlockHeader) -> bool {
+        let state_hash = match &self.account {
+            Some(acct) if acct.id() != &self.acct_id => return false,
+            Some(acct) => Some(acct.leaf_hash(self.multisig_policy.as_ref())),
+            None if self.multisig_policy.is_some() => return false,
+            None => None,
+        };
+
+        self.proof
+            .verify(header.state_root(), &self.acct_id.0, state_hash.as_ref())
+    }
+}
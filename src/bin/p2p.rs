@@ -8,7 +8,8 @@ use std::{
     net::{IpAddr, Ipv4Addr},
 };
 
-fn main() -> Result<()> {
+#[tokio::main]
+async fn main() -> Result<()> {
     let kp_filepath = String::from("tests/keys/main_ed25519.json");
     let f = File::open(kp_filepath.clone()).unwrap();
     let reader = BufReader::new(f);
@@ -24,6 +25,9 @@ fn main() -> Result<()> {
 
     let mut node = Node::new(new_p2p, new_wallet);
     node.start_p2p()?;
+    while let Some(event) = node.next_network_event().await {
+        println!("{event:?}");
+    }
 
     Ok(())
 }
@@ -1,21 +1,42 @@
-use crate::ledger::{
-    block::{block_id::BlockId, types::*},
-    general::PbKey,
+use crate::{
+    ledger::{
+        block::{
+            block_id::BlockId,
+            constants::{BLOCK_HEADER_VERSION, BLOCK_MSG_CTX},
+            types::*,
+        },
+        chain_id::ChainId,
+        general::{HashAlgo, PbKey},
+    },
+    utils::{
+        encoding::{CanonicalDecoder, CanonicalEncoder, EncodingError},
+        hash::BlakeHash,
+        merkle,
+    },
 };
 use {
     chrono::prelude::*,
+    ed25519_dalek::Digest,
     serde::{Deserialize, Serialize},
-    std::borrow::BorrowMut,
 };
 
 /// ## Block header
 ///
-/// Data contained within a block header.
-/// - @todo this should not hold transactions - temporary, for serialization;
+/// Data contained within a block header.\
+/// Transactions are kept in the block body - the header only commits to them through `txn_root`,
+/// so headers can be shipped and verified without the transactions.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BlockHeader {
-    /// List/map of all transactions to be included in the block
-    txns: BlockTxnMap,
+    /// Network the block is for - see `ChainId`
+    chain_id: ChainId,
+    /// Merkle root over the ids of all transactions in the block (see `Block::calc_txn_root()`)
+    txn_root: BlakeHash,
+    /// Sparse Merkle root over all accounts after executing the block's transactions (see `Accounts::state_root()`)
+    state_root: BlakeHash,
+    /// Merkle root over the receipts of the block's transactions, in txn order (see `calc_receipts_root()`)
+    receipts_root: BlakeHash,
+    /// Hash of the validator set taking over from the next block (see `ValidatorSet::hash()`) - `None` if unchanged
+    validator_set_hash: Option<BlakeHash>,
     /// Public key of the current block proposer (node)
     leader: PbKey,
     /// Identifier of the previous block - hash digest
@@ -30,39 +51,106 @@ pub struct BlockHeader {
 impl BlockHeader {
     /// ### `BlockHeader` constructor fxn - create a new block header (not genesis block).
     pub fn new(
-        txns: BlockTxnMap,
+        chain_id: ChainId,
+        txn_root: BlakeHash,
+        state_root: BlakeHash,
+        receipts_root: BlakeHash,
         leader: PbKey,
         prev_block_id: BlockId,
         prev_blockheight: u128,
+    ) -> Self {
+        let system_time: u64 = Utc::now().timestamp_millis().try_into().unwrap();
+
+        Self::new_at(
+            chain_id,
+            txn_root,
+            state_root,
+            receipts_root,
+            leader,
+            prev_block_id,
+            prev_blockheight,
+            system_time,
+        )
+    }
+    /// ### `BlockHeader` constructor fxn with an explicit timestamp.
+    /// Same as `BlockHeader::new()`, for headers that must be reproducible (i.e. test vectors, genesis).
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_at(
+        chain_id: ChainId,
+        txn_root: BlakeHash,
+        state_root: BlakeHash,
+        receipts_root: BlakeHash,
+        leader: PbKey,
+        prev_block_id: BlockId,
+        prev_blockheight: u128,
+        system_time: u64,
     ) -> Self {
         // calculate the blockheight
         let blockheight = prev_blockheight + 1;
 
         Self {
-            txns,
+            chain_id,
+            txn_root,
+            state_root,
+            receipts_root,
+            validator_set_hash: None,
             leader,
             prev_block_id,
             blockheight,
-            system_time: Utc::now().timestamp_millis().try_into().unwrap(),
+            system_time,
         }
     }
     /// ### Create the genesis block header.
-    pub fn genesis(leader: PbKey) -> Self {
+    pub fn genesis(chain_id: ChainId, leader: PbKey) -> Self {
         Self::new(
-            BlockTxnMap::new(),
+            chain_id,
+            merkle::empty_root(),
+            merkle::empty_root(),
+            merkle::empty_root(),
+            leader,
+            BlockId::from_bytes([0u8; 64]),
+            0,
+        )
+    }
+    /// ### Genesis block header with an initial state and an explicit timestamp.
+    /// Reproducible from a `GenesisSpec` by every node.
+    pub fn genesis_at(
+        chain_id: ChainId,
+        leader: PbKey,
+        state_root: BlakeHash,
+        system_time: u64,
+    ) -> Self {
+        Self::new_at(
+            chain_id,
+            merkle::empty_root(),
+            state_root,
+            merkle::empty_root(),
             leader,
             BlockId::from_bytes([0u8; 64]),
             0,
+            system_time,
         )
     }
 
-    /// ### Get property `Block.txns`.
-    pub fn txns(&self) -> &BlockTxnMap {
-        &self.txns
+    /// ### Get property `Block.chain_id`.
+    pub fn chain_id(&self) -> &ChainId {
+        &self.chain_id
     }
-    /// ### Get property `Block.txns` as mutable.
-    pub fn txns_mut(&mut self) -> &mut BlockTxnMap {
-        self.txns.borrow_mut()
+    /// ### Get property `Block.txn_root`.
+    pub fn txn_root(&self) -> &BlakeHash {
+        &self.txn_root
+    }
+    /// ### Get property `Block.state_root`.
+    pub fn state_root(&self) -> &BlakeHash {
+        &self.state_root
+    }
+    /// ### Get property `Block.receipts_root`.
+    pub fn receipts_root(&self) -> &BlakeHash {
+        &self.receipts_root
+    }
+    /// ### Get property `Block.validator_set_hash`.
+    pub fn validator_set_hash(&self) -> Option<&BlakeHash> {
+        self.validator_set_hash.as_ref()
     }
     /// ### Get property `Block.leader`.
     pub fn leader(&self) -> &PbKey {
@@ -76,10 +164,100 @@ impl BlockHeader {
     pub fn blockheight(&self) -> &u128 {
         &self.blockheight
     }
+    /// ### Get property `Block.system_time`.
+    pub fn system_time(&self) -> &u64 {
+        &self.system_time
+    }
+
+    /// ### Set the transaction root.
+    /// Only the owning `Block` should call this, after its transactions change.
+    pub(super) fn set_txn_root(&mut self, txn_root: BlakeHash) {
+        self.txn_root = txn_root;
+    }
+    /// ### Set the state root.
+    /// Only the owning `Block` should call this, after its transactions are executed.
+    pub(super) fn set_state_root(&mut self, state_root: BlakeHash) {
+        self.state_root = state_root;
+    }
+    /// ### Set the receipts root.
+    /// Only the owning `Block` should call this, after its transactions are executed.
+    pub(super) fn set_receipts_root(&mut self, receipts_root: BlakeHash) {
+        self.receipts_root = receipts_root;
+    }
+    /// ### Set the hash of the validator set taking over from the next block.
+    pub(super) fn set_validator_set_hash(&mut self, validator_set_hash: BlakeHash) {
+        self.validator_set_hash = Some(validator_set_hash);
+    }
+
+    /// ### Calculate the id (blockhash) for the block this header belongs to.
+    /// The id commits to the header only; the body is committed to via `txn_root`.
+    pub fn calc_id(&self) -> BlockId {
+        // Create a hash digest object which we'll feed the message into:
+        let mut prehash = HashAlgo::new();
+        // add the block version
+        prehash.update(BLOCK_MSG_CTX);
+        // add the block header bytes
+        prehash.update(self.serialize());
+
+        // return the hash digest - the block's id
+        let digest: BlockDigest = prehash.finalize().into();
+
+        BlockId(digest)
+    }
 
     /// ### Serialize header to bytes - NOT id/hash/message/digest
+    ///
+    /// Canonical encoding (see `CanonicalEncoder`), version `BLOCK_HEADER_VERSION`. Field order:
+    /// 1. `chain_id` - bytes
+    /// 1. `txn_root` - bytes
+    /// 1. `state_root` - bytes
+    /// 1. `receipts_root` - bytes
+    /// 1. `validator_set_hash` - u8 flag (0 = `None`, 1 = `Some`), followed by bytes if `Some`
+    /// 1. `leader` - bytes
+    /// 1. `prev_block_id` - bytes
+    /// 1. `blockheight` - u128
+    /// 1. `system_time` - u64
+    ///
     /// @todo replace `Vec<u8>` - don't allocate if possible
     pub fn serialize(&self) -> Vec<u8> {
-        serde_json::to_vec(self).expect("Error serializing block header")
+        let mut encoder = CanonicalEncoder::new(BLOCK_HEADER_VERSION);
+        encoder
+            .put_bytes(self.chain_id.as_bytes())
+            .put_bytes(self.txn_root.as_bytes())
+            .put_bytes(self.state_root.as_bytes())
+            .put_bytes(self.receipts_root.as_bytes());
+        match &self.validator_set_hash {
+            Some(validator_set_hash) => encoder.put_u8(1).put_bytes(validator_set_hash.as_bytes()),
+            None => encoder.put_u8(0),
+        };
+        encoder
+            .put_bytes(&self.leader.0)
+            .put_bytes(&self.prev_block_id.0)
+            .put_u128(self.blockheight)
+            .put_u64(self.system_time);
+
+        encoder.finish()
+    }
+    /// ### Deserialize block header from its canonical encoding.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, EncodingError> {
+        let mut decoder = CanonicalDecoder::new(bytes, BLOCK_HEADER_VERSION)?;
+        let header = Self {
+            chain_id: ChainId::try_from(decoder.get_bytes()?)?,
+            txn_root: BlakeHash::from_bytes(decoder.get_array()?),
+            state_root: BlakeHash::from_bytes(decoder.get_array()?),
+            receipts_root: BlakeHash::from_bytes(decoder.get_array()?),
+            validator_set_hash: match decoder.get_u8()? {
+                0 => None,
+                1 => Some(BlakeHash::from_bytes(decoder.get_array()?)),
+                _ => return Err(EncodingError::InvalidValue("validator_set_hash flag")),
+            },
+            leader: PbKey(decoder.get_array()?),
+            prev_block_id: BlockId(decoder.get_array()?),
+            blockheight: decoder.get_u128()?,
+            system_time: decoder.get_u64()?,
+        };
+        decoder.finish()?;
+
+        Ok(header)
     }
 }
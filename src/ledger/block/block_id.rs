@@ -1,8 +1,12 @@
-use crate::ledger::{block::types::BlockDigest, general::HashAlgo};
+use crate::{
+    ledger::{block::types::BlockDigest, general::HashAlgo},
+    utils::address::{decode_check, encode_check, AddressError, BLOCK_ID_VERSION},
+};
 use {
     ed25519_dalek::Digest,
     serde::{Deserialize, Serialize},
     serde_big_array::BigArray,
+    std::{fmt, str::FromStr},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -17,6 +21,19 @@ impl BlockId {
     }
 }
 
+/// Text format: base58check with `BLOCK_ID_VERSION`.
+impl fmt::Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&encode_check(BLOCK_ID_VERSION, &self.0))
+    }
+}
+impl FromStr for BlockId {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(decode_check(BLOCK_ID_VERSION, s)?))
+    }
+}
 impl From<HashAlgo> for BlockId {
     fn from(value: HashAlgo) -> Self {
         let val: BlockDigest = value.finalize().into();
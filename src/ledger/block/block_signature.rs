@@ -1,3 +1,4 @@
+use crate::ledger::error::CryptoError;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -7,9 +8,18 @@ impl BlockSignature {
         hex::encode(self.0.clone())
     }
 }
-impl From<BlockSignature> for ed25519_dalek::Signature {
-    fn from(x: BlockSignature) -> ed25519::Signature {
-        ed25519::Signature::from_bytes(&x.0).unwrap()
+impl TryFrom<BlockSignature> for ed25519_dalek::Signature {
+    type Error = CryptoError;
+
+    fn try_from(x: BlockSignature) -> Result<ed25519::Signature, Self::Error> {
+        ed25519::Signature::try_from(&x)
+    }
+}
+impl TryFrom<&BlockSignature> for ed25519_dalek::Signature {
+    type Error = CryptoError;
+
+    fn try_from(x: &BlockSignature) -> Result<ed25519::Signature, Self::Error> {
+        Ok(ed25519::Signature::from_bytes(&x.0)?)
     }
 }
 impl From<ed25519_dalek::Signature> for BlockSignature {
@@ -1,3 +1,7 @@
 pub const BLOCK_SIGNATURE_CTX: &[u8; 32] = b"ed25519BlkSignatureCtx3bx029zk3c";
+/// Context for validators' finality votes - distinct from `BLOCK_SIGNATURE_CTX` so a leader's signature can't count as a vote.
+pub const FINALITY_SIGNATURE_CTX: &[u8; 32] = b"ed25519BlkFinalityCtx7vm2q8xk4wd";
 pub const BLOCK_MSG_CTX: &[u8; 8] = b"block-v0";
+/// Version byte of the canonical `BlockHeader` encoding.
+pub const BLOCK_HEADER_VERSION: u8 = 1;
 pub const BLOCK_DIGEST_LEN: usize = 64;
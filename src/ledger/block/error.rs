@@ -1,11 +1,33 @@
+use crate::ledger::{chain_id::ChainId, error::CryptoError, general::PbKey};
+
 #[derive(Debug, thiserror::Error)]
 pub enum BlockError {
     #[error("BlockError::EmptySignature- Invalid block: No signature")]
     EmptySignature,
     #[error("BlockError::EmptyId- Invalid block: No ID")]
     EmptyId,
+    #[error("BlockError::EmptyTxnId- Invalid block: A transaction has no ID")]
+    EmptyTxnId,
     #[error("BlockError::IncorrectId- Incorrect ID")]
     IncorrectId,
+    #[error("BlockError::IncorrectTxnRoot- Transaction root does not match block body")]
+    IncorrectTxnRoot,
     #[error("BlockError::InvalidSignature- {0}. Testing signature:\n{1}")]
     InvalidSignature(ed25519_dalek::SignatureError, ed25519::Signature),
+    #[error(
+        "BlockError::OverCapacity- Block uses {gas} gas and {bytes} bytes, over the chain's limits"
+    )]
+    OverCapacity { gas: u64, bytes: u64 },
+    #[error("BlockError::GasOverflow- Gas used by the block's transactions overflows u64")]
+    GasOverflow,
+    #[error("BlockError::MismatchedFinality- Finality certificate is for a different block")]
+    MismatchedFinality,
+    #[error("BlockError::UnknownValidator- {0} is not in the validator set")]
+    UnknownValidator(PbKey),
+    #[error("BlockError::InsufficientFinality- {found} of {total} voting power signed, more than 2/3 required")]
+    InsufficientFinality { found: u128, total: u128 },
+    #[error("BlockError::WrongChainId- Expected chain {expected}, found {found}")]
+    WrongChainId { expected: ChainId, found: ChainId },
+    #[error("BlockError::Crypto- {0}")]
+    Crypto(#[from] CryptoError),
 }
@@ -0,0 +1,87 @@
+use crate::ledger::{
+    block::{
+        block_id::BlockId, block_signature::BlockSignature, constants::FINALITY_SIGNATURE_CTX,
+        error::BlockError, validation::verify_block_msg,
+    },
+    general::PbKey,
+    validator_set::ValidatorSet,
+    wallet::Wallet,
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::collections::BTreeMap,
+};
+
+/// ## Validators' votes finalizing a block.
+///
+/// Each vote is a validator's signature over the block id (with `FINALITY_SIGNATURE_CTX`).
+/// The block is final once the voters hold more than 2/3 of the validator set's voting power.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalityCertificate {
+    block_id: BlockId,
+    /// Signature per voting validator - ordered, one vote per validator
+    votes: BTreeMap<PbKey, BlockSignature>,
+}
+
+impl FinalityCertificate {
+    /// ### Create an empty certificate for a block.
+    pub fn new(block_id: BlockId) -> Self {
+        Self {
+            block_id,
+            votes: BTreeMap::new(),
+        }
+    }
+
+    /// ### Get property `FinalityCertificate.block_id`.
+    pub fn block_id(&self) -> &BlockId {
+        &self.block_id
+    }
+    /// ### Get property `FinalityCertificate.votes`.
+    pub fn votes(&self) -> &BTreeMap<PbKey, BlockSignature> {
+        &self.votes
+    }
+
+    /// ### Add the wallet owner's vote.
+    pub fn sign(&mut self, wallet: &Wallet) -> BlockSignature {
+        let signature = wallet.sign_finality(&self.block_id);
+        self.add_vote(wallet.pbkey(), signature.clone());
+
+        signature
+    }
+    /// ### Add a vote collected from a validator - replaces any earlier vote from the same validator.
+    pub fn add_vote(&mut self, validator: PbKey, signature: BlockSignature) {
+        self.votes.insert(validator, signature);
+    }
+
+    /// ### Check that the certificate finalizes `block_id` under a validator set.
+    ///
+    /// 1. Assert the certificate is for `block_id` - `BlockError::MismatchedFinality`
+    /// 1. Assert every voter is a validator - `BlockError::UnknownValidator`
+    /// 1. Assert every vote is valid - `BlockError::InvalidSignature`
+    /// 1. Assert voters hold more than 2/3 of the voting power - `BlockError::InsufficientFinality`
+    pub fn verify(
+        &self,
+        block_id: &BlockId,
+        validators: &ValidatorSet,
+    ) -> std::result::Result<(), BlockError> {
+        if &self.block_id != block_id {
+            return Err(BlockError::MismatchedFinality);
+        }
+        let mut power = 0u128;
+        for (validator, signature) in self.votes.iter() {
+            let validator_power = validators
+                .power(validator)
+                .ok_or(BlockError::UnknownValidator(*validator))?;
+            verify_block_msg(FINALITY_SIGNATURE_CTX, block_id, signature, validator)?;
+            power = power.saturating_add(validator_power);
+        }
+        if !validators.is_quorum(self.votes.keys()) {
+            return Err(BlockError::InsufficientFinality {
+                found: power,
+                total: validators.total_power(),
+            });
+        }
+
+        Ok(())
+    }
+}
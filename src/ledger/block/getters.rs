@@ -1,8 +1,10 @@
 use crate::ledger::{
     block::{block_header::BlockHeader, Block, BlockId, BlockSignature, BlockTxnMap},
     blockchain::types::*,
+    chain_id::ChainId,
     general::PbKey,
 };
+use crate::utils::hash::BlakeHash;
 
 impl Block {
     /// ### Get property `Block.transactions` included in this `Block`.
@@ -11,10 +13,27 @@ impl Block {
     }
     /// ### Get property `Block.transactions` in this `Block`.
     pub fn txns(&self) -> &BlockTxnMap {
-        self.header.txns()
+        &self.txns
     }
-    pub fn txns_mut(&mut self) -> &mut BlockTxnMap {
-        self.header.txns_mut()
+    /// ### Get property `Block.chain_id`.
+    pub fn chain_id(&self) -> &ChainId {
+        self.header.chain_id()
+    }
+    /// ### Get property `Block.txn_root`.
+    pub fn txn_root(&self) -> &BlakeHash {
+        self.header.txn_root()
+    }
+    /// ### Get property `Block.state_root`.
+    pub fn state_root(&self) -> &BlakeHash {
+        self.header.state_root()
+    }
+    /// ### Get property `Block.receipts_root`.
+    pub fn receipts_root(&self) -> &BlakeHash {
+        self.header.receipts_root()
+    }
+    /// ### Get property `Block.validator_set_hash`.
+    pub fn validator_set_hash(&self) -> Option<&BlakeHash> {
+        self.header.validator_set_hash()
     }
     /// ### Get property `Block.blockheight`.
     pub fn blockheight(&self) -> &u128 {
@@ -2,28 +2,37 @@ pub mod block_header;
 pub mod block_id;
 pub mod block_signature;
 pub mod constants;
-mod error;
+pub mod error;
+pub mod finality;
 mod getters;
 pub mod setters;
+pub mod txn_proof;
 pub mod types;
 mod utils;
 mod validation;
 
-use crate::ledger::{
-    block::{
-        block_header::BlockHeader, block_id::BlockId, block_signature::BlockSignature, types::*,
+use crate::{
+    ledger::{
+        block::{
+            block_header::BlockHeader, block_id::BlockId, block_signature::BlockSignature, types::*,
+        },
+        chain_id::ChainId,
+        general::PbKey,
+        validator_set::ValidatorSet,
+        wallet::Wallet,
     },
-    general::PbKey,
-    wallet::Wallet,
+    utils::{hash::BlakeHash, merkle},
 };
 use serde::{Deserialize, Serialize};
 
 /// ## Info contained within a block
 ///
-/// @todo create method to check if block is over gas limit (create gas value for each `Txn` type);
+/// Gas and byte-size limits are chain parameters - see `Block::is_within_capacity()`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Block {
     header: BlockHeader,
+    /// Block body: all transactions included in the block - committed to by `header.txn_root`
+    txns: BlockTxnMap,
     /// Identifier/ID - hash digest of the current block
     id: Option<BlockId>,
     /// The leader's signature for this block submission - Ecdsa signature
@@ -36,15 +45,27 @@ impl Block {
     /// @todo add `blockchain` as param - use it to get block count
     /// @todo allow `None` input for `txns` to default to a new block txn map
     pub fn new(
+        chain_id: ChainId,
         txns: BlockTxnMap,
         leader: PbKey,
         prev_block_id: BlockId,
         prev_blockheight: u128,
     ) -> Self {
         // create block header
-        let header = BlockHeader::new(txns, leader, prev_block_id, prev_blockheight);
+        let txn_root = Self::calc_txn_root_for(&txns);
+        // the state and receipts roots are only known once the txns are executed - see `Block::set_state_root()`
+        let header = BlockHeader::new(
+            chain_id,
+            txn_root,
+            merkle::empty_root(),
+            merkle::empty_root(),
+            leader,
+            prev_block_id,
+            prev_blockheight,
+        );
         let mut block = Self {
             header,
+            txns,
             id: None,
             signature: None,
         };
@@ -56,12 +77,13 @@ impl Block {
     /// ### Create and add the genesis block.
     ///
     /// The genesis block is the initial/seed block for the entire blockchain.
-    pub fn new_genesis(initializer: &Wallet) -> Self {
+    pub fn new_genesis(chain_id: ChainId, initializer: &Wallet) -> Self {
         // create genesis block header
-        let genesis_block = BlockHeader::genesis(initializer.pbkey());
+        let genesis_block = BlockHeader::genesis(chain_id, initializer.pbkey());
         // create a new block using the `Block` constructor - we need to replace the blockheight, id, and signature
         let mut genesis_block = Self {
             header: genesis_block,
+            txns: BlockTxnMap::new(),
             id: None,
             signature: None,
         };
@@ -71,9 +93,38 @@ impl Block {
 
         genesis_block
     }
+    /// ### Create the genesis block for an initial state and validator set - see `GenesisSpec::block()`.
+    ///
+    /// Every node builds the same block from the same inputs, so it has no leader (zeroed key) and is not signed.
+    pub fn new_genesis_at(
+        chain_id: ChainId,
+        state_root: BlakeHash,
+        validators: &ValidatorSet,
+        system_time: u64,
+    ) -> Self {
+        let header = BlockHeader::genesis_at(chain_id, PbKey([0u8; 32]), state_root, system_time);
+        let mut genesis_block = Self {
+            header,
+            txns: BlockTxnMap::new(),
+            id: None,
+            signature: None,
+        };
+        // commit to the initial validators - also sets the id
+        genesis_block.set_next_validator_set(validators);
+
+        genesis_block
+    }
 
     /////////////////////////////////////////////////
     //////////////// PRIVATE SETTERS ////////////////
+    /// ### Recalculate the transaction root and id after the block body changes.
+    /// Returns id.
+    fn update_txn_root(&mut self) -> BlockId {
+        let txn_root = self.calc_txn_root();
+        self.header.set_txn_root(txn_root);
+
+        self.update_id()
+    }
     /// ### Calculate and set the id for a `Block`.
     /// Returns id.
     fn update_id(&mut self) -> BlockId {
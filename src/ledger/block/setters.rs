@@ -1,7 +1,11 @@
-use crate::ledger::{
-    block::{Block, BlockSignature},
-    txn::Txn,
-    wallet::Wallet,
+use crate::{
+    ledger::{
+        block::{Block, BlockSignature},
+        txn::Txn,
+        validator_set::ValidatorSet,
+        wallet::Wallet,
+    },
+    utils::hash::BlakeHash,
 };
 
 impl Block {
@@ -18,12 +22,35 @@ impl Block {
     }
 
     /// ### Add a transaction to the block.
-    /// Since we are updating the state of the block, we update the block id (hash) here.
+    /// Since we are updating the state of the block, we update the transaction root and block id (hash) here.
     pub fn add_txn(&mut self, new_txn: Txn) {
-        self.txns_mut()
+        self.txns
             .entry(new_txn.id_key().to_owned())
             .or_insert(new_txn);
-        // update block hash since the transactions map has been updated
+        // update txn root and block hash since the transactions map has been updated
+        self.update_txn_root();
+    }
+    /// ### Drop the block body, keeping the header - and so the id and signature (see `PruningMode::HeadersOnly`).
+    /// The txn root still commits to the dropped txns.
+    pub fn prune_txns(&mut self) {
+        self.txns.clear();
+    }
+    /// ### Set the state root resulting from executing this block's transactions.
+    /// Updates the block id (hash) - the block must be signed afterwards.
+    pub fn set_state_root(&mut self, state_root: BlakeHash) {
+        self.header.set_state_root(state_root);
+        self.update_id();
+    }
+    /// ### Set the receipts root resulting from executing this block's transactions.
+    /// Updates the block id (hash) - the block must be signed afterwards.
+    pub fn set_receipts_root(&mut self, receipts_root: BlakeHash) {
+        self.header.set_receipts_root(receipts_root);
+        self.update_id();
+    }
+    /// ### Hand over to a new validator set from the next block on.
+    /// Updates the block id (hash) - the block must be signed afterwards.
+    pub fn set_next_validator_set(&mut self, validators: &ValidatorSet) {
+        self.header.set_validator_set_hash(validators.hash());
         self.update_id();
     }
 }
@@ -0,0 +1,46 @@
+use crate::{
+    ledger::{
+        block::{block_header::BlockHeader, Block},
+        txn::txn_id::TxnId,
+    },
+    utils::merkle::MerkleProof,
+};
+use serde::{Deserialize, Serialize};
+
+/// ## Proof that a transaction is included in a block.
+///
+/// Verified against a `BlockHeader` alone, so the block body is not needed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnInclusionProof {
+    txn_id: TxnId,
+    proof: MerkleProof,
+}
+
+impl TxnInclusionProof {
+    /// ### Get property `TxnInclusionProof.txn_id`.
+    pub fn txn_id(&self) -> &TxnId {
+        &self.txn_id
+    }
+    /// ### Get the position of the transaction within the block.
+    pub fn position(&self) -> u64 {
+        self.proof.index()
+    }
+    /// ### Check that the transaction is included in the block with this header.
+    pub fn verify(&self, header: &BlockHeader) -> bool {
+        self.proof.verify(header.txn_root(), &self.txn_id.0)
+    }
+}
+
+impl Block {
+    /// ### Create an inclusion proof for a transaction in this block.
+    /// Returns `None` if the transaction is not in the block.
+    pub fn txn_proof(&self, txn_id: &TxnId) -> Option<TxnInclusionProof> {
+        let position = self.txns.values().position(|txn| txn.id() == txn_id)?;
+        let proof = Self::txn_tree(&self.txns).proof(position)?;
+
+        Some(TxnInclusionProof {
+            txn_id: *txn_id,
+            proof,
+        })
+    }
+}
@@ -4,7 +4,7 @@ use crate::ledger::{
 };
 
 /// ### This is TxnMap with added functionality.
-/// @todo add condition that this map cant have more than _ number of txns.
+/// Capacity is bounded by the chain's gas and byte-size limits (see `ChainParams`).
 pub type BlockTxnMap = TxnMap;
 
 pub type BlockDigest = [u8; 64];
@@ -1,37 +1,35 @@
-use crate::ledger::{
-    block::{constants::BLOCK_MSG_CTX, types::BlockDigest, Block, BlockId, BlockSignature},
-    general::HashAlgo,
-    wallet::Wallet,
+use crate::{
+    ledger::{
+        block::{types::BlockTxnMap, Block, BlockId, BlockSignature},
+        wallet::Wallet,
+    },
+    utils::{hash::BlakeHash, merkle::MerkleTree},
 };
-use ed25519_dalek::Digest;
 
 impl Block {
     /// ### Calculate the id (blockhash) for a `Block`.
-    /// Converts semantic data for the block - all non-calculated fields (i.e. excludes `id` and `signature`) into bytes.
-    ///
-    /// Hashes this info and produces a hash digest - the ID.
+    /// Hashes the block header (see `BlockHeader::calc_id()`), which commits to the transactions via `txn_root`.
     pub fn calc_id(&self) -> BlockId {
-        let prehash = self.calc_id_sha512_prehash();
-
-        // return the hash digest - the block's id
-        let digest: BlockDigest = prehash.finalize().into();
+        self.header.calc_id()
+    }
 
-        BlockId(digest)
+    /// ### Calculate the Merkle root over the ids of this block's transactions.
+    pub fn calc_txn_root(&self) -> BlakeHash {
+        Self::calc_txn_root_for(&self.txns)
+    }
+    /// ### Build the Merkle tree over transaction ids, in `BlockTxnMap` order.
+    pub(super) fn txn_tree(txns: &BlockTxnMap) -> MerkleTree {
+        MerkleTree::new(txns.values().map(|txn| txn.id().0))
     }
-    /// ### Calculate the pre-hash struct for the id.
-    /// This is ONLY used with calc-id method.
-    ///
-    /// @todo add more block information to the block hasher.
-    fn calc_id_sha512_prehash(&self) -> HashAlgo {
-        // Create a hash digest object which we'll feed the message into:
-        let mut prehash = HashAlgo::new();
-        // add the block version
-        prehash.update(BLOCK_MSG_CTX);
-        // add the block header bytes
-        prehash.update(self.header.serialize());
-        // @todo add other info to the prehash
-        // return the hasher/prehash struct
-        prehash
+    pub(super) fn calc_txn_root_for(txns: &BlockTxnMap) -> BlakeHash {
+        Self::txn_tree(txns).root()
+    }
+
+    /// ### Get the encoded size of the block in bytes - header plus all txns (see `Txn::size()`).
+    pub fn size(&self) -> u64 {
+        let header_len = self.header.serialize().len() as u64;
+
+        header_len + self.txns.values().map(|txn| txn.size()).sum::<u64>()
     }
 
     /// ### Create and return a block signature based on the contents of the transaction
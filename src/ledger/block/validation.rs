@@ -1,50 +1,152 @@
 use crate::ledger::{
-    block::{constants::BLOCK_SIGNATURE_CTX, error::BlockError, types::BlockDigest, Block},
+    block::{
+        block_header::BlockHeader, block_id::BlockId, block_signature::BlockSignature,
+        constants::BLOCK_SIGNATURE_CTX, error::BlockError, types::BlockDigest, Block,
+    },
+    chain_id::ChainId,
+    chain_params::ChainParams,
     general::PbKey,
+    txn::Txn,
 };
 
+impl BlockHeader {
+    /// ### Check if a leader's signature over this header's block id is valid.
+    ///
+    /// Needs only the header, so it is shared by full blocks and header-only (light) clients.\
+    /// Signed under the signing context of the header's chain (see `ChainId::signing_ctx()`).
+    pub fn verify_signature(
+        &self,
+        signature: &BlockSignature,
+        signer_pbkey: &PbKey,
+    ) -> std::result::Result<(), BlockError> {
+        verify_block_msg(
+            &self.chain_id().signing_ctx(BLOCK_SIGNATURE_CTX),
+            &self.calc_id(),
+            signature,
+            signer_pbkey,
+        )
+    }
+    /// ### Check the header is for the network `chain_id`.
+    pub fn is_for_chain(&self, chain_id: &ChainId) -> std::result::Result<(), BlockError> {
+        if self.chain_id() != chain_id {
+            return Err(BlockError::WrongChainId {
+                expected: chain_id.clone(),
+                found: self.chain_id().clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// ### Check a signature over a block id, prefixed with a signing context.
+/// Matches `Wallet::sign_block()` and `Wallet::sign_finality()`.
+pub(super) fn verify_block_msg(
+    ctx: &[u8],
+    block_id: &BlockId,
+    signature: &BlockSignature,
+    signer_pbkey: &PbKey,
+) -> std::result::Result<(), BlockError> {
+    // create message for verification
+    let msg: BlockDigest = (*block_id).into();
+    let mut presigned_msg = ctx.to_vec();
+    presigned_msg.append(&mut msg.to_vec());
+
+    let sig_test = ed25519::Signature::try_from(signature)?;
+    let signer_conv = ed25519_dalek::PublicKey::try_from(signer_pbkey)?;
+
+    match signer_conv.verify_strict(&presigned_msg, &sig_test) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(BlockError::InvalidSignature(e, sig_test)),
+    }
+}
+
 impl Block {
     /// ### Check if signature is valid.
     ///
-    /// 1. Assert there is a signature (not applicable currently - program panics)
-    /// 1. Assert signature is valid
-    ///
-    /// Return is a Result of an Option to handle non-existant signatures
-    /// - `Some()` indicates a signature exists and its valid/invalid
-    /// - `None` indicates there is no signature
-    /// - `Error` is for error handling
+    /// 1. Assert there is a signature - `BlockError::EmptySignature`
+    /// 1. Assert the signature and signer key are well-formed - `BlockError::Crypto`
+    /// 1. Assert signature is valid - `BlockError::InvalidSignature`
     pub fn is_signature_valid(&self, signer_pbkey: &PbKey) -> std::result::Result<(), BlockError> {
-        // create message for verification
-        let msg: BlockDigest = self.calc_id().into();
-        let mut presigned_msg = BLOCK_SIGNATURE_CTX.to_vec();
-        presigned_msg.append(&mut msg.to_vec());
-
         // get the current signature
-        let block_signature = self.signature();
-        let sig_test = ed25519::Signature::from_bytes(&block_signature.0).unwrap();
-        let signer_conv: ed25519_dalek::PublicKey = signer_pbkey.into();
+        let block_signature = self.signature.as_ref().ok_or(BlockError::EmptySignature)?;
+
+        self.header.verify_signature(block_signature, signer_pbkey)
+    }
 
-        match signer_conv.verify_strict(&presigned_msg, &sig_test) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(BlockError::InvalidSignature(e, sig_test)),
+    /// ### Check the block, and every txn in it, has an id - so `id()` getters can't panic.
+    ///
+    /// Blocks decoded from the wire (or an archive) may lack ids - check before reading them.
+    pub fn has_ids(&self) -> std::result::Result<(), BlockError> {
+        self.id.ok_or(BlockError::EmptyId)?;
+        if !self.txns.values().all(Txn::has_id) {
+            return Err(BlockError::EmptyTxnId);
         }
+
+        Ok(())
     }
 
     /// ### Check if block is valid.
     ///
     /// Valid criteria:
-    ///   - all struct properties are not `None` - (not applicable - default is panic)
+    ///   - all struct properties are not `None`
     ///   - hash is valid
+    ///   - transaction root matches the block body
     ///   - signature is valid
     pub fn is_valid(&self, signer_pbkey: &PbKey) -> std::result::Result<(), BlockError> {
         // validate fields
+        self.has_ids()?;
         // validate hash
-        if self.calc_id() != self.id() {
+        if self.calc_id() != self.id.ok_or(BlockError::EmptyId)? {
             return Err(BlockError::IncorrectId);
         }
+        // validate body
+        if &self.calc_txn_root() != self.txn_root() {
+            return Err(BlockError::IncorrectTxnRoot);
+        }
         // validate signature
         self.is_signature_valid(&signer_pbkey)?;
 
         Ok(())
     }
+
+    /// ### Check the block, and every txn in it, is for the network `chain_id`.
+    pub fn is_for_chain(&self, chain_id: &ChainId) -> std::result::Result<(), BlockError> {
+        self.header.is_for_chain(chain_id)?;
+        match self.txns.values().find(|txn| txn.chain_id() != chain_id) {
+            Some(txn) => Err(BlockError::WrongChainId {
+                expected: chain_id.clone(),
+                found: txn.chain_id().clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// ### Check that the block is within the chain's gas and byte-size limits.
+    pub fn is_within_capacity(&self, params: &ChainParams) -> std::result::Result<(), BlockError> {
+        let gas = params.block_gas(self)?;
+        let bytes = self.size();
+        if gas > params.block_gas_limit() || bytes > params.block_byte_limit() {
+            return Err(BlockError::OverCapacity { gas, bytes });
+        }
+
+        Ok(())
+    }
+    /// ### Check that the block stays within the chain's limits after adding `txn`.
+    pub fn has_capacity_for(
+        &self,
+        txn: &Txn,
+        params: &ChainParams,
+    ) -> std::result::Result<(), BlockError> {
+        let gas = params
+            .block_gas(self)?
+            .checked_add(params.gas_cost(txn.txn_type()))
+            .ok_or(BlockError::GasOverflow)?;
+        let bytes = self.size() + txn.size();
+        if gas > params.block_gas_limit() || bytes > params.block_byte_limit() {
+            return Err(BlockError::OverCapacity { gas, bytes });
+        }
+
+        Ok(())
+    }
 }
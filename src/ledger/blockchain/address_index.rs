@@ -0,0 +1,200 @@
+use crate::{
+    accounts::account::AccountId,
+    ledger::{
+        blockchain::{
+            error::BlockchainError,
+            txn_index::{ConfirmedTxn, TxnLocation},
+            Blockchain,
+        },
+        txn::{Txn, TxnType},
+        txn_pool::TxnMapKey,
+    },
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeMap, ops::Bound},
+};
+
+/// Largest number of txns returned in one page of an account's history.
+pub const MAX_HISTORY_PAGE: usize = 1000;
+
+/// ## Position of a txn on the chain - orders an account's history by height, then by position in the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TxnCursor {
+    pub height: u128,
+    pub position: u64,
+}
+impl From<&TxnLocation> for TxnCursor {
+    fn from(value: &TxnLocation) -> Self {
+        Self {
+            height: value.height,
+            position: value.position,
+        }
+    }
+}
+
+/// ## Txns sent or received by each account, ordered by height.
+///
+/// Keyed by the `AccountId`s of each txn's `pbkey_send` and `pbkey_recv` -
+/// a txn sent to its own sender is listed once.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AddressIndex {
+    accounts: BTreeMap<AccountId, BTreeMap<TxnCursor, TxnMapKey>>,
+}
+
+impl AddressIndex {
+    /// ### Initialize an empty `AddressIndex`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// ### Add a txn at the given location to the history of its sender and receiver.
+    pub fn insert(&mut self, txn: &Txn, location: &TxnLocation) {
+        for acct_id in Self::parties(txn) {
+            self.accounts
+                .entry(acct_id)
+                .or_default()
+                .insert(location.into(), txn.id_key());
+        }
+    }
+    /// ### Remove a txn at the given location from the history of its sender and receiver.
+    pub fn remove(&mut self, txn: &Txn, location: &TxnLocation) {
+        for acct_id in Self::parties(txn) {
+            if let Some(history) = self.accounts.get_mut(&acct_id) {
+                history.remove(&location.into());
+                if history.is_empty() {
+                    self.accounts.remove(&acct_id);
+                }
+            }
+        }
+    }
+    /// ### Get an account's txns after `after` (exclusive), oldest first.
+    pub fn history(
+        &self,
+        acct_id: &AccountId,
+        after: Option<&TxnCursor>,
+    ) -> impl Iterator<Item = (&TxnCursor, &TxnMapKey)> {
+        let start = match after {
+            Some(cursor) => Bound::Excluded(*cursor),
+            None => Bound::Unbounded,
+        };
+
+        self.accounts
+            .get(acct_id)
+            .into_iter()
+            .flat_map(move |history| history.range((start, Bound::Unbounded)))
+    }
+    /// ### Get the number of txns in an account's history.
+    pub fn txn_ct(&self, acct_id: &AccountId) -> usize {
+        self.accounts
+            .get(acct_id)
+            .map_or(0, |history| history.len())
+    }
+    /// ### Get the accounts a txn is indexed under.
+    fn parties(txn: &Txn) -> Vec<AccountId> {
+        let send_id = AccountId::from(txn.pbkey_send());
+        let recv_id = AccountId::from(txn.pbkey_recv());
+
+        match send_id == recv_id {
+            true => vec![send_id],
+            false => vec![send_id, recv_id],
+        }
+    }
+}
+
+/// ## Query for a page of an account's txn history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryQuery {
+    /// Only return txns after this cursor - the `next` cursor of the previous page
+    after: Option<TxnCursor>,
+    /// Only return txns of this type
+    txn_type: Option<TxnType>,
+    /// Max number of txns to return - between 1 and `MAX_HISTORY_PAGE`
+    limit: usize,
+}
+
+impl HistoryQuery {
+    /// ### Query the first `limit` txns of an account's history, of any type.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            after: None,
+            txn_type: None,
+            limit: limit.clamp(1, MAX_HISTORY_PAGE),
+        }
+    }
+    /// ### Continue from the page ending at `cursor`.
+    pub fn after(mut self, cursor: TxnCursor) -> Self {
+        self.after = Some(cursor);
+        self
+    }
+    /// ### Only return txns of the given type.
+    pub fn txn_type(mut self, txn_type: TxnType) -> Self {
+        self.txn_type = Some(txn_type);
+        self
+    }
+}
+
+/// ## A page of an account's txn history, oldest first.
+#[derive(Debug, Clone)]
+pub struct HistoryPage<'a> {
+    pub txns: Vec<ConfirmedTxn<'a>>,
+    /// Cursor to query the next page with - `None` if this is the last page
+    pub next: Option<TxnCursor>,
+}
+
+impl Blockchain {
+    /// ### Start maintaining the address index, indexing the blocks already on the chain.
+    /// The index is optional - only nodes serving account history (i.e. for wallets) need it.
+    pub fn enable_address_index(&mut self) {
+        let mut index = AddressIndex::new();
+        for txn in self
+            .heights
+            .values()
+            .flat_map(|id| self.blocks[id].txns().values())
+        {
+            index.insert(txn, &self.txn_index[&txn.id_key()]);
+        }
+        self.address_index = Some(index);
+    }
+    /// ### Get property `Blockchain.address_index` - `None` unless enabled (see `enable_address_index()`).
+    pub fn address_index(&self) -> Option<&AddressIndex> {
+        self.address_index.as_ref()
+    }
+    /// ### Get a page of the txns sent or received by an account, ordered by height.
+    ///
+    /// `BlockchainError::HistoryPruned` if the page reaches a txn whose block's body is no longer kept (see `PruningMode`).
+    pub fn account_history(
+        &self,
+        acct_id: &AccountId,
+        query: &HistoryQuery,
+    ) -> std::result::Result<HistoryPage<'_>, BlockchainError> {
+        let index = self
+            .address_index
+            .as_ref()
+            .ok_or(BlockchainError::AddressIndexDisabled)?;
+
+        let mut matches = index
+            .history(acct_id, query.after.as_ref())
+            .filter_map(|(cursor, key)| match self.find_txn_by_key(key) {
+                Ok(found) => found.map(|found| Ok((cursor, found))),
+                Err(e) => Some(Err(e)),
+            })
+            .filter(|entry| match (entry, &query.txn_type) {
+                (Ok((_, found)), Some(txn_type)) => found.txn.txn_type() == txn_type,
+                _ => true,
+            });
+
+        let mut txns = vec![];
+        let mut last = None;
+        for entry in matches.by_ref().take(query.limit) {
+            let (cursor, found) = entry?;
+            txns.push(found);
+            last = Some(*cursor);
+        }
+        let next = match matches.next() {
+            Some(_) => last,
+            None => None,
+        };
+
+        Ok(HistoryPage { txns, next })
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{
+    accounts::accounts::{AccountChanges, Accounts},
+    ledger::{
+        block::{block_id::BlockId, Block},
+        blockchain::{error::ArchiveError, Blockchain},
+        chain_id::ChainId,
+    },
+    storage::segment_log::{
+        checksum, encode_record, parse_record_header, MAX_RECORD_LEN, RECORD_HEADER_LEN,
+    },
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::io::{Read, Write},
+};
+
+/// Bytes an archive starts with.
+pub const ARCHIVE_MAGIC: &[u8; 8] = b"POSBCARC";
+/// Current archive format version - written after the magic bytes (u32 BE).
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// ## What an archive holds - its first record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveHeader {
+    pub chain_id: ChainId,
+    /// Id of the chain's genesis block - an archive only imports onto the same chain
+    pub genesis_id: BlockId,
+    /// Height of the first block
+    pub from_height: u128,
+    /// Height of the last block
+    pub to_height: u128,
+    /// Whether the account state after the last block follows the blocks
+    pub has_state: bool,
+}
+
+/// ## A record in an archive, after the magic bytes and version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ArchiveRecord {
+    Header(ArchiveHeader),
+    Block(Box<Block>),
+    /// Account state after the block at `height` - every account, as the changes since an empty state
+    State {
+        height: u128,
+        accounts: AccountChanges,
+    },
+}
+
+/// ## Result of importing an archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Blocks added to the chain
+    pub imported: u128,
+    /// Blocks already on the chain
+    pub skipped: u128,
+    /// Height of the chain after the import
+    pub height: u128,
+}
+
+impl Blockchain {
+    /// ### Write blocks `from..=to` to a portable archive - i.e. for backups, or to seed a node offline.
+    ///
+    /// Layout: `ARCHIVE_MAGIC || version (u32 BE)`, then records framed like `SegmentLog` records
+    /// (`len || checksum || JSON payload`): the `ArchiveHeader`, each block in height order,
+    /// and - if `include_state` - the account state after block `to`.
+    pub fn export_archive(
+        &self,
+        mut writer: impl Write,
+        from: u128,
+        to: u128,
+        include_state: bool,
+    ) -> Result<ArchiveHeader, ArchiveError> {
+        if from == 0 || from > to || to > self.height() {
+            return Err(ArchiveError::InvalidRange { from, to });
+        }
+        // pruned blocks are headers only - they can't be validated on import
+        if from <= self.pruned_height() {
+            return Err(ArchiveError::HistoryPruned(from));
+        }
+        let state = match include_state {
+            true => Some(
+                self.state_at(&self.block_at(to).unwrap().id_key())
+                    .map_err(|_| ArchiveError::HistoryPruned(to))?,
+            ),
+            false => None,
+        };
+        let header = ArchiveHeader {
+            chain_id: self.chain_id.clone(),
+            genesis_id: self.block_at(1).unwrap().id(),
+            from_height: from,
+            to_height: to,
+            has_state: include_state,
+        };
+
+        writer.write_all(ARCHIVE_MAGIC)?;
+        writer.write_all(&ARCHIVE_VERSION.to_be_bytes())?;
+        Self::write_archive_record(&mut writer, &ArchiveRecord::Header(header.clone()))?;
+        for height in from..=to {
+            let block = self.block_at(height).unwrap().clone();
+            Self::write_archive_record(&mut writer, &ArchiveRecord::Block(Box::new(block)))?;
+        }
+        if let Some(accounts) = state {
+            Self::write_archive_record(
+                &mut writer,
+                &ArchiveRecord::State {
+                    height: to,
+                    accounts: accounts.changes_since(&Accounts::new()),
+                },
+            )?;
+        }
+        writer.flush()?;
+
+        Ok(header)
+    }
+    /// ### Import an archive written by `export_archive()`, validating every block.
+    ///
+    /// Blocks already on the chain are skipped. The rest are added with `replay_block()` - the same checks as
+    /// `add_block()` (signatures, chain id, capacity, conflicting height), plus linking to the chain's tip,
+    /// executing the block's txns and checking its state and receipts roots.\
+    /// The header's heights must be a range from 1 or above - `ArchiveError::Corrupt`.\
+    /// Stops at the first invalid block (`ArchiveError::InvalidBlock`) - the blocks before it stay on the chain.
+    /// If the archive holds a state, the chain's resulting state must match it.
+    pub fn import_archive(&mut self, mut reader: impl Read) -> Result<ImportSummary, ArchiveError> {
+        let mut offset = 0;
+        let mut magic = [0u8; 8];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| ArchiveError::Corrupt(0))?;
+        if &magic != ARCHIVE_MAGIC {
+            return Err(ArchiveError::Corrupt(0));
+        }
+        let mut version = [0u8; 4];
+        reader
+            .read_exact(&mut version)
+            .map_err(|_| ArchiveError::Corrupt(8))?;
+        let version = u32::from_be_bytes(version);
+        if version != ARCHIVE_VERSION {
+            return Err(ArchiveError::UnsupportedVersion(version));
+        }
+        offset += 12;
+
+        let header = match Self::read_archive_record(&mut reader, &mut offset)? {
+            Some(ArchiveRecord::Header(header))
+                if header.from_height > 0 && header.from_height <= header.to_height =>
+            {
+                header
+            }
+            _ => return Err(ArchiveError::Corrupt(offset)),
+        };
+        if let Some(genesis) = self.block_at(1) {
+            if genesis.id() != header.genesis_id {
+                return Err(ArchiveError::GenesisMismatch(header.genesis_id));
+            }
+        }
+        if header.from_height > self.height() + 1 {
+            return Err(ArchiveError::MissingBlocks {
+                expected: self.height() + 1,
+                found: header.from_height,
+            });
+        }
+
+        let mut summary = ImportSummary {
+            imported: 0,
+            skipped: 0,
+            height: self.height(),
+        };
+        let mut last_height = header.from_height - 1;
+        let mut state = None;
+        while let Some(record) = Self::read_archive_record(&mut reader, &mut offset)? {
+            let block = match record {
+                ArchiveRecord::Block(block) => *block,
+                ArchiveRecord::State { height, accounts } if header.has_state => {
+                    state = Some((height, accounts));
+                    break;
+                }
+                _ => return Err(ArchiveError::Corrupt(offset)),
+            };
+            let height = *block.blockheight();
+            let expected = last_height.saturating_add(1);
+            if height != expected || height > header.to_height {
+                return Err(ArchiveError::UnexpectedHeight {
+                    expected,
+                    found: height,
+                });
+            }
+            last_height = height;
+
+            // the checksum doesn't authenticate the block - check it has ids before reading them
+            block.has_ids().map_err(|e| ArchiveError::InvalidBlock {
+                height,
+                source: e.into(),
+            })?;
+            if self.block_at(height).map(|b| b.id()) == Some(block.id()) {
+                summary.skipped += 1;
+                continue;
+            }
+            self.replay_block(block)
+                .map_err(|source| ArchiveError::InvalidBlock { height, source })?;
+            summary.imported += 1;
+        }
+        if state.is_some() && Self::read_archive_record(&mut reader, &mut offset)?.is_some() {
+            return Err(ArchiveError::Corrupt(offset));
+        }
+        summary.height = self.height();
+        if last_height != header.to_height {
+            return Err(ArchiveError::Incomplete {
+                expected: header.to_height,
+                found: last_height,
+            });
+        }
+
+        match (header.has_state, state) {
+            (false, _) => {}
+            (true, Some((height, accounts))) if height == header.to_height => {
+                let mut archived = Accounts::new();
+                archived.apply_changes(accounts);
+                let local = self
+                    .state_at(&self.block_at(height).unwrap().id_key())
+                    .map_err(|_| ArchiveError::HistoryPruned(height))?;
+                if archived.state_root() != local.state_root() {
+                    return Err(ArchiveError::StateMismatch(height));
+                }
+            }
+            (true, _) => return Err(ArchiveError::Corrupt(offset)),
+        }
+
+        Ok(summary)
+    }
+
+    /// ### Frame and write an archive record.
+    fn write_archive_record(
+        writer: &mut impl Write,
+        record: &ArchiveRecord,
+    ) -> Result<(), ArchiveError> {
+        writer.write_all(&encode_record(&serde_json::to_vec(record)?))?;
+
+        Ok(())
+    }
+    /// ### Read the next archive record - `None` at the end of the archive.
+    /// `offset` is advanced past the record.
+    fn read_archive_record(
+        reader: &mut impl Read,
+        offset: &mut u64,
+    ) -> Result<Option<ArchiveRecord>, ArchiveError> {
+        let mut header = [0u8; RECORD_HEADER_LEN];
+        let mut read = 0;
+        while read < RECORD_HEADER_LEN {
+            match reader.read(&mut header[read..])? {
+                0 if read == 0 => return Ok(None),
+                0 => return Err(ArchiveError::Corrupt(*offset)),
+                n => read += n,
+            }
+        }
+        let (len, sum) = parse_record_header(&header);
+        if len > MAX_RECORD_LEN {
+            return Err(ArchiveError::Corrupt(*offset));
+        }
+        let mut payload = vec![0u8; len];
+        reader
+            .read_exact(&mut payload)
+            .map_err(|_| ArchiveError::Corrupt(*offset))?;
+        if checksum(&payload) != sum {
+            return Err(ArchiveError::Corrupt(*offset));
+        }
+        *offset += (RECORD_HEADER_LEN + len) as u64;
+
+        Ok(Some(serde_json::from_slice(&payload)?))
+    }
+}
@@ -1,3 +1,5 @@
+use crate::ledger::{block::block_id::BlockId, txn::txn_id::TxnId};
+
 #[derive(Debug, thiserror::Error)]
 pub enum BlockchainError {
     /// @todo move to `Txn`
@@ -8,4 +10,55 @@ pub enum BlockchainError {
     #[allow(dead_code)]
     #[error("Account balance does not change by amount determined by txn.")]
     AccountBalanceChangeMismatch,
+    #[error("BlockchainError::IncorrectStateRoot- State after executing block does not match its state root.")]
+    IncorrectStateRoot,
+    #[error("BlockchainError::IncorrectReceiptsRoot- Receipts of the block's txns do not match its receipts root.")]
+    IncorrectReceiptsRoot,
+    #[error("BlockchainError::ConflictingBlock- A different block is already at height {0}, roll back first.")]
+    ConflictingBlock(u128),
+    #[error("BlockchainError::InvalidBlockHeight- Expected a block at height {expected}, found {found}.")]
+    InvalidBlockHeight { expected: u128, found: u128 },
+    #[error("BlockchainError::InvalidPrevBlockId- Block at height {0} does not link to the chain's tip.")]
+    InvalidPrevBlockId(u128),
+    #[error("BlockchainError::UnknownHeight- No block at height {0}.")]
+    UnknownHeight(u128),
+    #[error("BlockchainError::UnknownBlock- Block {0} is not on the chain.")]
+    UnknownBlock(BlockId),
+    #[error("BlockchainError::UnknownTxn- Txn {0} is not on the chain.")]
+    UnknownTxn(TxnId),
+    #[error("BlockchainError::HistoryPruned- History at height {0} is not kept, it was pruned or the chain was restored above it.")]
+    HistoryPruned(u128),
+    #[error("BlockchainError::AddressIndexDisabled- Address index is not enabled, see `Blockchain::enable_address_index()`.")]
+    AddressIndexDisabled,
+}
+
+/// ## Errors from exporting or importing a chain archive (see `Blockchain::export_archive()`).
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveError {
+    #[error("ArchiveError::Io- {0}")]
+    Io(#[from] std::io::Error),
+    #[error("ArchiveError::InvalidRecord- {0}")]
+    InvalidRecord(#[from] serde_json::Error),
+    #[error("ArchiveError::Corrupt- Invalid archive data at offset {0}")]
+    Corrupt(u64),
+    #[error("ArchiveError::UnsupportedVersion- Archive version {0} is not supported")]
+    UnsupportedVersion(u32),
+    #[error("ArchiveError::InvalidRange- No blocks {from}..={to} on the chain")]
+    InvalidRange { from: u128, to: u128 },
+    #[error("ArchiveError::HistoryPruned- History at height {0} is not kept")]
+    HistoryPruned(u128),
+    #[error("ArchiveError::GenesisMismatch- Archive is for the chain with genesis {0}")]
+    GenesisMismatch(BlockId),
+    #[error("ArchiveError::MissingBlocks- Archive starts at height {found}, the chain needs height {expected} first")]
+    MissingBlocks { expected: u128, found: u128 },
+    #[error("ArchiveError::UnexpectedHeight- Expected block at height {expected}, found {found}")]
+    UnexpectedHeight { expected: u128, found: u128 },
+    #[error("ArchiveError::Incomplete- Archive ends at height {found}, expected {expected}")]
+    Incomplete { expected: u128, found: u128 },
+    #[error("ArchiveError::InvalidBlock- Block at height {height} is invalid: {source}")]
+    InvalidBlock { height: u128, source: anyhow::Error },
+    #[error(
+        "ArchiveError::StateMismatch- State after height {0} does not match the archive's state"
+    )]
+    StateMismatch(u128),
 }
@@ -1,9 +1,19 @@
 use crate::{
-    accounts::accounts::{AccountMap, Accounts},
+    accounts::{
+        account::AccountId,
+        accounts::{AccountMap, Accounts},
+        proof::AccountProof,
+    },
     ledger::{
         block::Block,
-        blockchain::{BlockMap, BlockMapKey, Blockchain},
+        blockchain::{error::BlockchainError, BlockMap, BlockMapKey, Blockchain},
+        chain_id::ChainId,
+        chain_params::ChainParams,
+        receipt::{BlockReceipts, TxnReceipt},
+        txn::txn_id::TxnId,
+        txn_pool::TxnMapKey,
     },
+    storage::backend::ChainBackend,
 };
 use std::borrow::BorrowMut;
 
@@ -16,14 +26,36 @@ impl Blockchain {
     pub fn block(&self, key: &BlockMapKey) -> Option<&Block> {
         self.blocks.get(key)
     }
-    /// ### Get last block on `Blockchain.blocks`.
+    /// ### Get last block on `Blockchain.blocks` - the block with the greatest height.
     /// Current behavior is to panic when no block is present.\
     /// A blockchain should never be empty, representing an undefined state.\
     /// A blockchain always needs a genesis block.
     ///
     /// @todo return a result if empty, not panic.
     pub fn last_block(&self) -> &Block {
-        self.blocks.values().next_back().unwrap()
+        let (_height, block_id) = self.heights.iter().next_back().unwrap();
+
+        &self.blocks[block_id]
+    }
+    /// ### Get the height of the last block - 0 if there are no blocks.
+    pub fn height(&self) -> u128 {
+        self.heights.keys().next_back().copied().unwrap_or(0)
+    }
+    /// ### Get the block at a given height.
+    pub fn block_at(&self, height: u128) -> Option<&Block> {
+        self.blocks.get(self.heights.get(&height)?)
+    }
+    /// ### Get property `Blockchain.chain_id`.
+    pub fn chain_id(&self) -> &ChainId {
+        &self.chain_id
+    }
+    /// ### Get property `Blockchain.backend` - `None` unless the chain is stored (see `Blockchain::open_with_backend()`).
+    pub fn backend(&self) -> Option<&dyn ChainBackend> {
+        self.backend.as_deref()
+    }
+    /// ### Get property `Blockchain.params`.
+    pub fn params(&self) -> &ChainParams {
+        &self.params
     }
     /// ### Get property `Blockchain.accounts`.
     /// This may be removed in the future.
@@ -38,4 +70,58 @@ impl Blockchain {
     pub fn account_map(&self) -> &AccountMap {
         &self.accounts.accounts()
     }
+    /// ### Get the receipts for a block's txns.
+    ///
+    /// `BlockchainError::UnknownBlock` if the block is not on the chain,
+    /// `BlockchainError::HistoryPruned` if its receipts are no longer kept (see `PruningMode`).
+    pub fn block_receipts(&self, key: &BlockMapKey) -> Result<&BlockReceipts, BlockchainError> {
+        let block = self.block(key).ok_or(BlockchainError::UnknownBlock(*key))?;
+
+        self.receipts
+            .get(key)
+            .ok_or(BlockchainError::HistoryPruned(*block.blockheight()))
+    }
+    /// ### Get the receipt for a txn in a block on the chain.
+    /// Fails like `block_receipts()` - `BlockchainError::UnknownTxn` if the txn is not on the chain.
+    pub fn receipt(&self, txn_id: &TxnId) -> Result<&TxnReceipt, BlockchainError> {
+        let location = self
+            .txn_location(txn_id)
+            .ok_or(BlockchainError::UnknownTxn(*txn_id))?;
+
+        self.block_receipts(&location.block_id)?
+            .get(&TxnMapKey::from(*txn_id))
+            .ok_or(BlockchainError::UnknownTxn(*txn_id))
+    }
+    /// ### Get the account state after a given block was applied.
+    ///
+    /// Rebuilt from the state after the lowest kept block, and the changes of the blocks above it up to the given one.\
+    /// `BlockchainError::UnknownBlock` if the block is not on the chain,
+    /// `BlockchainError::HistoryPruned` if its state is no longer kept (see `PruningMode`).
+    pub fn state_at(&self, key: &BlockMapKey) -> Result<Accounts, BlockchainError> {
+        let block = self.block(key).ok_or(BlockchainError::UnknownBlock(*key))?;
+        let height = *block.blockheight();
+        if !self.states.contains_key(&height) {
+            return Err(BlockchainError::HistoryPruned(height));
+        }
+
+        let mut accounts = self.state_base.clone();
+        for (_, changes) in self.states.range(..=height).skip(1) {
+            accounts.apply_changes(changes.clone());
+        }
+
+        Ok(accounts)
+    }
+    /// ### Create a proof of an account's state (balance, or non-existence) after a given block.
+    ///
+    /// Fails like `state_at()` if the block is unknown or its state was pruned.\
+    /// Verify the proof with `AccountProof::verify()` against the block's header.
+    pub fn account_proof(
+        &self,
+        key: &BlockMapKey,
+        acct_id: &AccountId,
+    ) -> Result<AccountProof, BlockchainError> {
+        let accounts = self.state_at(key)?;
+
+        Ok(AccountProof::new(&accounts, acct_id))
+    }
 }
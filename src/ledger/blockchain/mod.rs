@@ -1,27 +1,198 @@
-mod error;
+pub mod address_index;
+pub mod archive;
+pub mod error;
 mod getters;
+pub mod pruning;
 mod setters;
+pub mod txn_index;
 pub mod types;
 mod validation;
 
-use crate::{accounts::accounts::Accounts, ledger::blockchain::types::*};
-use serde::{Deserialize, Serialize};
+use crate::{
+    accounts::accounts::Accounts,
+    ledger::{
+        blockchain::{address_index::AddressIndex, pruning::PruningMode, types::*},
+        chain_id::ChainId,
+        chain_params::ChainParams,
+        genesis::GenesisSpec,
+        receipt::BlockReceipts,
+        Result,
+    },
+    storage::{
+        backend::{disk::DiskChainBackend, ChainBackend},
+        block_store::ChainMeta,
+        config::StorageConfig,
+        error::StorageError,
+    },
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::path::Path,
+};
 
 /// ## Data structure, contains list of sequential blocks.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Blockchain {
     /// Ordered lookup collection (BTreeMap) of blocks, queriable by their ID.
     blocks: BlockMap,
+    /// Block IDs by height - the order blocks were appended in.
+    heights: HeightMap,
+    /// Location of every txn in a block on the chain.
+    txn_index: TxnIndex,
+    /// Txns sent or received by each account - optional, see `enable_address_index()`.
+    address_index: Option<AddressIndex>,
     /// Ordered lookup collection (BTreeMap) of accounts, queriable by their ID, wrapped with methods.
     accounts: Accounts,
+    /// Changes each block made to `accounts` - used to rebuild (and prove) account state at past blocks, see `state_at()`.
+    /// Only the recent ones are kept, see `pruning`.
+    states: StateMap,
+    /// Account state after the lowest block in `states` - the states after the blocks above it are rebuilt from their changes.
+    state_base: Accounts,
+    /// Account state after the tip - `accounts` may also hold txns executed for the next block (see `add_txn_to_block()`).
+    tip_state: Accounts,
+    /// Receipts of each block's txns
+    receipts: ReceiptMap,
+    /// Receipts of txns executed locally (see `add_txn_to_block()`) that are not in a block yet
+    pending_receipts: BlockReceipts,
+    /// Network this chain belongs to - txns and blocks for other chains are rejected
+    chain_id: ChainId,
+    /// Chain-wide parameters (i.e. block gas and size limits)
+    params: ChainParams,
+    /// How much history (states, block bodies and receipts) is kept - see `set_pruning()`
+    pruning: PruningMode,
+    /// Blocks up to this height had their bodies and receipts pruned - 0 if none
+    pruned_height: u128,
+    /// Storage the chain is written to - every added block and its resulting state, see `open_with_backend()`.
+    #[serde(skip)]
+    backend: Option<Box<dyn ChainBackend>>,
 }
 impl Blockchain {
     /// ### Initialize a new `Blockchain` instance.
     /// Contains an ordered mapping of blocks, and an ordered mapping of accounts.
     pub fn new() -> Self {
+        Self::with_params(ChainParams::default())
+    }
+    /// ### Initialize a new `Blockchain` instance with the given chain parameters, on the default chain.
+    pub fn with_params(params: ChainParams) -> Self {
+        Self::for_chain(ChainId::default(), params)
+    }
+    /// ### Initialize a new `Blockchain` instance for the network `chain_id`, with the given chain parameters.
+    pub fn for_chain(chain_id: ChainId, params: ChainParams) -> Self {
         Self {
             blocks: BlockMap::new(),
+            heights: HeightMap::new(),
+            txn_index: TxnIndex::new(),
+            address_index: None,
             accounts: Accounts::new(),
+            states: StateMap::new(),
+            state_base: Accounts::new(),
+            tip_state: Accounts::new(),
+            receipts: ReceiptMap::new(),
+            pending_receipts: BlockReceipts::new(),
+            chain_id,
+            params,
+            pruning: PruningMode::default(),
+            pruned_height: 0,
+            backend: None,
         }
     }
+    /// ### Start a `Blockchain` from a genesis spec.
+    /// Holds the spec's genesis block, initial accounts and chain parameters.
+    pub fn from_genesis(spec: &GenesisSpec) -> Result<Self> {
+        spec.validate()?;
+        let mut blockchain = Self::for_chain(spec.chain_id().clone(), spec.params().clone());
+        blockchain.accounts = spec.accounts();
+
+        // built locally from the spec - not signed, so not added through `add_block()`
+        let genesis = spec.block();
+        blockchain.store_receipts(&genesis, BlockReceipts::new());
+        blockchain.snapshot_state(&genesis);
+        blockchain.index_block(&genesis);
+        blockchain.blocks.insert(genesis.id_key(), genesis);
+
+        Ok(blockchain)
+    }
+    /// ### Open the chain stored in `dir`, or start one there from a genesis spec - with the default `StorageConfig`.
+    pub fn open(dir: impl AsRef<Path>, spec: &GenesisSpec) -> Result<Self> {
+        Self::open_with_config(dir, spec, &StorageConfig::default())
+    }
+    /// ### Open the chain stored in `dir`, or start one there from a genesis spec - see `open_with_backend()`.
+    pub fn open_with_config(
+        dir: impl AsRef<Path>,
+        spec: &GenesisSpec,
+        config: &StorageConfig,
+    ) -> Result<Self> {
+        let backend = DiskChainBackend::open(dir, config)?;
+
+        Self::open_with_backend(Box::new(backend), spec, config.pruning())
+    }
+    /// ### Open the chain held by a storage backend, or start one on it from a genesis spec.
+    ///
+    /// A new backend records the spec and its genesis block. An existing backend must hold the chain
+    /// started from the same spec (`StorageError::GenesisMismatch`).\
+    /// The chain restarts at the height it stopped at:
+    /// - the blocks up to the stored account state are restored without executing them -
+    ///   account state from before that height is not kept, so the chain can't roll back below it;
+    /// - the blocks after it (i.e. if the node stopped mid-commit) are replayed.
+    ///
+    /// With `PruningMode::Archive`, every block is replayed instead, so the state after each one is kept.\
+    /// From then on, every block added (and every rollback) is written to the backend before the chain is updated.
+    pub fn open_with_backend(
+        mut backend: Box<dyn ChainBackend>,
+        spec: &GenesisSpec,
+        pruning: PruningMode,
+    ) -> Result<Self> {
+        let genesis_id = spec.block().id();
+        match backend.meta() {
+            Some(meta) if meta.genesis_id != genesis_id || &meta.spec != spec => {
+                return Err(StorageError::GenesisMismatch(meta.genesis_id).into());
+            }
+            Some(_) => {}
+            None => backend.put_meta(ChainMeta {
+                genesis_id,
+                spec: spec.clone(),
+            })?,
+        }
+
+        let mut blockchain = Self::from_genesis(spec)?;
+        blockchain.pruning = pruning;
+        if backend.height() == 0 {
+            backend.append_block(blockchain.last_block(), &BlockReceipts::new())?;
+        }
+        // the stored state is only usable if it is the state after a stored block -
+        // and archive nodes execute every block, to keep the states before it
+        let checkpoint = backend.load_state()?.filter(|checkpoint| {
+            pruning != PruningMode::Archive
+                && backend.block_id_at(checkpoint.height) == Some(checkpoint.block_id)
+        });
+        match checkpoint {
+            Some(checkpoint) => {
+                for height in 2..=checkpoint.height {
+                    let stored = backend.block_at(height)?;
+                    blockchain.restore_block(stored.block, stored.receipts);
+                }
+                if &checkpoint.accounts.state_root() != blockchain.last_block().state_root() {
+                    return Err(StorageError::StateMismatch(checkpoint.height).into());
+                }
+                blockchain.accounts = checkpoint.accounts;
+                blockchain.snapshot_state(&blockchain.last_block().clone());
+            }
+            None => backend.reset_state(1, genesis_id, &blockchain.accounts)?,
+        }
+
+        // blocks already stored are not written again, only their state is (see `persist_block()`)
+        let stored_height = backend.height();
+        blockchain.backend = Some(backend);
+        for height in blockchain.height() + 1..=stored_height {
+            let stored = blockchain.backend.as_ref().unwrap().block_at(height)?;
+            blockchain.replay_block(stored.block)?;
+        }
+        blockchain.prune_history();
+
+        Ok(blockchain)
+    }
+    /// ### Detach the chain's storage backend, i.e. to reopen the chain on it (see `open_with_backend()`).
+    pub fn into_backend(self) -> Option<Box<dyn ChainBackend>> {
+        self.backend
+    }
 }
@@ -0,0 +1,104 @@
+use crate::ledger::blockchain::Blockchain;
+use serde::{Deserialize, Serialize};
+
+/// Default number of recent blocks whose account state a pruned node keeps.
+pub const DEFAULT_STATE_HISTORY: u128 = 128;
+
+/// ## How much history a node keeps - local to the node, like `StorageConfig`.
+///
+/// Every mode keeps every block header, and the account state at the tip (needed to validate the next block).
+/// Queries for history that is no longer kept return `BlockchainError::HistoryPruned`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PruningMode {
+    /// Keep the account state after every block, and every block body and receipt (i.e. for explorers).\
+    /// A stored chain replays every block when opened, to rebuild the states.
+    Archive,
+    /// Keep the account state after the last `n` blocks (at least 1) - enough to roll back `n - 1` blocks.
+    Pruned(u128),
+    /// Keep only the tip's account state - the txns and receipts of the blocks below the tip are dropped too.\
+    /// A stored chain drops them from disk once the block store segment holding them is full (see `BlockStore::prune_bodies()`) -
+    /// it can't be reopened as an archive node afterwards, as the pruned blocks can't be replayed.
+    HeadersOnly,
+}
+impl PruningMode {
+    /// ### Get the number of recent blocks whose account state is kept - `None` if every state is kept.
+    pub fn state_history(&self) -> Option<u128> {
+        match self {
+            Self::Archive => None,
+            Self::Pruned(blocks) => Some((*blocks).max(1)),
+            Self::HeadersOnly => Some(1),
+        }
+    }
+    /// ### Whether block bodies (txns) and receipts are kept below the tip.
+    pub fn keeps_bodies(&self) -> bool {
+        !matches!(self, Self::HeadersOnly)
+    }
+}
+impl Default for PruningMode {
+    fn default() -> Self {
+        Self::Pruned(DEFAULT_STATE_HISTORY)
+    }
+}
+
+impl Blockchain {
+    /// ### Get property `Blockchain.pruning`.
+    pub fn pruning(&self) -> PruningMode {
+        self.pruning
+    }
+    /// ### Switch to another pruning mode, pruning the history it doesn't keep.
+    /// History already pruned is not restored by switching to a mode that keeps more.
+    /// The blocks of a stored chain are pruned on disk by the mode of its `StorageConfig` (see `open_with_config()`).
+    pub fn set_pruning(&mut self, pruning: PruningMode) {
+        self.pruning = pruning;
+        self.prune_history();
+    }
+    /// ### Get the height up to which block bodies and receipts were pruned - 0 if none were.
+    pub fn pruned_height(&self) -> u128 {
+        self.pruned_height
+    }
+    /// ### Get the height of the lowest block whose state is still kept - 0 if there are no blocks.
+    ///
+    /// The chain can't be rolled back past it (see `rollback_to()`), so blocks up to it are final for this node.
+    pub fn finalized_height(&self) -> u128 {
+        let floor = match self.pruning.state_history() {
+            Some(history) => self.height().saturating_sub(history) + 1,
+            None => 1,
+        };
+
+        self.states
+            .range(floor..)
+            .next()
+            .map_or(0, |(height, _)| *height)
+    }
+
+    /// ### Drop the history the pruning mode doesn't keep - called after every block added to the chain.
+    ///
+    /// The changes of the blocks whose state is dropped are applied to `state_base`,
+    /// so it stays the state after the lowest kept block.
+    pub(super) fn prune_history(&mut self) {
+        let tip = self.height();
+        if let Some(history) = self.pruning.state_history() {
+            let floor = tip.saturating_sub(history);
+            while let Some(entry) = self.states.first_entry() {
+                if *entry.key() > floor {
+                    break;
+                }
+                entry.remove();
+                if let Some((_, changes)) = self.states.first_key_value() {
+                    self.state_base.apply_changes(changes.clone());
+                }
+            }
+        }
+
+        let floor = tip.saturating_sub(1);
+        if !self.pruning.keeps_bodies() && floor > self.pruned_height {
+            for (_, block_id) in self.heights.range(self.pruned_height + 1..=floor) {
+                self.receipts.remove(block_id);
+                if let Some(block) = self.blocks.get_mut(block_id) {
+                    block.prune_txns();
+                }
+            }
+            self.pruned_height = floor;
+        }
+    }
+}
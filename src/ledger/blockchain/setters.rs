@@ -1,6 +1,7 @@
 use crate::ledger::{
     block::Block,
-    blockchain::Blockchain,
+    blockchain::{error::BlockchainError, txn_index::TxnLocation, Blockchain},
+    receipt::{calc_receipts_root, BlockReceipts, TxnReceipt},
     txn::Txn,
     txn_pool::{TxnMap, TxnPool},
     Result,
@@ -11,35 +12,36 @@ impl Blockchain {
     ///
     /// Flow:
     /// - (upstream) node has selected several txns to add to a block, this method moves one of them from the txn pool to the new block.
+    /// - validate chain id - the txn must be for this chain
     /// - validate id - check if the id/digest matches what is calculated from txn.calc_id()
     /// - validate signature - check if signature matches what is calculated from txn.calc_signature()
     /// - validate state change - simulate the state updates:
     ///     - query accounts involved
     ///     - make copies of accts and perform ops specified in the txn
     ///     - return bool if successful
-    /// - if txn is valid, apply state changes
+    /// - if txn is valid, apply state changes and charge the fee - see `Accounts::execute_txn()`
+    /// - keep the receipt until the txn's block is added (see `add_block()`)
     /// - add to block, validate the append (remove on error, i.e. if block is at capacity)
     /// - remove from txn pool
     /// - validate both events (above) happened
-    pub fn add_txn_to_block(&mut self, txn: &Txn) -> Result<()> {
-        // look up `send` account, decrease their balance
-        let acct_send = self
-            .accounts
-            .get_acct_mut(&txn.pbkey_send().into())
-            .unwrap();
-        acct_send.decrease_balance(&txn)?;
+    pub fn add_txn_to_block(&mut self, txn: &Txn) -> Result<TxnReceipt> {
+        txn.is_for_chain(&self.chain_id)?;
+        // validate signature(s) against the sending account
+        self.is_txn_authorized(txn)?;
 
-        // look up `recv` account, increase their balance
-        let acct_recv = self.accounts.get_or_init_acct(&txn.pbkey_recv().into());
-        acct_recv.increase_balance(&txn)?;
+        // apply the balance changes
+        let receipt = self.accounts.execute_txn(txn, &self.params)?;
+        self.pending_receipts.insert(txn.id_key(), receipt.clone());
 
-        Ok(())
+        Ok(receipt)
     }
     /// ### Process a set of `transfer` txns.
     ///
     /// Take txns from an arbitrary list of txns (selected and ordered by leader) and execute them one by one,
     /// applying the state changes to the accounts and placing these transactions
-    /// in the specified block.
+    /// in the specified block.\
+    /// A txn that would take the block over the chain's gas or byte-size limit is not executed - `BlockError::OverCapacity`.\
+    /// Once all txns are executed, the block's state and receipts roots are set - the block must be signed afterwards.
     ///
     /// @todo optimize by changing txns to preallocated array of hashes (ultimately &str-s)
     /// - This would allow us a set a ceiling limit on the # of txns in a given block
@@ -50,7 +52,9 @@ impl Blockchain {
         block: &mut Block,
         txn_pool: &mut TxnPool,
     ) -> Result<()> {
+        block.header().is_for_chain(&self.chain_id)?;
         for (_k, txn) in txns_to_add.iter() {
+            block.has_capacity_for(txn, &self.params)?;
             // @todo which one of these is the valid one? pick one.
             // validate and update account states
             self.add_txn_to_block(&txn)?;
@@ -60,6 +64,10 @@ impl Blockchain {
             // #64: remove from txn pool
             txn_pool.remove_txn(&txn)?;
         }
+        // commit the block to the resulting state and receipts
+        block.set_state_root(self.accounts.state_root());
+        let receipts = self.pending_block_receipts(block);
+        block.set_receipts_root(calc_receipts_root(&receipts));
 
         Ok(())
     }
@@ -68,14 +76,183 @@ impl Blockchain {
     /// Block must be signed and pass validation.
     /// - validate block id
     /// - validate block signature
+    /// - validate block and its txns are for this chain
+    /// - validate block is within the chain's gas and byte-size limits
+    /// - validate no other block is at the same height - see `rollback_to()` for reorgs
+    /// - validate signature(s) of every txn in the block
+    /// - validate the receipts of the block's (locally executed) txns against the block's `receipts_root`
+    /// - write the block and the resulting state to the backend, if the chain is stored (see `Blockchain::open_with_backend()`)
+    /// - prune the history the chain's `PruningMode` doesn't keep
     ///
     /// @todo validate previous block's: 1) height; 2) id. Add error responses for each (InvalidBlockHeight & InvalidBlockId, respectively).
     pub fn add_block(&mut self, block: Block) -> Result<&mut Block> {
         // check if block is valid
         let pbkey = block.leader();
         block.is_valid(&pbkey)?;
+        block.is_for_chain(&self.chain_id)?;
+        block.is_within_capacity(&self.params)?;
+        self.is_height_free(&block)?;
+        self.are_block_txns_authorized(&block)?;
+        let receipts = self.pending_block_receipts(&block);
+        if &calc_receipts_root(&receipts) != block.receipts_root() {
+            return Err(BlockchainError::IncorrectReceiptsRoot.into());
+        }
         // check if block is signed
         // check if entry exists -> if not, then insert
-        Ok(self.blocks.entry(block.id_key()).or_insert(block))
+        self.persist_block(&block, &receipts)?;
+        self.store_receipts(&block, receipts);
+        self.snapshot_state(&block);
+        self.index_block(&block);
+        let block_id = block.id_key();
+        self.blocks.entry(block_id).or_insert(block);
+        self.prune_history();
+
+        Ok(self.blocks.get_mut(&block_id).unwrap())
+    }
+    /// ### Replay a block built elsewhere (i.e. received from a peer) onto the blockchain.
+    ///
+    /// Unlike `add_block()`, the block's txns have not been applied to `accounts` yet.
+    /// - validate block (see `add_block()`)
+    /// - validate block extends the chain's tip - height and `prev_block_id` (see `is_next_block()`)
+    /// - execute its txns on a copy of the accounts
+    /// - validate the resulting state and receipts against the block's `state_root` and `receipts_root`
+    /// - write the block and the resulting state to the backend, if the chain is stored
+    /// - commit the new state, store the receipts and add the block
+    /// - prune the history the chain's `PruningMode` doesn't keep
+    pub fn replay_block(&mut self, block: Block) -> Result<&mut Block> {
+        block.is_valid(block.leader())?;
+        block.is_for_chain(&self.chain_id)?;
+        block.is_within_capacity(&self.params)?;
+        self.is_height_free(&block)?;
+        self.is_next_block(&block)?;
+        self.are_block_txns_authorized(&block)?;
+
+        let (accounts, receipts) = self.execute_block(&block)?;
+        if &accounts.state_root() != block.state_root() {
+            return Err(BlockchainError::IncorrectStateRoot.into());
+        }
+        if &calc_receipts_root(&receipts) != block.receipts_root() {
+            return Err(BlockchainError::IncorrectReceiptsRoot.into());
+        }
+        let prev_accounts = std::mem::replace(&mut self.accounts, accounts);
+        if let Err(e) = self.persist_block(&block, &receipts) {
+            self.accounts = prev_accounts;
+            return Err(e);
+        }
+
+        self.store_receipts(&block, receipts);
+        self.snapshot_state(&block);
+        self.index_block(&block);
+        let block_id = block.id_key();
+        self.blocks.entry(block_id).or_insert(block);
+        self.prune_history();
+
+        Ok(self.blocks.get_mut(&block_id).unwrap())
+    }
+    /// ### Roll the chain back to the block at `height`, i.e. to switch to another fork (reorg).
+    ///
+    /// Removes every block above `height` - along with its receipts, state snapshot and txn/address index entries -
+    /// and restores the accounts to their state after the block at `height`.\
+    /// Receipts of txns executed but not yet in a block are dropped. The rollback is written to the backend, if the chain is stored.\
+    /// The state at `height` must still be kept (see `PruningMode`) - `BlockchainError::HistoryPruned`.
+    ///
+    /// Returns the removed blocks, highest first, so their txns can be returned to the txn pool.
+    pub fn rollback_to(&mut self, height: u128) -> Result<Vec<Block>> {
+        let block_id = *self
+            .heights
+            .get(&height)
+            .ok_or(BlockchainError::UnknownHeight(height))?;
+        let accounts = self.state_at(&block_id)?;
+        if let Some(backend) = &mut self.backend {
+            backend.rollback_to(height)?;
+            backend.reset_state(height, block_id, &accounts)?;
+        }
+
+        let mut removed = vec![];
+        while let Some((&tip_height, &tip_id)) = self.heights.iter().next_back() {
+            if tip_height <= height {
+                break;
+            }
+            self.heights.remove(&tip_height);
+            self.states.remove(&tip_height);
+            self.receipts.remove(&tip_id);
+            let block = self.blocks.remove(&tip_id).unwrap();
+            for (key, txn) in block.txns() {
+                let location = self.txn_index.remove(key);
+                if let (Some(index), Some(location)) = (&mut self.address_index, location) {
+                    index.remove(txn, &location);
+                }
+            }
+            removed.push(block);
+        }
+        self.tip_state = accounts.clone();
+        self.accounts = accounts;
+        self.pending_receipts.clear();
+
+        Ok(removed)
+    }
+    /// ### Write a block, its receipts and the resulting account state (`accounts`) to the backend, if the chain is stored.
+    /// A block the backend already holds (i.e. replayed by `Blockchain::open_with_backend()`) only has its state written.
+    fn persist_block(&mut self, block: &Block, receipts: &BlockReceipts) -> Result<()> {
+        if let Some(backend) = &mut self.backend {
+            let height = *block.blockheight();
+            if height > backend.height() {
+                backend.append_block(block, receipts)?;
+            }
+            backend.commit_state(height, block.id(), &self.tip_state, &self.accounts)?;
+        }
+
+        Ok(())
+    }
+    /// ### Add a stored block without executing it - its receipts were stored with it (see `Blockchain::open_with_backend()`).
+    pub(super) fn restore_block(&mut self, block: Block, receipts: BlockReceipts) {
+        self.store_receipts(&block, receipts);
+        self.index_block(&block);
+        self.blocks.insert(block.id_key(), block);
+    }
+    /// ### Record a block's height and the location of its txns.
+    pub(super) fn index_block(&mut self, block: &Block) {
+        let block_id = block.id();
+        let height = *block.blockheight();
+        self.heights.insert(height, block_id);
+        for (position, key) in block.txns().keys().enumerate() {
+            let location = TxnLocation {
+                block_id,
+                height,
+                position: position as u64,
+            };
+            if let Some(index) = &mut self.address_index {
+                index.insert(&block.txns()[key], &location);
+            }
+            self.txn_index.insert(key.clone(), location);
+        }
+    }
+    /// ### Collect the pending receipts of a block's txns, in txn order.
+    fn pending_block_receipts(&self, block: &Block) -> BlockReceipts {
+        block
+            .txns()
+            .keys()
+            .filter_map(|key| Some((key.clone(), self.pending_receipts.get(key)?.clone())))
+            .collect()
+    }
+    /// ### Store a block's receipts, dropping them from the pending receipts.
+    pub(super) fn store_receipts(&mut self, block: &Block, receipts: BlockReceipts) {
+        for key in receipts.keys() {
+            self.pending_receipts.remove(key);
+        }
+        self.receipts.entry(block.id_key()).or_insert(receipts);
+    }
+    /// ### Record the current account state as the state after `block` - as the changes since the state after the tip.
+    pub(super) fn snapshot_state(&mut self, block: &Block) {
+        let height = *block.blockheight();
+        if self.states.contains_key(&height) {
+            return;
+        }
+        let changes = self.accounts.changes_since(&self.tip_state);
+        if self.states.is_empty() {
+            self.state_base = self.accounts.clone();
+        }
+        self.tip_state.apply_changes(changes.clone());
+        self.states.insert(height, changes);
     }
 }
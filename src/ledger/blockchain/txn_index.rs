@@ -0,0 +1,62 @@
+use crate::ledger::{
+    block::block_id::BlockId,
+    blockchain::{error::BlockchainError, Blockchain},
+    txn::{txn_id::TxnId, Txn},
+    txn_pool::TxnMapKey,
+};
+use serde::{Deserialize, Serialize};
+
+/// ## Where a txn sits on the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnLocation {
+    /// Block containing the txn
+    pub block_id: BlockId,
+    /// Height of that block
+    pub height: u128,
+    /// Position of the txn within the block (see `Block::txn_proof()`)
+    pub position: u64,
+}
+
+/// ## A txn found on the chain, with its location and confirmation depth.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfirmedTxn<'a> {
+    pub txn: &'a Txn,
+    pub location: TxnLocation,
+    /// Number of blocks from the txn's block up to the tip, inclusive - 1 when in the latest block
+    pub confirmations: u128,
+}
+
+impl Blockchain {
+    /// ### Get the location of a txn on the chain.
+    pub fn txn_location(&self, txn_id: &TxnId) -> Option<&TxnLocation> {
+        self.txn_index.get(&TxnMapKey::from(*txn_id))
+    }
+    /// ### Find a txn on the chain, along with its block and confirmation depth - `None` if it isn't on the chain.
+    ///
+    /// `BlockchainError::HistoryPruned` if the txn is on the chain, but its block's body is no longer kept (see `PruningMode`).
+    pub fn find_txn(&self, txn_id: &TxnId) -> Result<Option<ConfirmedTxn<'_>>, BlockchainError> {
+        self.find_txn_by_key(&TxnMapKey::from(*txn_id))
+    }
+    /// ### Same as `find_txn()`, by the txn's map key.
+    pub(super) fn find_txn_by_key(
+        &self,
+        key: &TxnMapKey,
+    ) -> Result<Option<ConfirmedTxn<'_>>, BlockchainError> {
+        let location = match self.txn_index.get(key) {
+            Some(location) => *location,
+            None => return Ok(None),
+        };
+        if location.height <= self.pruned_height {
+            return Err(BlockchainError::HistoryPruned(location.height));
+        }
+
+        Ok(self
+            .block(&location.block_id)
+            .and_then(|block| block.txns().get(key))
+            .map(|txn| ConfirmedTxn {
+                txn,
+                location,
+                confirmations: self.height() - location.height + 1,
+            }))
+    }
+}
@@ -1,6 +1,22 @@
-use crate::ledger::block::{block_id::BlockId, Block};
+use crate::{
+    accounts::accounts::AccountChanges,
+    ledger::{
+        block::{block_id::BlockId, Block},
+        blockchain::txn_index::TxnLocation,
+        receipt::BlockReceipts,
+        txn_pool::TxnMapKey,
+    },
+};
 use std::collections::BTreeMap;
 
 /// ### Lookup type for the `blocks` map a string
 pub type BlockMapKey = BlockId;
 pub type BlockMap = BTreeMap<BlockMapKey, Block>;
+/// ### Changes each block made to the account state, queriable by height - see `Blockchain::state_at()`.
+pub type StateMap = BTreeMap<u128, AccountChanges>;
+/// ### Receipts for each block's txns, queriable by block ID.
+pub type ReceiptMap = BTreeMap<BlockMapKey, BlockReceipts>;
+/// ### Block IDs on the chain, queriable by height.
+pub type HeightMap = BTreeMap<u128, BlockMapKey>;
+/// ### Location of each txn on the chain, queriable by txn ID.
+pub type TxnIndex = BTreeMap<TxnMapKey, TxnLocation>;
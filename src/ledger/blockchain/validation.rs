@@ -1,4 +1,13 @@
-use crate::ledger::{block::Block, blockchain::Blockchain};
+use crate::{
+    accounts::accounts::Accounts,
+    ledger::{
+        block::Block,
+        blockchain::{error::BlockchainError, Blockchain},
+        receipt::BlockReceipts,
+        txn::{error::TxnError, Txn},
+        Result,
+    },
+};
 
 impl Blockchain {
     /// ### Check if the current blockheight is valid
@@ -17,8 +26,73 @@ impl Blockchain {
 
     /// ### Check if a given block is the genesis block on current instance of blockchain.
     pub fn is_genesis_block(&self, block: &Block) -> bool {
-        let genesis = self.blocks().values().next().unwrap();
+        let genesis = self.block_at(1).unwrap();
 
         block.id() == genesis.calc_id() && block.calc_id() == genesis.id()
     }
+
+    /// ### Check that no other block is already at the block's height.
+    pub fn is_height_free(&self, block: &Block) -> std::result::Result<(), BlockchainError> {
+        match self.heights.get(block.blockheight()) {
+            Some(block_id) if block_id != &block.id() => {
+                Err(BlockchainError::ConflictingBlock(*block.blockheight()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// ### Check a block is the next block on the chain - at the height above the tip, linked to it by `prev_block_id`.
+    pub fn is_next_block(&self, block: &Block) -> std::result::Result<(), BlockchainError> {
+        let tip = self.height();
+        let height = *block.blockheight();
+        if height != tip.saturating_add(1) {
+            return Err(BlockchainError::InvalidBlockHeight {
+                expected: tip.saturating_add(1),
+                found: height,
+            });
+        }
+        let tip_id = self
+            .heights
+            .get(&tip)
+            .ok_or(BlockchainError::UnknownHeight(tip))?;
+        if block.prev_block_id() != tip_id {
+            return Err(BlockchainError::InvalidPrevBlockId(height));
+        }
+
+        Ok(())
+    }
+
+    /// ### Check if a txn is authorized by its sending account.
+    ///
+    /// - Multi-signature accounts: the txn must carry enough valid partial signatures to meet the account's policy;
+    /// - All other accounts: the txn signature must be valid for `pbkey_send`.
+    pub fn is_txn_authorized(&self, txn: &Txn) -> std::result::Result<(), TxnError> {
+        match self.accounts.multisig_policy(&txn.pbkey_send().into()) {
+            Some(policy) => txn.is_multisig_valid(policy),
+            None => txn.is_signature_valid(txn.pbkey_send()),
+        }
+    }
+
+    /// ### Check that every txn in a block is authorized by its sending account.
+    pub fn are_block_txns_authorized(&self, block: &Block) -> std::result::Result<(), TxnError> {
+        for txn in block.txns().values() {
+            self.is_txn_authorized(txn)?;
+        }
+
+        Ok(())
+    }
+
+    /// ### Execute a block's txns on a copy of the current accounts.
+    ///
+    /// Returns the resulting accounts and the txns' receipts, leaving the blockchain's state untouched.
+    pub fn execute_block(&self, block: &Block) -> Result<(Accounts, BlockReceipts)> {
+        let mut accounts = self.accounts.clone();
+        let mut receipts = BlockReceipts::new();
+        for (key, txn) in block.txns().iter() {
+            let receipt = accounts.execute_txn(txn, &self.params)?;
+            receipts.insert(key.clone(), receipt);
+        }
+
+        Ok((accounts, receipts))
+    }
 }
@@ -0,0 +1,88 @@
+use crate::utils::encoding::EncodingError;
+use {
+    serde::{Deserialize, Serialize},
+    std::{fmt, str::FromStr},
+};
+
+/// Chain id used when none is configured (i.e. `Blockchain::new()`) - a local development chain.
+pub const DEFAULT_CHAIN_ID: &str = "posbc-devnet";
+/// Maximum length of a chain id, in bytes.
+pub const MAX_CHAIN_ID_LEN: usize = 64;
+
+/// ## Name of the network a txn or block belongs to, i.e. "posbc-testnet".
+///
+/// Part of every `TxnHeader` and `BlockHeader`, and of the signing context (see `signing_ctx()`),
+/// so a signature made for one network is not valid on another.\
+/// 1 to `MAX_CHAIN_ID_LEN` ASCII letters, digits, `-`, `_` or `.`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ChainId(String);
+
+impl ChainId {
+    /// ### Get the chain id as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+    /// ### Get the chain id as bytes - as written in the canonical encodings.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+    /// ### Derive the signing context for this chain from a base context (i.e. `TXN_SIGNATURE_CTX`).
+    ///
+    /// `blake3(ctx || u32 BE len(chain_id) || chain_id)` - same length as the base context,
+    /// so the signed message layout is unchanged.
+    pub fn signing_ctx(&self, ctx: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(ctx);
+        hasher.update(&(self.0.len() as u32).to_be_bytes());
+        hasher.update(self.as_bytes());
+
+        *hasher.finalize().as_bytes()
+    }
+}
+impl Default for ChainId {
+    fn default() -> Self {
+        Self(DEFAULT_CHAIN_ID.to_string())
+    }
+}
+impl fmt::Display for ChainId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl TryFrom<String> for ChainId {
+    type Error = EncodingError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let is_valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+        if value.is_empty() || value.len() > MAX_CHAIN_ID_LEN || !value.chars().all(is_valid_char) {
+            return Err(EncodingError::InvalidValue(
+                "chain id must be 1-64 ASCII letters, digits, '-', '_' or '.'",
+            ));
+        }
+
+        Ok(Self(value))
+    }
+}
+impl TryFrom<&[u8]> for ChainId {
+    type Error = EncodingError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let value = String::from_utf8(value.to_vec())
+            .map_err(|_| EncodingError::InvalidValue("chain id is not valid UTF-8"))?;
+
+        Self::try_from(value)
+    }
+}
+impl FromStr for ChainId {
+    type Err = EncodingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.to_string())
+    }
+}
+impl From<ChainId> for String {
+    fn from(value: ChainId) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,95 @@
+use crate::ledger::{
+    block::{error::BlockError, Block},
+    txn::{error::TxnError, TxnType},
+};
+use serde::{Deserialize, Serialize};
+
+/// Gas charged for a `TxnType::Transfer` txn.
+pub const TRANSFER_GAS: u64 = 1_000;
+/// Default maximum gas used by all txns in a block - 1000 transfers.
+pub const DEFAULT_BLOCK_GAS_LIMIT: u64 = 1_000_000;
+/// Default maximum encoded size of a block, in bytes - 1 MiB.
+pub const DEFAULT_BLOCK_BYTE_LIMIT: u64 = 1 << 20;
+/// Default price per unit of gas - txns are free unless configured otherwise.
+pub const DEFAULT_GAS_PRICE: u128 = 0;
+/// Highest price per unit of gas a genesis spec may set - so a txn's fee always fits in a `u128`.
+pub const MAX_GAS_PRICE: u128 = u64::MAX as u128;
+
+/// ## Chain-wide parameters every node must agree on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainParams {
+    /// Gas charged for a transfer txn
+    transfer_gas: u64,
+    /// Maximum gas used by all txns in a block
+    block_gas_limit: u64,
+    /// Maximum encoded size of a block, in bytes (see `Block::size()`)
+    block_byte_limit: u64,
+    /// Price per unit of gas - fees are burned
+    gas_price: u128,
+}
+
+impl ChainParams {
+    pub fn new(
+        transfer_gas: u64,
+        block_gas_limit: u64,
+        block_byte_limit: u64,
+        gas_price: u128,
+    ) -> Self {
+        Self {
+            transfer_gas,
+            block_gas_limit,
+            block_byte_limit,
+            gas_price,
+        }
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get the gas charged for a txn of the given type.
+    pub fn gas_cost(&self, txn_type: &TxnType) -> u64 {
+        match txn_type {
+            TxnType::Transfer => self.transfer_gas,
+        }
+    }
+    /// ### Get property `ChainParams.block_gas_limit`.
+    pub fn block_gas_limit(&self) -> u64 {
+        self.block_gas_limit
+    }
+    /// ### Get property `ChainParams.block_byte_limit`.
+    pub fn block_byte_limit(&self) -> u64 {
+        self.block_byte_limit
+    }
+    /// ### Get property `ChainParams.gas_price`.
+    pub fn gas_price(&self) -> u128 {
+        self.gas_price
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Get the fee charged for a txn of the given type - gas x gas price.
+    /// `TxnError::FeeOverflow` if it doesn't fit in a `u128`.
+    pub fn fee(&self, txn_type: &TxnType) -> Result<u128, TxnError> {
+        (self.gas_cost(txn_type) as u128)
+            .checked_mul(self.gas_price)
+            .ok_or(TxnError::FeeOverflow)
+    }
+    /// ### Get the gas used by all txns in a block.
+    /// `BlockError::GasOverflow` if it doesn't fit in a `u64`.
+    pub fn block_gas(&self, block: &Block) -> Result<u64, BlockError> {
+        block.txns().values().try_fold(0u64, |gas, txn| {
+            gas.checked_add(self.gas_cost(txn.txn_type()))
+                .ok_or(BlockError::GasOverflow)
+        })
+    }
+}
+
+impl Default for ChainParams {
+    fn default() -> Self {
+        Self::new(
+            TRANSFER_GAS,
+            DEFAULT_BLOCK_GAS_LIMIT,
+            DEFAULT_BLOCK_BYTE_LIMIT,
+            DEFAULT_GAS_PRICE,
+        )
+    }
+}
@@ -0,0 +1,15 @@
+/// ## Errors from decoding or verifying keys and signatures.
+///
+/// Returned by the fallible conversions between this crate's byte wrappers (`PbKey`, `TxnSignature`, `BlockSignature`)
+/// and the crypto libraries' types, so malformed input from a peer is an error rather than a panic.
+#[derive(Debug, thiserror::Error)]
+pub enum CryptoError {
+    #[error("CryptoError::InvalidLength- expected {expected} bytes, found {found}")]
+    InvalidLength { expected: usize, found: usize },
+    #[error("CryptoError::InvalidSignature- {0}")]
+    InvalidSignature(#[from] ed25519_dalek::SignatureError),
+    #[error("CryptoError::InvalidPublicKey- {0}")]
+    InvalidPublicKey(String),
+    #[error("CryptoError::UnsupportedKeyType- Only ed25519 keys are supported")]
+    UnsupportedKeyType,
+}
@@ -1,12 +1,32 @@
-use serde::{Deserialize, Serialize};
+use crate::{
+    ledger::error::CryptoError,
+    utils::address::{decode_check, encode_check, AddressError, ADDRESS_VERSION},
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::{fmt, str::FromStr},
+};
 // aliased types
 /// General reference regardless of which lib we use
 pub type HashAlgo = ed25519_dalek::Sha512;
 pub type KP = ed25519_dalek::Keypair;
 pub type Result<T> = std::result::Result<T, anyhow::Error>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct PbKey(pub [u8; 32]);
+/// Text address format: base58check with `ADDRESS_VERSION`.
+impl fmt::Display for PbKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&encode_check(ADDRESS_VERSION, &self.0))
+    }
+}
+impl FromStr for PbKey {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self(decode_check(ADDRESS_VERSION, s)?))
+    }
+}
 impl From<PbKey> for [u8; 32] {
     fn from(value: PbKey) -> Self {
         value.0
@@ -27,33 +47,41 @@ impl From<ed25519_dalek::PublicKey> for PbKey {
         Self(value.to_bytes())
     }
 }
-impl From<PbKey> for ed25519_dalek::PublicKey {
-    fn from(value: PbKey) -> Self {
-        ed25519_dalek::PublicKey::from_bytes(&value.0).unwrap()
+impl TryFrom<PbKey> for ed25519_dalek::PublicKey {
+    type Error = CryptoError;
+
+    fn try_from(value: PbKey) -> std::result::Result<Self, Self::Error> {
+        ed25519_dalek::PublicKey::try_from(&value)
     }
 }
-impl From<&PbKey> for ed25519_dalek::PublicKey {
-    fn from(value: &PbKey) -> Self {
-        ed25519_dalek::PublicKey::from_bytes(&value.0).unwrap()
+impl TryFrom<&PbKey> for ed25519_dalek::PublicKey {
+    type Error = CryptoError;
+
+    fn try_from(value: &PbKey) -> std::result::Result<Self, Self::Error> {
+        ed25519_dalek::PublicKey::from_bytes(&value.0)
+            .map_err(|e| CryptoError::InvalidPublicKey(e.to_string()))
     }
 }
 
-impl From<PbKey> for libp2p::identity::PublicKey {
-    fn from(value: PbKey) -> Self {
-        let pk = libp2p::identity::ed25519::PublicKey::decode(&value.0).unwrap();
-        let pk: libp2p::identity::PublicKey = libp2p::identity::PublicKey::Ed25519(pk);
+impl TryFrom<PbKey> for libp2p::identity::PublicKey {
+    type Error = CryptoError;
+
+    fn try_from(value: PbKey) -> std::result::Result<Self, Self::Error> {
+        let pk = libp2p::identity::ed25519::PublicKey::decode(&value.0)
+            .map_err(|e| CryptoError::InvalidPublicKey(e.to_string()))?;
 
-        pk
+        Ok(libp2p::identity::PublicKey::Ed25519(pk))
     }
 }
 
-impl From<libp2p::identity::PublicKey> for PbKey {
+impl TryFrom<libp2p::identity::PublicKey> for PbKey {
+    type Error = CryptoError;
+
     #[allow(unreachable_patterns)]
-    fn from(value: libp2p::identity::PublicKey) -> Self {
-        let pk = match value {
-            libp2p::identity::PublicKey::Ed25519(pk) => pk,
-            _ => panic!("Unsupported public key type"),
-        };
-        Self(pk.encode())
+    fn try_from(value: libp2p::identity::PublicKey) -> std::result::Result<Self, Self::Error> {
+        match value {
+            libp2p::identity::PublicKey::Ed25519(pk) => Ok(Self(pk.encode())),
+            _ => Err(CryptoError::UnsupportedKeyType),
+        }
     }
 }
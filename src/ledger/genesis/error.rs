@@ -0,0 +1,21 @@
+use crate::{accounts::account::AccountId, ledger::general::PbKey};
+
+#[derive(Debug, thiserror::Error)]
+pub enum GenesisError {
+    #[error("GenesisError::Io- Could not read genesis file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("GenesisError::Parse- Invalid genesis file: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("GenesisError::NoValidators- At least one validator is required")]
+    NoValidators,
+    #[error("GenesisError::ZeroPower- Validator {0} has no voting power")]
+    ZeroPower(PbKey),
+    #[error("GenesisError::DuplicateValidator- Validator {0} is listed more than once")]
+    DuplicateValidator(PbKey),
+    #[error("GenesisError::DuplicateAllocation- Account {0} is allocated more than once")]
+    DuplicateAllocation(AccountId),
+    #[error("GenesisError::SupplyOverflow- Total allocated balance overflows u128")]
+    SupplyOverflow,
+    #[error("GenesisError::InvalidParams- {0}")]
+    InvalidParams(&'static str),
+}
@@ -0,0 +1,211 @@
+pub mod error;
+
+use crate::{
+    accounts::{
+        account::{Account, AccountId},
+        accounts::Accounts,
+    },
+    ledger::{
+        block::Block,
+        chain_id::ChainId,
+        chain_params::{ChainParams, MAX_GAS_PRICE},
+        general::PbKey,
+        genesis::error::GenesisError,
+        txn::TxnType,
+        validator_set::ValidatorSet,
+    },
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeSet, fs, path::Path},
+};
+
+/// ## Initial balance of an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisAllocation {
+    /// Account address (see `AccountId`'s `Display`)
+    #[serde(with = "address")]
+    pub acct_id: AccountId,
+    pub balance: u128,
+}
+
+/// ## Initial validator and its voting power.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisValidator {
+    /// Validator address (see `PbKey`'s `Display`)
+    #[serde(with = "address")]
+    pub pbkey: PbKey,
+    pub power: u128,
+}
+
+/// ## Everything needed to start a chain, shared by every node (i.e. as a JSON file).
+///
+/// Every node derives the same genesis block and initial `Accounts` from the same spec -
+/// a node joins a chain by loading its spec, and can check a peer's genesis block against it.
+///
+/// Txns and blocks carry the spec's `chain_id`, so they are only valid on this chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisSpec {
+    /// Name of the chain, i.e. "posbc-devnet"
+    chain_id: ChainId,
+    /// Genesis block timestamp, in milliseconds since the unix epoch
+    timestamp: u64,
+    /// Initial account balances
+    #[serde(default)]
+    allocations: Vec<GenesisAllocation>,
+    /// Initial validator set
+    validators: Vec<GenesisValidator>,
+    /// Chain parameters - defaults if missing from the file
+    #[serde(default)]
+    params: ChainParams,
+}
+
+impl GenesisSpec {
+    pub fn new(
+        chain_id: ChainId,
+        timestamp: u64,
+        allocations: Vec<GenesisAllocation>,
+        validators: Vec<GenesisValidator>,
+        params: ChainParams,
+    ) -> Self {
+        Self {
+            chain_id,
+            timestamp,
+            allocations,
+            validators,
+            params,
+        }
+    }
+    /// ### Load and validate a genesis spec from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, GenesisError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+    /// ### Parse and validate a genesis spec from JSON.
+    pub fn from_json(json: &str) -> Result<Self, GenesisError> {
+        let spec: Self = serde_json::from_str(json)?;
+        spec.validate()?;
+
+        Ok(spec)
+    }
+    /// ### Serialize the genesis spec to (pretty-printed) JSON.
+    pub fn to_json(&self) -> Result<String, GenesisError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `GenesisSpec.chain_id`.
+    pub fn chain_id(&self) -> &ChainId {
+        &self.chain_id
+    }
+    /// ### Get property `GenesisSpec.timestamp`.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+    /// ### Get property `GenesisSpec.allocations`.
+    pub fn allocations(&self) -> &[GenesisAllocation] {
+        &self.allocations
+    }
+    /// ### Get property `GenesisSpec.validators`.
+    pub fn validators(&self) -> &[GenesisValidator] {
+        &self.validators
+    }
+    /// ### Get property `GenesisSpec.params`.
+    pub fn params(&self) -> &ChainParams {
+        &self.params
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Check the spec describes a usable chain.
+    /// - at least one validator, each with voting power, none listed twice
+    /// - no account allocated twice, and the total supply fits in a `u128`
+    /// - non-zero block limits, that fit a transfer, and a gas price up to `MAX_GAS_PRICE`
+    pub fn validate(&self) -> Result<(), GenesisError> {
+        if self.validators.is_empty() {
+            return Err(GenesisError::NoValidators);
+        }
+        let mut validators = BTreeSet::new();
+        for validator in self.validators.iter() {
+            if validator.power == 0 {
+                return Err(GenesisError::ZeroPower(validator.pbkey));
+            }
+            if !validators.insert(validator.pbkey) {
+                return Err(GenesisError::DuplicateValidator(validator.pbkey));
+            }
+        }
+        let mut accts = BTreeSet::new();
+        let mut supply: u128 = 0;
+        for allocation in self.allocations.iter() {
+            if !accts.insert(allocation.acct_id) {
+                return Err(GenesisError::DuplicateAllocation(allocation.acct_id));
+            }
+            supply = supply
+                .checked_add(allocation.balance)
+                .ok_or(GenesisError::SupplyOverflow)?;
+        }
+        let params = &self.params;
+        if params.block_gas_limit() == 0 || params.block_byte_limit() == 0 {
+            return Err(GenesisError::InvalidParams("block limits must be non-zero"));
+        }
+        if params.gas_cost(&TxnType::Transfer) > params.block_gas_limit() {
+            return Err(GenesisError::InvalidParams(
+                "a transfer's gas must fit in the block gas limit",
+            ));
+        }
+        if params.gas_price() > MAX_GAS_PRICE {
+            return Err(GenesisError::InvalidParams(
+                "gas price must be at most MAX_GAS_PRICE",
+            ));
+        }
+
+        Ok(())
+    }
+    /// ### Build the initial account state from the allocations.
+    pub fn accounts(&self) -> Accounts {
+        let mut accounts = Accounts::new();
+        for allocation in self.allocations.iter() {
+            accounts.add_acct(Account::new(&allocation.acct_id, Some(allocation.balance)));
+        }
+
+        accounts
+    }
+    /// ### Build the initial validator set.
+    pub fn validator_set(&self) -> ValidatorSet {
+        ValidatorSet::new(self.validators.iter().map(|v| (v.pbkey, v.power)))
+    }
+    /// ### Build the genesis block - committing to the initial state and validator set.
+    pub fn block(&self) -> Block {
+        Block::new_genesis_at(
+            self.chain_id.clone(),
+            self.accounts().state_root(),
+            &self.validator_set(),
+            self.timestamp,
+        )
+    }
+}
+
+/// Serialize keys and account ids by their text address, so genesis files are human-editable.
+mod address {
+    use {
+        serde::{de, Deserialize, Deserializer, Serializer},
+        std::{fmt::Display, str::FromStr},
+    };
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
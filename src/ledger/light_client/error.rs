@@ -0,0 +1,17 @@
+use crate::ledger::{block::error::BlockError, general::PbKey};
+
+#[derive(Debug, thiserror::Error)]
+pub enum LightClientError {
+    #[error("LightClientError::InvalidHeight- expected block {expected}, found {found}")]
+    InvalidHeight { expected: u128, found: u128 },
+    #[error("LightClientError::UnlinkedHeader- Header does not extend the latest verified header")]
+    UnlinkedHeader,
+    #[error("LightClientError::UnknownLeader- Leader {0} is not in the validator set")]
+    UnknownLeader(PbKey),
+    #[error("LightClientError::MissingValidatorSet- Header hands over to a validator set that was not provided")]
+    MissingValidatorSet,
+    #[error("LightClientError::ValidatorSetMismatch- Validator set does not match the header's validator set hash")]
+    ValidatorSetMismatch,
+    #[error("LightClientError::Block- {0}")]
+    Block(#[from] BlockError),
+}
@@ -0,0 +1,42 @@
+use crate::ledger::block::{
+    block_header::BlockHeader, block_signature::BlockSignature, finality::FinalityCertificate,
+};
+use serde::{Deserialize, Serialize};
+
+/// ## Everything a light client downloads per block.
+///
+/// The header with its leader signature and finality certificate - no transactions or state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightBlock {
+    header: BlockHeader,
+    /// The leader's signature over the block id
+    signature: BlockSignature,
+    finality: FinalityCertificate,
+}
+
+impl LightBlock {
+    pub fn new(
+        header: BlockHeader,
+        signature: BlockSignature,
+        finality: FinalityCertificate,
+    ) -> Self {
+        Self {
+            header,
+            signature,
+            finality,
+        }
+    }
+
+    /// ### Get property `LightBlock.header`.
+    pub fn header(&self) -> &BlockHeader {
+        &self.header
+    }
+    /// ### Get property `LightBlock.signature`.
+    pub fn signature(&self) -> &BlockSignature {
+        &self.signature
+    }
+    /// ### Get property `LightBlock.finality`.
+    pub fn finality(&self) -> &FinalityCertificate {
+        &self.finality
+    }
+}
@@ -0,0 +1,137 @@
+pub mod error;
+pub mod light_block;
+
+use crate::{
+    accounts::proof::AccountProof,
+    ledger::{
+        block::{block_header::BlockHeader, block_id::BlockId, txn_proof::TxnInclusionProof},
+        light_client::{error::LightClientError, light_block::LightBlock},
+        validator_set::ValidatorSet,
+    },
+};
+use std::collections::BTreeMap;
+
+/// ## Client that follows the chain by headers only.
+///
+/// Starts from a trusted header (genesis or a checkpoint) and its validator set, then verifies each
+/// following header's leader signature and finality certificate, switching validator sets when a
+/// finalized header hands over to a new one.\
+/// Transactions and account state are checked with inclusion proofs against verified headers,
+/// so the client never holds blocks or `Accounts`.
+#[derive(Debug)]
+pub struct LightClient {
+    /// Verified headers, queriable by block id
+    headers: BTreeMap<BlockId, BlockHeader>,
+    /// Id of the most recent verified header
+    latest: BlockId,
+    /// Validator set finalizing the next header
+    validators: ValidatorSet,
+}
+
+impl LightClient {
+    /// ### Start from a trusted header and the validator set finalizing the header after it.
+    ///
+    /// If the trusted header hands over to a new validator set, `validators` must match it.
+    pub fn new(
+        trusted_header: BlockHeader,
+        validators: ValidatorSet,
+    ) -> Result<Self, LightClientError> {
+        if let Some(validator_set_hash) = trusted_header.validator_set_hash() {
+            if validator_set_hash != &validators.hash() {
+                return Err(LightClientError::ValidatorSetMismatch);
+            }
+        }
+        let latest = trusted_header.calc_id();
+
+        Ok(Self {
+            headers: BTreeMap::from([(latest, trusted_header)]),
+            latest,
+            validators,
+        })
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get a verified header.
+    pub fn header(&self, block_id: &BlockId) -> Option<&BlockHeader> {
+        self.headers.get(block_id)
+    }
+    /// ### Get the most recent verified header.
+    pub fn latest_header(&self) -> &BlockHeader {
+        &self.headers[&self.latest]
+    }
+    /// ### Get property `LightClient.validators`.
+    pub fn validators(&self) -> &ValidatorSet {
+        &self.validators
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Verify the next header and add it to the verified headers.
+    ///
+    /// 1. Assert the header is for the same chain as the trusted header - `Block`
+    /// 1. Assert the header extends the latest verified header - `InvalidHeight`, `UnlinkedHeader`
+    /// 1. Assert the leader is a validator - `UnknownLeader`
+    /// 1. Assert the leader's signature is valid - `Block`
+    /// 1. Assert the header is finalized by the current validator set - `Block`
+    /// 1. If the header hands over to a new validator set, assert `next_validators` matches it
+    ///    and switch to it - `MissingValidatorSet`, `ValidatorSetMismatch`
+    ///
+    /// Returns the header's block id.
+    pub fn verify_light_block(
+        &mut self,
+        light_block: LightBlock,
+        next_validators: Option<ValidatorSet>,
+    ) -> Result<BlockId, LightClientError> {
+        let header = light_block.header();
+        let latest = self.latest_header();
+        header.is_for_chain(latest.chain_id())?;
+        let expected_height = latest.blockheight() + 1;
+        if header.blockheight() != &expected_height {
+            return Err(LightClientError::InvalidHeight {
+                expected: expected_height,
+                found: *header.blockheight(),
+            });
+        }
+        if header.prev_block_id() != &self.latest {
+            return Err(LightClientError::UnlinkedHeader);
+        }
+
+        let leader = header.leader();
+        if !self.validators.contains(leader) {
+            return Err(LightClientError::UnknownLeader(*leader));
+        }
+        header.verify_signature(light_block.signature(), leader)?;
+
+        let block_id = header.calc_id();
+        light_block.finality().verify(&block_id, &self.validators)?;
+
+        if let Some(validator_set_hash) = header.validator_set_hash() {
+            let next_validators = next_validators.ok_or(LightClientError::MissingValidatorSet)?;
+            if validator_set_hash != &next_validators.hash() {
+                return Err(LightClientError::ValidatorSetMismatch);
+            }
+            self.validators = next_validators;
+        }
+
+        self.headers.insert(block_id, light_block.header().clone());
+        self.latest = block_id;
+
+        Ok(block_id)
+    }
+
+    /// ### Check that a txn is included in a verified block.
+    pub fn verify_txn(&self, block_id: &BlockId, proof: &TxnInclusionProof) -> bool {
+        match self.header(block_id) {
+            Some(header) => proof.verify(header),
+            None => false,
+        }
+    }
+    /// ### Check an account's state after a verified block.
+    pub fn verify_account(&self, block_id: &BlockId, proof: &AccountProof) -> bool {
+        match self.header(block_id) {
+            Some(header) => proof.verify(header),
+            None => false,
+        }
+    }
+}
@@ -1,8 +1,15 @@
 pub mod block;
 pub mod blockchain;
+pub mod chain_id;
+pub mod chain_params;
+pub mod error;
 pub mod general;
+pub mod genesis;
+pub mod light_client;
+pub mod receipt;
 pub mod txn;
 pub mod txn_pool;
+pub mod validator_set;
 pub mod wallet;
 
 pub use general::*;
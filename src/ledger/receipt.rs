@@ -0,0 +1,169 @@
+use crate::{
+    accounts::account::AccountId,
+    ledger::{txn::txn_id::TxnId, txn_pool::TxnMapKey},
+    utils::{encoding::CanonicalEncoder, hash::BlakeHash, merkle::MerkleTree},
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::collections::BTreeMap,
+};
+
+/// Canonical encoding version for `TxnReceipt` (see `TxnReceipt::serialize()`).
+pub const RECEIPT_VERSION: u8 = 1;
+
+/// ### Receipts for all txns in a block, in the same order as `BlockTxnMap`.
+pub type BlockReceipts = BTreeMap<TxnMapKey, TxnReceipt>;
+
+/// ## Outcome of executing a txn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReceiptStatus {
+    /// The transfer was applied
+    Success = 0,
+    /// The sender could pay the fee but not the amount - only the fee was charged
+    InsufficientBalance = 1,
+}
+
+/// ## State change emitted while executing a txn.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxnEvent {
+    /// `amt` moved from `from` to `to`
+    Transfer {
+        from: AccountId,
+        to: AccountId,
+        amt: u128,
+    },
+    /// Fee taken from the sender and burned (removed from supply)
+    FeeBurned { payer: AccountId, amt: u128 },
+    /// Account created by receiving its first transfer
+    AccountCreated { acct_id: AccountId },
+}
+
+/// ## An account's balance before and after a txn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceDelta {
+    pub acct_id: AccountId,
+    pub before: u128,
+    pub after: u128,
+}
+
+/// ## Result of executing a single txn, stored alongside its block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnReceipt {
+    txn_id: TxnId,
+    status: ReceiptStatus,
+    gas_used: u64,
+    /// Fee charged to the sender - `gas_used` x gas price
+    fee: u128,
+    /// Balance changes, ordered by account id
+    balance_deltas: Vec<BalanceDelta>,
+    /// Events, in the order they happened
+    events: Vec<TxnEvent>,
+}
+
+impl TxnReceipt {
+    pub fn new(
+        txn_id: TxnId,
+        status: ReceiptStatus,
+        gas_used: u64,
+        fee: u128,
+        balance_deltas: Vec<BalanceDelta>,
+        events: Vec<TxnEvent>,
+    ) -> Self {
+        Self {
+            txn_id,
+            status,
+            gas_used,
+            fee,
+            balance_deltas,
+            events,
+        }
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `TxnReceipt.txn_id`.
+    pub fn txn_id(&self) -> &TxnId {
+        &self.txn_id
+    }
+    /// ### Get property `TxnReceipt.status`.
+    pub fn status(&self) -> ReceiptStatus {
+        self.status
+    }
+    /// ### Check if the txn was applied.
+    pub fn is_success(&self) -> bool {
+        self.status == ReceiptStatus::Success
+    }
+    /// ### Get property `TxnReceipt.gas_used`.
+    pub fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+    /// ### Get property `TxnReceipt.fee`.
+    pub fn fee(&self) -> u128 {
+        self.fee
+    }
+    /// ### Get property `TxnReceipt.balance_deltas`.
+    pub fn balance_deltas(&self) -> &[BalanceDelta] {
+        &self.balance_deltas
+    }
+    /// ### Get property `TxnReceipt.events`.
+    pub fn events(&self) -> &[TxnEvent] {
+        &self.events
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Serialize receipt to bytes.
+    ///
+    /// Canonical encoding (see `CanonicalEncoder`), version `RECEIPT_VERSION`. Field order:
+    /// 1. `txn_id` - bytes
+    /// 1. `status` - u8
+    /// 1. `gas_used` - u64
+    /// 1. `fee` - u128
+    /// 1. `balance_deltas` - count, then per delta: `acct_id` bytes, `before` u128, `after` u128
+    /// 1. `events` - count, then per event: kind u8, then its fields in declaration order
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut encoder = CanonicalEncoder::new(RECEIPT_VERSION);
+        encoder
+            .put_bytes(&self.txn_id.0)
+            .put_u8(self.status as u8)
+            .put_u64(self.gas_used)
+            .put_u128(self.fee)
+            .put_len(self.balance_deltas.len());
+        for delta in self.balance_deltas.iter() {
+            encoder
+                .put_bytes(&delta.acct_id.0)
+                .put_u128(delta.before)
+                .put_u128(delta.after);
+        }
+        encoder.put_len(self.events.len());
+        for event in self.events.iter() {
+            match event {
+                TxnEvent::Transfer { from, to, amt } => encoder
+                    .put_u8(0)
+                    .put_bytes(&from.0)
+                    .put_bytes(&to.0)
+                    .put_u128(*amt),
+                TxnEvent::FeeBurned { payer, amt } => {
+                    encoder.put_u8(1).put_bytes(&payer.0).put_u128(*amt)
+                }
+                TxnEvent::AccountCreated { acct_id } => encoder.put_u8(2).put_bytes(&acct_id.0),
+            };
+        }
+
+        encoder.finish()
+    }
+    /// ### Hash of the receipt - its leaf in the block's receipts root.
+    pub fn hash(&self) -> BlakeHash {
+        blake3::hash(&self.serialize()).into()
+    }
+}
+
+/// ### Calculate the Merkle root over a block's receipts, in txn order.
+pub fn calc_receipts_root(receipts: &BlockReceipts) -> BlakeHash {
+    MerkleTree::new(
+        receipts
+            .values()
+            .map(|receipt| receipt.hash().as_bytes().to_owned()),
+    )
+    .root()
+}
@@ -1,3 +1,7 @@
 pub const TXN_MSG_CTX: &[u8; 6] = b"txn-v0";
+/// Version byte of the canonical `TxnHeader` encoding.
+pub const TXN_HEADER_VERSION: u8 = 1;
 pub const TXN_DIGEST_LEN: usize = 64;
 pub const TXN_SIGNATURE_CTX: &[u8; 32] = b"ed25519TxnSignatureCtx3bx029zk3c";
+/// Prefix (name + version) of the portable text encoding of a `Txn`.
+pub const TXN_EXPORT_PREFIX: &str = "posbc-txn-v0:";
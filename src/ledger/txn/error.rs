@@ -0,0 +1,27 @@
+use crate::ledger::{chain_id::ChainId, error::CryptoError, general::PbKey};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TxnError {
+    #[error("TxnError::EmptySignature- Invalid txn: No signature")]
+    EmptySignature,
+    #[error("TxnError::EmptyId- Invalid txn: No ID")]
+    EmptyId,
+    #[error("TxnError::IncorrectId- Incorrect ID")]
+    IncorrectId,
+    #[error("TxnError::InvalidSignature- {0}")]
+    InvalidSignature(ed25519_dalek::SignatureError),
+    #[error("TxnError::Crypto- {0}")]
+    Crypto(#[from] CryptoError),
+    #[error("TxnError::InvalidEncoding- {0}")]
+    InvalidEncoding(String),
+    #[error("TxnError::MismatchedTxn- Signatures belong to a different txn")]
+    MismatchedTxn,
+    #[error("TxnError::UnknownSigner- {0:?} is not a signer for this account")]
+    UnknownSigner(PbKey),
+    #[error("TxnError::ThresholdNotMet- {found} of {required} required signatures")]
+    ThresholdNotMet { required: u8, found: usize },
+    #[error("TxnError::WrongChainId- Txn is for chain {found}, expected {expected}")]
+    WrongChainId { expected: ChainId, found: ChainId },
+    #[error("TxnError::FeeOverflow- Txn fee (gas x gas price) overflows u128")]
+    FeeOverflow,
+}
@@ -0,0 +1,65 @@
+use crate::ledger::txn::{error::TxnError, Txn, TxnSignature, TXN_EXPORT_PREFIX};
+
+impl Txn {
+    /// ### Encode the transaction in a portable text format.
+    ///
+    /// Format: `TXN_EXPORT_PREFIX` followed by the url-safe base64 of the txn's JSON.\
+    /// Works for unsigned, signed and partially signed (multisig) txns, so a txn can be built on one machine,
+    /// signed on another (i.e. air-gapped), and brought back for broadcast.
+    pub fn export(&self) -> String {
+        let txn_bytes = serde_json::to_vec(self).expect("Error serializing transaction");
+
+        format!(
+            "{TXN_EXPORT_PREFIX}{}",
+            base64::encode_config(txn_bytes, base64::URL_SAFE_NO_PAD)
+        )
+    }
+
+    /// ### Decode a transaction from the portable text format (see `Txn::export()`).
+    ///
+    /// The decoded txn is not trusted:
+    /// - the id is recalculated from the header and must match;
+    /// - an attached signature must be valid for `pbkey_send`;
+    /// - attached multisig signatures must each be valid for their signer.
+    ///
+    /// Whether multisig signatures satisfy the account's policy is checked against account state, not here.
+    pub fn import(encoded: &str) -> std::result::Result<Self, TxnError> {
+        let encoded = encoded
+            .trim()
+            .strip_prefix(TXN_EXPORT_PREFIX)
+            .ok_or_else(|| TxnError::InvalidEncoding("Unknown txn encoding prefix".into()))?;
+        let txn_bytes = base64::decode_config(encoded, base64::URL_SAFE_NO_PAD)
+            .map_err(|e| TxnError::InvalidEncoding(e.to_string()))?;
+        let txn: Self = serde_json::from_slice(&txn_bytes)
+            .map_err(|e| TxnError::InvalidEncoding(e.to_string()))?;
+
+        // validate fields
+        match txn.id {
+            Some(id) if id == txn.calc_id() => {}
+            _ => return Err(TxnError::IncorrectId),
+        }
+        if let Some(signature) = &txn.signature {
+            txn.verify_signature(signature, txn.pbkey_send())?;
+        }
+        if let Some(multisig) = &txn.multisig {
+            for cosig in multisig.iter() {
+                txn.verify_signature(&cosig.signature, &cosig.signer)?;
+            }
+        }
+
+        Ok(txn)
+    }
+
+    /// ### Attach a signature produced elsewhere (i.e. on an offline signer).
+    ///
+    /// Signature must be valid for the txn's `pbkey_send`.
+    pub fn attach_signature(
+        &mut self,
+        signature: TxnSignature,
+    ) -> std::result::Result<(), TxnError> {
+        self.verify_signature(&signature, self.pbkey_send())?;
+        self.set_signature(signature);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::ledger::{
+    chain_id::ChainId,
     general::PbKey,
-    txn::{Txn, TxnHeader, TxnId, TxnSignature, TxnType},
+    txn::{Txn, TxnHeader, TxnId, TxnMultisig, TxnSignature, TxnType},
     txn_pool::TxnMapKey,
 };
 use std::borrow::BorrowMut;
@@ -11,6 +12,10 @@ impl Txn {
     pub fn id(&self) -> &TxnId {
         self.id.as_ref().unwrap()
     }
+    /// ### Whether property `Txn.id` is set - txns decoded from the wire may lack one.
+    pub fn has_id(&self) -> bool {
+        self.id.is_some()
+    }
     /// ### Get `TxnMap` key type (derived from TxnId).
     pub fn id_key(&self) -> TxnMapKey {
         self.id.as_ref().unwrap().into()
@@ -19,6 +24,11 @@ impl Txn {
     pub fn signature(&self) -> &TxnSignature {
         self.signature.as_ref().unwrap()
     }
+    /// ### Get property `Txn.multisig`.
+    /// Returns `None` if no partial signatures have been collected.
+    pub fn multisig(&self) -> Option<&TxnMultisig> {
+        self.multisig.as_ref()
+    }
     /// ### Get property `Txn.header`.
     pub fn header(&self) -> &TxnHeader {
         &self.header
@@ -29,6 +39,10 @@ impl Txn {
     }
 
     // Getters for header properties
+    /// ### Get property `Txn.chain_id`.
+    pub fn chain_id(&self) -> &ChainId {
+        self.header.chain_id()
+    }
     /// ### Get property `Txn.amt`.
     pub fn amt(&self) -> &u128 {
         self.header.amt()
@@ -1,17 +1,26 @@
 pub mod constants;
+pub mod error;
+mod export;
 mod getters;
 pub mod txn_header;
 pub mod txn_id;
+pub mod txn_multisig;
 pub mod txn_signature;
 pub mod types;
 mod utils;
+mod validation;
 
-use crate::ledger::{
-    general::PbKey,
-    txn::{
-        constants::*, txn_header::TxnHeader, txn_id::TxnId, txn_signature::TxnSignature, types::*,
+use crate::{
+    ledger::{
+        chain_id::ChainId,
+        general::PbKey,
+        txn::{
+            constants::*, txn_header::TxnHeader, txn_id::TxnId, txn_multisig::TxnMultisig,
+            txn_signature::TxnSignature, types::*,
+        },
+        wallet::Wallet,
     },
-    wallet::Wallet,
+    utils::encoding::EncodingError,
 };
 use {
     chrono::prelude::*,
@@ -25,6 +34,16 @@ use {
 pub enum TxnType {
     Transfer = 1,
 }
+impl TryFrom<u8> for TxnType {
+    type Error = EncodingError;
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(TxnType::Transfer),
+            _ => Err(EncodingError::InvalidValue("Unknown txn type")),
+        }
+    }
+}
 impl fmt::Display for TxnType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Debug::fmt(self, f)
@@ -49,7 +68,6 @@ impl fmt::Display for TxnType {
 ///
 /// @todo make all fields private, making all accessible fields available thru getter methods.;\
 /// @todo generalize this and abstract all separate types.;\
-/// @todo create lookup enum for `gas_value` for each `Txn` type;\
 /// @todo create `TxnHeader` struct to hold all fields except `id` and `signature`;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Txn {
@@ -59,12 +77,15 @@ pub struct Txn {
     id: Option<TxnId>,
     /// Ecdsa signature as byte array
     signature: Option<TxnSignature>,
+    /// Partial signatures, when sent from a multi-signature account
+    multisig: Option<TxnMultisig>,
 }
 
 impl Txn {
     /// ### Transaction constructor fxn
-    /// Creates a transaction `object`.
+    /// Creates a transaction `object` for the network `chain_id`.
     pub fn new(
+        chain_id: ChainId,
         pbkey_send: PbKey,
         pbkey_recv: PbKey,
         // amt to send
@@ -75,11 +96,23 @@ impl Txn {
         // get the current system time
         let system_time: u64 = Utc::now().timestamp_millis().try_into().unwrap();
         // build the header
-        let txn_header = TxnHeader::new(amt, pbkey_send, pbkey_recv, system_time, txn_type);
+        let txn_header =
+            TxnHeader::new(chain_id, amt, pbkey_send, pbkey_recv, system_time, txn_type);
+
+        Self::from_header(txn_header)
+    }
+
+    /// ### Create an unsigned transaction from a prebuilt header.
+    ///
+    /// Unlike `Txn::new()`, the caller sets every header field (including `system_time`),
+    /// so the same txn (and id) can be rebuilt deterministically, e.g. when preparing a txn
+    /// to be signed offline.
+    pub fn from_header(header: TxnHeader) -> Self {
         let mut txn = Self {
-            header: txn_header,
+            header,
             id: None,        //[0u8; 64],
             signature: None, //[0u8; 64],
+            multisig: None,
         };
 
         // set the id with the body
@@ -95,12 +128,13 @@ impl Txn {
     /// Uses `Txn::new()` assoc fxn. to construct the txn, and signs the txn with given wallet.
     pub fn new_signed(
         wallet: &Wallet,
+        chain_id: ChainId,
         pbkey_recv: PbKey,
         amt_to_send: u128,
         txn_type: TxnType,
     ) -> Txn {
         let pbkey_send = wallet.pbkey();
-        let mut txn = Self::new(pbkey_send, pbkey_recv, amt_to_send, txn_type);
+        let mut txn = Self::new(chain_id, pbkey_send, pbkey_recv, amt_to_send, txn_type);
 
         // add signature to body
         txn.sign(&wallet);
@@ -122,6 +156,12 @@ impl Txn {
     fn set_signature(&mut self, signature: TxnSignature) {
         self.signature = Some(signature);
     }
+    /// ### Add a partial signature for a multi-signature transaction.
+    fn set_cosignature(&mut self, signer: PbKey, signature: TxnSignature) {
+        self.multisig
+            .get_or_insert_with(TxnMultisig::default)
+            .insert(signer, signature);
+    }
     //////////////// PRIVATE SETTERS ////////////////
     /////////////////////////////////////////////////
 }
@@ -1,10 +1,19 @@
-use crate::ledger::{general::PbKey, txn::TxnType};
+use crate::{
+    ledger::{
+        chain_id::ChainId,
+        general::PbKey,
+        txn::{constants::TXN_HEADER_VERSION, TxnType},
+    },
+    utils::encoding::{CanonicalDecoder, CanonicalEncoder, EncodingError},
+};
 use serde::{Deserialize, Serialize};
 
 /// ## Transaction header.
 /// Contains the transaction header information.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxnHeader {
+    /// Network the txn is for - see `ChainId`
+    chain_id: ChainId,
     // Token amount to be transfered
     amt: u128,
     pbkey_send: PbKey,
@@ -19,6 +28,7 @@ pub struct TxnHeader {
 impl TxnHeader {
     /// ### Create a new transaction header.
     pub fn new(
+        chain_id: ChainId,
         amt: u128,
         pbkey_send: PbKey,
         pbkey_recv: PbKey,
@@ -26,6 +36,7 @@ impl TxnHeader {
         txn_type: TxnType,
     ) -> Self {
         Self {
+            chain_id,
             amt,
             pbkey_send,
             pbkey_recv,
@@ -33,6 +44,10 @@ impl TxnHeader {
             txn_type,
         }
     }
+    /// ### Get property `Txn.chain_id`.
+    pub fn chain_id(&self) -> &ChainId {
+        &self.chain_id
+    }
     /// ### Get property `Txn.amt`.
     pub fn amt(&self) -> &u128 {
         &self.amt
@@ -56,8 +71,40 @@ impl TxnHeader {
 
     /// ### Serialize transaction header to bytes.
     ///
+    /// Canonical encoding (see `CanonicalEncoder`), version `TXN_HEADER_VERSION`. Field order:
+    /// 1. `chain_id` - bytes
+    /// 1. `amt` - u128
+    /// 1. `pbkey_send` - bytes
+    /// 1. `pbkey_recv` - bytes
+    /// 1. `system_time` - u64
+    /// 1. `txn_type` - u8
+    ///
     /// @todo replace `Vec<u8>` - don't allocate if all inputs have known size.
     pub fn serialize(&self) -> Vec<u8> {
-        serde_json::to_vec(self).expect("Error serializing transaction header")
+        let mut encoder = CanonicalEncoder::new(TXN_HEADER_VERSION);
+        encoder
+            .put_bytes(self.chain_id.as_bytes())
+            .put_u128(self.amt)
+            .put_bytes(&self.pbkey_send.0)
+            .put_bytes(&self.pbkey_recv.0)
+            .put_u64(self.system_time)
+            .put_u8(self.txn_type as u8);
+
+        encoder.finish()
+    }
+    /// ### Deserialize transaction header from its canonical encoding.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, EncodingError> {
+        let mut decoder = CanonicalDecoder::new(bytes, TXN_HEADER_VERSION)?;
+        let header = Self {
+            chain_id: ChainId::try_from(decoder.get_bytes()?)?,
+            amt: decoder.get_u128()?,
+            pbkey_send: PbKey(decoder.get_array()?),
+            pbkey_recv: PbKey(decoder.get_array()?),
+            system_time: decoder.get_u64()?,
+            txn_type: decoder.get_u8()?.try_into()?,
+        };
+        decoder.finish()?;
+
+        Ok(header)
     }
 }
@@ -1,11 +1,16 @@
-use crate::ledger::{
-    general::HashAlgo,
-    txn::{TxnCtxDigest, TxnDigest, TXN_DIGEST_LEN, TXN_SIGNATURE_CTX},
+use crate::{
+    ledger::{
+        chain_id::ChainId,
+        general::HashAlgo,
+        txn::{TxnCtxDigest, TxnDigest, TXN_DIGEST_LEN, TXN_SIGNATURE_CTX},
+    },
+    utils::address::{decode_check, encode_check, AddressError, TXN_ID_VERSION},
 };
 use {
     ed25519_dalek::Digest,
     serde::{Deserialize, Serialize},
     serde_big_array::BigArray,
+    std::{fmt, str::FromStr},
 };
 
 #[derive(Debug, Clone, Copy, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -37,14 +42,29 @@ impl From<&TxnId> for String {
         hex::encode(value.0)
     }
 }
+/// Text format: base58check with `TXN_ID_VERSION`.
+impl fmt::Display for TxnId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&encode_check(TXN_ID_VERSION, &self.0))
+    }
+}
+impl FromStr for TxnId {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(decode_check(TXN_ID_VERSION, s)?))
+    }
+}
 impl TxnId {
     pub fn from_bytes(value: TxnDigest) -> Self {
         Self(value)
     }
-    pub fn to_presigned_digest(&self) -> TxnCtxDigest {
+    /// ### Get the message signed for this txn id on the network `chain_id` - the chain's signing context followed by the id.
+    pub fn to_presigned_digest(&self, chain_id: &ChainId) -> TxnCtxDigest {
         let mut digest_buffer: TxnCtxDigest = [0_u8; TXN_DIGEST_LEN + TXN_SIGNATURE_CTX.len()];
         // add context
-        digest_buffer[..TXN_SIGNATURE_CTX.len()].copy_from_slice(TXN_SIGNATURE_CTX);
+        digest_buffer[..TXN_SIGNATURE_CTX.len()]
+            .copy_from_slice(&chain_id.signing_ctx(TXN_SIGNATURE_CTX));
         // add digest
         digest_buffer[TXN_SIGNATURE_CTX.len()..self.0.len() + TXN_SIGNATURE_CTX.len()]
             .copy_from_slice(&self.0);
@@ -0,0 +1,43 @@
+use crate::ledger::{general::PbKey, txn::TxnSignature};
+use serde::{Deserialize, Serialize};
+
+/// ## A single signer's partial signature over a multisig txn.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnCosignature {
+    pub signer: PbKey,
+    pub signature: TxnSignature,
+}
+
+/// ## Partial signatures collected for a multi-signature txn.
+///
+/// Kept ordered by signer with at most one signature per signer,
+/// so collecting the same signature twice does not count twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnMultisig(Vec<TxnCosignature>);
+
+impl TxnMultisig {
+    /// ### Add (or replace) the signature for a given signer.
+    pub fn insert(&mut self, signer: PbKey, signature: TxnSignature) {
+        let cosig = TxnCosignature { signer, signature };
+        match self.0.binary_search_by(|c| c.signer.cmp(&signer)) {
+            Ok(idx) => self.0[idx] = cosig,
+            Err(idx) => self.0.insert(idx, cosig),
+        }
+    }
+    /// ### Get the signature for a given signer.
+    pub fn get(&self, signer: &PbKey) -> Option<&TxnSignature> {
+        self.0
+            .binary_search_by(|c| c.signer.cmp(signer))
+            .ok()
+            .map(|idx| &self.0[idx].signature)
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, TxnCosignature> {
+        self.0.iter()
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
@@ -1,11 +1,20 @@
-use crate::ledger::txn::TxnDigest;
+use crate::ledger::{error::CryptoError, txn::TxnDigest};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxnSignature(pub Vec<u8>);
-impl From<TxnSignature> for ed25519_dalek::Signature {
-    fn from(x: TxnSignature) -> ed25519::Signature {
-        ed25519::Signature::from_bytes(&x.0).unwrap()
+impl TryFrom<TxnSignature> for ed25519_dalek::Signature {
+    type Error = CryptoError;
+
+    fn try_from(x: TxnSignature) -> Result<ed25519::Signature, Self::Error> {
+        ed25519::Signature::try_from(&x)
+    }
+}
+impl TryFrom<&TxnSignature> for ed25519_dalek::Signature {
+    type Error = CryptoError;
+
+    fn try_from(x: &TxnSignature) -> Result<ed25519::Signature, Self::Error> {
+        Ok(ed25519::Signature::from_bytes(&x.0)?)
     }
 }
 impl From<ed25519_dalek::Signature> for TxnSignature {
@@ -18,9 +27,15 @@ impl From<TxnDigest> for TxnSignature {
         TxnSignature(x.to_vec())
     }
 }
-impl From<TxnSignature> for TxnDigest {
-    fn from(x: TxnSignature) -> TxnDigest {
-        x.0.try_into().unwrap()
+impl TryFrom<TxnSignature> for TxnDigest {
+    type Error = CryptoError;
+
+    fn try_from(x: TxnSignature) -> Result<TxnDigest, Self::Error> {
+        let found = x.0.len();
+        x.0.try_into().map_err(|_| CryptoError::InvalidLength {
+            expected: std::mem::size_of::<TxnDigest>(),
+            found,
+        })
     }
 }
 impl From<TxnSignature> for String {
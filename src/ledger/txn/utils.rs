@@ -1,6 +1,6 @@
 use crate::ledger::{
     general::HashAlgo,
-    txn::{Txn, TxnDigest, TxnId, TxnSignature, TXN_MSG_CTX},
+    txn::{error::TxnError, Txn, TxnDigest, TxnId, TxnSignature, TXN_DIGEST_LEN, TXN_MSG_CTX},
     wallet::Wallet,
 };
 use ed25519_dalek::Digest;
@@ -34,6 +34,22 @@ impl Txn {
         prehash
     }
 
+    /// ### Get the encoded size of the txn in bytes - counted towards the block's byte limit.
+    ///
+    /// Header, id, signature, and any partial signatures (signer key + signature each).
+    pub fn size(&self) -> u64 {
+        let signature_len = self.signature.as_ref().map_or(0, |sig| sig.0.len());
+        let multisig_len: usize = self.multisig.as_ref().map_or(0, |multisig| {
+            multisig
+                .iter()
+                .map(|cosig| cosig.signer.0.len() + cosig.signature.0.len())
+                .sum()
+        });
+        let size = self.header.serialize().len() + TXN_DIGEST_LEN + signature_len + multisig_len;
+
+        size as u64
+    }
+
     /// ### Create and return a message signature based on the contents of the transaction
     pub fn calc_signature(&self, wallet: &Wallet) -> TxnSignature {
         wallet.sign_txn(self)
@@ -49,4 +65,31 @@ impl Txn {
 
         sig
     }
+
+    /// ### Add a partial signature to a multi-signature transaction in place.
+    ///
+    /// Each signer of the sending account's policy calls this on their own copy of the txn
+    /// (possibly offline); the copies are then combined with `merge_multisig()`.
+    pub fn sign_multisig(&mut self, wallet: &Wallet) -> TxnSignature {
+        let sig = self.calc_signature(wallet);
+        self.set_cosignature(wallet.pbkey(), sig.to_owned());
+
+        sig
+    }
+
+    /// ### Collect the partial signatures from another copy of this transaction.
+    ///
+    /// Both copies must have the same id; signatures are de-duplicated by signer.
+    pub fn merge_multisig(&mut self, other: &Txn) -> std::result::Result<(), TxnError> {
+        if self.calc_id() != other.calc_id() {
+            return Err(TxnError::MismatchedTxn);
+        }
+        if let Some(other_multisig) = other.multisig() {
+            for cosig in other_multisig.iter() {
+                self.set_cosignature(cosig.signer, cosig.signature.to_owned());
+            }
+        }
+
+        Ok(())
+    }
 }
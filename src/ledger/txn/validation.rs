@@ -0,0 +1,96 @@
+use crate::{
+    accounts::multisig::MultisigPolicy,
+    ledger::{
+        chain_id::ChainId,
+        general::PbKey,
+        txn::{error::TxnError, Txn, TxnSignature},
+    },
+};
+use std::collections::BTreeSet;
+
+impl Txn {
+    /// ### Check if a signature over this txn is valid for `signer_pbkey`.
+    ///
+    /// The signed message is the txn's chain's signature context followed by the txn id,
+    /// matching `Wallet::sign_txn()`.
+    pub fn verify_signature(
+        &self,
+        signature: &TxnSignature,
+        signer_pbkey: &PbKey,
+    ) -> std::result::Result<(), TxnError> {
+        let presigned_msg = self.calc_id().to_presigned_digest(self.chain_id());
+        let sig_test = ed25519::Signature::try_from(signature)?;
+        let signer_conv = ed25519_dalek::PublicKey::try_from(signer_pbkey)?;
+
+        signer_conv
+            .verify_strict(&presigned_msg, &sig_test)
+            .map_err(TxnError::InvalidSignature)
+    }
+
+    /// ### Check if the txn's (single) signature is valid.
+    pub fn is_signature_valid(&self, signer_pbkey: &PbKey) -> std::result::Result<(), TxnError> {
+        let signature = self.signature.as_ref().ok_or(TxnError::EmptySignature)?;
+
+        self.verify_signature(signature, signer_pbkey)
+    }
+
+    /// ### Check if the txn is well-formed and signed.
+    ///
+    /// Stateless checks, usable before the sender's account is known (i.e. on pool admission):
+    /// - id is set and matches the header;
+    /// - multisig txns: every partial signature is valid for its signer
+    ///   (the account's policy is checked with `is_multisig_valid()`);
+    /// - all other txns: the signature is valid for `pbkey_send`.
+    pub fn is_valid(&self) -> std::result::Result<(), TxnError> {
+        match self.id {
+            Some(id) if id == self.calc_id() => {}
+            Some(_) => return Err(TxnError::IncorrectId),
+            None => return Err(TxnError::EmptyId),
+        }
+
+        match &self.multisig {
+            Some(multisig) if !multisig.is_empty() => multisig
+                .iter()
+                .try_for_each(|cosig| self.verify_signature(&cosig.signature, &cosig.signer)),
+            _ => self.is_signature_valid(self.pbkey_send()),
+        }
+    }
+
+    /// ### Check the txn is for the network `chain_id`.
+    pub fn is_for_chain(&self, chain_id: &ChainId) -> std::result::Result<(), TxnError> {
+        if self.chain_id() != chain_id {
+            return Err(TxnError::WrongChainId {
+                expected: chain_id.clone(),
+                found: self.chain_id().clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// ### Check if the collected partial signatures satisfy a multisig policy.
+    ///
+    /// Every attached signature must come from a policy signer and be valid,
+    /// and at least `policy.threshold()` distinct signers must have signed.\
+    /// Signers are counted once however often they appear - txns decoded from peers may repeat a signature.
+    pub fn is_multisig_valid(&self, policy: &MultisigPolicy) -> std::result::Result<(), TxnError> {
+        let multisig = self.multisig.as_ref().ok_or(TxnError::EmptySignature)?;
+
+        let mut signers = BTreeSet::new();
+        for cosig in multisig.iter() {
+            if !policy.is_signer(&cosig.signer) {
+                return Err(TxnError::UnknownSigner(cosig.signer));
+            }
+            self.verify_signature(&cosig.signature, &cosig.signer)?;
+            signers.insert(cosig.signer);
+        }
+        if signers.len() < policy.threshold() as usize {
+            return Err(TxnError::ThresholdNotMet {
+                required: policy.threshold(),
+                found: signers.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
-use crate::ledger::{general::Result, txn::Txn};
+use crate::{
+    ledger::{general::Result, txn::Txn},
+    storage::backend::TxnPoolBackend,
+};
 use {
     serde::{Deserialize, Serialize},
     std::collections::BTreeMap,
@@ -14,13 +17,37 @@ pub type TxnMap = BTreeMap<TxnMapKey, Txn>;
 pub struct TxnPool {
     /// Ordered lookup of transactions
     values: TxnMap,
+    /// Storage the pool is written to - every added and removed txn, see `open()`.
+    #[serde(skip)]
+    backend: Option<Box<dyn TxnPoolBackend>>,
 }
 impl TxnPool {
     /// ### Initialize new transaction pool.
     pub fn new() -> Self {
         let txns = TxnMap::new();
 
-        Self { values: txns }
+        Self {
+            values: txns,
+            backend: None,
+        }
+    }
+    /// ### Open the transaction pool held by a storage backend - i.e. to keep pending txns across restarts.
+    /// Added and removed txns are written to the backend before the pool is updated.
+    pub fn open(backend: Box<dyn TxnPoolBackend>) -> Result<Self> {
+        let values = backend
+            .txns()?
+            .into_iter()
+            .map(|txn| (txn.id_key(), txn))
+            .collect();
+
+        Ok(Self {
+            values,
+            backend: Some(backend),
+        })
+    }
+    /// ### Detach the pool's storage backend, i.e. to reopen the pool on it (see `open()`).
+    pub fn into_backend(self) -> Option<Box<dyn TxnPoolBackend>> {
+        self.backend
     }
     /// ### Check if a transaction exists in the txn pool (#7).
     ///
@@ -33,20 +60,29 @@ impl TxnPool {
     }
     /// ### Append a transaction to the pool.
     ///
-    /// Check for duplicate and handle properly.
+    /// Check for duplicate and handle properly.\
+    /// Txn must pass `Txn::is_valid()` (id and signature checks), otherwise it's rejected with a `TxnError`.
     /// - @todo verify the requesting node is authorized
-    /// - @todo validate signature
     pub fn add_txn(&mut self, txn: Txn) -> Result<()> {
-        // if value (txn from pool) is returned from the `.insert()` call, then this txn already exists in the pool - throw error
-        if let Some(_txn) = self.values.insert(txn.id_key().to_owned(), txn) {
+        txn.is_valid()?;
+
+        if self.does_txn_exist(&txn) {
             return Err(TxnPoolError::DuplicateTxn.into());
         }
+        if let Some(backend) = &mut self.backend {
+            backend.put_txn(&txn)?;
+        }
+        self.values.insert(txn.id_key(), txn);
 
         Ok(())
     }
 
     /////////////////////////////////////////////////////////////////////
     ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `TxnPool.backend` - `None` unless the pool is stored (see `open()`).
+    pub fn backend(&self) -> Option<&dyn TxnPoolBackend> {
+        self.backend.as_deref()
+    }
     /// ### Get property `TxnPool.txns`.
     pub fn txns(&self) -> &TxnMap {
         &self.values
@@ -65,13 +101,18 @@ impl TxnPool {
     /// ### Remove and return a transaction from pool.
     ///
     /// Use the Txn Map Key to look up the transaction in the transaction pool.\
-    /// Calls `BTreeMap.remove()`.
+    /// Calls `BTreeMap.remove()`, after removing it from the backend (if any).
     /// - @todo (unsure about this) verify the requesting node is authorized
     pub fn remove_txn(&mut self, txn: &Txn) -> Result<Txn> {
-        match self.values.remove(&txn.id_key()) {
-            Some(txn) => Ok(txn),
-            None => Err(TxnPoolError::TxnDoesNotExist.into()),
+        let key = txn.id_key();
+        if !self.values.contains_key(&key) {
+            return Err(TxnPoolError::TxnDoesNotExist.into());
         }
+        if let Some(backend) = &mut self.backend {
+            backend.remove_txn(&key)?;
+        }
+
+        Ok(self.values.remove(&key).unwrap())
     }
     ////////////////////////////// SETTERS //////////////////////////////
     /////////////////////////////////////////////////////////////////////
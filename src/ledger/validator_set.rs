@@ -0,0 +1,106 @@
+use crate::{
+    ledger::general::PbKey,
+    utils::{encoding::CanonicalEncoder, hash::BlakeHash},
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::collections::{BTreeMap, BTreeSet},
+};
+
+/// Domain separator for validator set hashes.
+pub const VALIDATOR_SET_CTX: &[u8; 15] = b"validator-set-0";
+/// Canonical encoding version for `ValidatorSet` (see `ValidatorSet::serialize()`).
+pub const VALIDATOR_SET_VERSION: u8 = 1;
+
+/// ## Set of validators that finalize blocks, with their voting power.
+///
+/// A block is final once validators holding more than 2/3 of the total voting power
+/// have signed it (see `FinalityCertificate`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorSet {
+    /// Voting power per validator - ordered
+    validators: BTreeMap<PbKey, u128>,
+}
+
+impl ValidatorSet {
+    /// ### Create a validator set from (validator, voting power) pairs.
+    /// Validators listed more than once keep the last voting power given.
+    pub fn new(validators: impl IntoIterator<Item = (PbKey, u128)>) -> Self {
+        Self {
+            validators: validators.into_iter().collect(),
+        }
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `ValidatorSet.validators`.
+    pub fn validators(&self) -> &BTreeMap<PbKey, u128> {
+        &self.validators
+    }
+    /// ### Get a validator's voting power - `None` if not in the set.
+    pub fn power(&self, pbkey: &PbKey) -> Option<u128> {
+        self.validators.get(pbkey).copied()
+    }
+    /// ### Check if a key is one of the validators.
+    pub fn contains(&self, pbkey: &PbKey) -> bool {
+        self.validators.contains_key(pbkey)
+    }
+    /// ### Get the combined voting power of all validators - saturates at `u128::MAX`.
+    /// Voting power is unbounded - compare it with `is_quorum()`, which can't overflow.
+    pub fn total_power(&self) -> u128 {
+        self.validators
+            .values()
+            .fold(0u128, |total, power| total.saturating_add(*power))
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Check if `voters` hold more than 2/3 of the total voting power.
+    ///
+    /// Voters that aren't validators hold no power, and each voter counts once.
+    /// Powers are summed and compared in 256 bits, so no voting power overflows.
+    pub fn is_quorum<'a>(&self, voters: impl IntoIterator<Item = &'a PbKey>) -> bool {
+        let voters: BTreeSet<_> = voters.into_iter().collect();
+        let power = wide_sum(voters.into_iter().filter_map(|voter| self.power(voter)));
+        let total = wide_sum(self.validators.values().copied());
+
+        // 3 * power > 2 * total
+        wide_add(wide_add(power, power), power) > wide_add(total, total)
+    }
+    /// ### Serialize validator set to bytes.
+    ///
+    /// Canonical encoding (see `CanonicalEncoder`), version `VALIDATOR_SET_VERSION`:
+    /// validator count, then each validator in key order:
+    /// 1. `pbkey` - bytes
+    /// 1. `power` - u128
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut encoder = CanonicalEncoder::new(VALIDATOR_SET_VERSION);
+        encoder.put_len(self.validators.len());
+        for (pbkey, power) in self.validators.iter() {
+            encoder.put_bytes(&pbkey.0).put_u128(*power);
+        }
+
+        encoder.finish()
+    }
+    /// ### Hash of the validator set - committed to in `BlockHeader.validator_set_hash`.
+    pub fn hash(&self) -> BlakeHash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(VALIDATOR_SET_CTX);
+        hasher.update(&self.serialize());
+
+        hasher.finalize().into()
+    }
+}
+
+/// ### Sum voting powers without overflowing - the sum's (high, low) 128-bit halves.
+fn wide_sum(powers: impl IntoIterator<Item = u128>) -> (u128, u128) {
+    powers
+        .into_iter()
+        .fold((0, 0), |sum, power| wide_add(sum, (0, power)))
+}
+/// ### Add two (high, low) 256-bit numbers - high halves stay small, they count overflows of the low ones.
+fn wide_add(a: (u128, u128), b: (u128, u128)) -> (u128, u128) {
+    let (low, carry) = a.1.overflowing_add(b.1);
+
+    (a.0 + b.0 + carry as u128, low)
+}
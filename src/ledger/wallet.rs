@@ -1,5 +1,5 @@
 use crate::ledger::{
-    block::{block_signature::BlockSignature, constants::*, Block},
+    block::{block_id::BlockId, block_signature::BlockSignature, constants::*, Block},
     general::{PbKey, Result, KP},
     txn::{constants::*, txn_signature::TxnSignature, Txn},
 };
@@ -44,19 +44,27 @@ impl Wallet {
         Self { keypair }
     }
     /// ### Return the signature for a given txn id/hash.
-    /// Take in id/hash digest, sign digest with current wallet's key, return signature.
+    /// Take in id/hash digest, sign digest with current wallet's key, return signature.\
+    /// Signed under the signing context of the txn's chain (see `ChainId::signing_ctx()`).
     /// - @todo make this a generic function for signing any message. move logic to txn.
     pub fn sign_txn(&self, txn: &Txn) -> TxnSignature {
-        self.sign_msg(&mut txn.calc_id().0, TXN_SIGNATURE_CTX)
-            .into()
+        let ctx = txn.chain_id().signing_ctx(TXN_SIGNATURE_CTX);
+        self.sign_msg(&mut txn.calc_id().0, &ctx).into()
     }
 
     /// ### Sign a block. Generate signature for block.
     /// We are using the prehash Sha512 for consistency, modularity and ease of use.\
-    /// Also, there may be significant or breaking changes in the future as suggested in their documentation (r.e. "bandaids").
+    /// Also, there may be significant or breaking changes in the future as suggested in their documentation (r.e. "bandaids").\
+    /// Signed under the signing context of the block's chain (see `ChainId::signing_ctx()`).
     /// - @todo make this a generic function for signing any message. move logic to block.
     pub fn sign_block(&self, block: &Block) -> BlockSignature {
-        self.sign_msg(&mut block.calc_id().0, BLOCK_SIGNATURE_CTX)
+        let ctx = block.chain_id().signing_ctx(BLOCK_SIGNATURE_CTX);
+        self.sign_msg(&mut block.calc_id().0, &ctx).into()
+    }
+    /// ### Sign a finality vote for a block - see `FinalityCertificate`.
+    /// The block id already commits to the block's chain id.
+    pub fn sign_finality(&self, block_id: &BlockId) -> BlockSignature {
+        self.sign_msg(&mut block_id.0.to_owned(), FINALITY_SIGNATURE_CTX)
             .into()
     }
     /// ### Standard function for signing messages.
@@ -120,8 +128,8 @@ impl Wallet {
 
     /// ### Validate a message.
     /// Intended for ed25519.
-    pub fn validate_msg(&self, msg: &Vec<u8>, signature: &Vec<u8>) -> Result<()> {
-        let signature = ed25519_dalek::Signature::from_bytes(&signature).unwrap();
+    pub fn validate_msg(&self, msg: &[u8], signature: &[u8]) -> Result<()> {
+        let signature = ed25519_dalek::Signature::from_bytes(signature)?;
 
         Ok(self.keypair.verify(msg, &signature)?)
     }
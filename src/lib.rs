@@ -1,4 +1,5 @@
 pub mod accounts;
 pub mod ledger;
 pub mod node;
+pub mod storage;
 pub mod utils;
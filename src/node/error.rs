@@ -8,6 +8,10 @@ pub enum NodeError {
     InitWallet,
     #[error("Transaction pool not initialized.")]
     InitTxnPool,
+    #[error("Sync stalled at height {0} - no peer could serve the missing blocks.")]
+    SyncStalled(u128),
+    #[error("Ledger: {0}")]
+    Ledger(#[from] anyhow::Error),
     #[error("P2P")]
     P2PError(#[from] P2PError),
 }
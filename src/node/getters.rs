@@ -1,6 +1,6 @@
 use crate::{
     ledger::{blockchain::Blockchain, txn_pool::TxnPool, wallet::Wallet},
-    node::{p2p::P2P, Node},
+    node::{p2p::P2P, sync::SyncManager, Node},
 };
 use std::borrow::BorrowMut;
 
@@ -19,6 +19,10 @@ impl Node {
     pub fn txn_pool(&self) -> &TxnPool {
         &self.txn_pool
     }
+    /// ### Get property `Node.sync` - where the node is in syncing its blockchain with its peers.
+    pub fn sync(&self) -> &SyncManager {
+        &self.sync
+    }
     /// ### Get property `Node.p2p`.
     /// Node.p2p is the node's peer to peer instance.
     pub fn p2p(&self) -> &P2P {
@@ -0,0 +1,170 @@
+use crate::{
+    ledger::{
+        self,
+        block::Block,
+        txn::{error::TxnError, Txn},
+        txn_pool::TxnPoolError,
+    },
+    node::{
+        p2p::{message::NetworkMessage, peer_score::Penalty},
+        types::Result,
+        Node,
+    },
+    storage::error::StorageError,
+};
+use {
+    libp2p::PeerId,
+    std::collections::{BTreeSet, VecDeque},
+};
+
+/// Number of txns a node remembers having seen, to gossip each of them only once.
+pub const MAX_SEEN_TXNS: usize = 16_384;
+/// Number of blocks a node remembers having seen, to hand each of them to the chain only once.
+pub const MAX_SEEN_BLOCKS: usize = 1_024;
+
+/// ## Bounded set of recently seen keys, i.e. txns already gossiped - the oldest key is dropped once full.
+#[derive(Debug, Clone)]
+pub struct SeenCache<K> {
+    capacity: usize,
+    keys: BTreeSet<K>,
+    /// Keys in the order they were first seen
+    order: VecDeque<K>,
+}
+
+impl<K: Clone + Ord> SeenCache<K> {
+    /// ### Create an empty cache holding up to `capacity` keys (at least 1).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            keys: BTreeSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// ### Whether a key was seen, and not dropped since.
+    pub fn contains(&self, key: &K) -> bool {
+        self.keys.contains(key)
+    }
+    /// ### Get the number of keys held.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+    /// ### Whether no key was seen yet.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+    /// ### Mark a key as seen - returns false if it already was.
+    pub fn insert(&mut self, key: K) -> bool {
+        if !self.keys.insert(key.clone()) {
+            return false;
+        }
+        self.order.push_back(key);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+
+        true
+    }
+}
+
+impl Node {
+    /// ### Add a txn to the node's txn pool, and gossip it to the node's peers.
+    ///
+    /// The txn must pass the same validation as txns received from peers (see `admit_txn()`) -
+    /// it is gossiped only if the p2p connection is running (see `start_p2p()`), and only once.
+    pub fn submit_txn(&mut self, txn: Txn) -> Result<()> {
+        self.admit_txn(txn.clone())?;
+        self.seen_txns.insert(txn.id_key());
+        if self.p2p.is_running() {
+            self.broadcast(&NetworkMessage::NewTxn(Box::new(txn)))?;
+        }
+
+        Ok(())
+    }
+    /// ### Add a txn gossiped by a peer to the node's txn pool.
+    ///
+    /// Txns the node already admitted (gossiped, pending in the pool, or on the chain) are ignored.
+    /// A txn is marked seen only once admitted - its id doesn't cover its signatures, so a copy failing validation
+    /// (i.e. with cosignatures stripped) mustn't hide a valid one arriving later.\
+    /// The pubsub topic relays each message once per node - the txn isn't published again.\
+    /// A txn failing validation (`TxnError`) costs its sender a `Penalty::Major` (see `P2P::penalize()`).
+    pub(super) fn receive_txn(&mut self, source: PeerId, txn: Txn) {
+        let key = txn.id_key();
+        if self.seen_txns.contains(&key) {
+            return;
+        }
+        match self.admit_txn(txn) {
+            Ok(()) => {
+                self.seen_txns.insert(key);
+            }
+            // duplicates (txns pending or on the chain) and storage errors aren't the sender's fault
+            Err(e) if e.downcast_ref::<TxnError>().is_some() => {
+                let _ = self.p2p.penalize(source, Penalty::Major);
+            }
+            Err(_) => {}
+        }
+    }
+    /// ### Hand a block propagated by a peer to the node's chain - see `apply_block()`.
+    ///
+    /// The block passed pre-validation (header and signature, see `NetworkMessage::pre_validate()`) before it was relayed.
+    /// Only blocks extending the chain's tip are added - a block above the tip means the node fell behind,
+    /// and has to sync (see `Node::sync_blockchain()`).\
+    /// A block is marked seen only once added - a copy arriving early, or failing validation, mustn't hide a valid one.\
+    /// A block failing validation costs its sender a `Penalty::Major` (see `P2P::penalize()`) -
+    /// one that can't be stored (`StorageError`) doesn't.
+    pub(super) fn receive_block(&mut self, source: PeerId, block: Block) {
+        let key = block.id_key();
+        if self.seen_blocks.contains(&key) {
+            return;
+        }
+        if !self.extends_tip(&block) {
+            if *block.blockheight() > self.blockchain.height() + 1 {
+                self.sync.fell_behind();
+            }
+            return;
+        }
+
+        match self.apply_block(block) {
+            Ok(()) => {
+                self.seen_blocks.insert(key);
+            }
+            // storage errors aren't the sender's fault
+            Err(e) if e.downcast_ref::<StorageError>().is_some() => {}
+            Err(_) => {
+                let _ = self.p2p.penalize(source, Penalty::Major);
+            }
+        }
+    }
+    /// ### Whether a block is the next block on the node's chain - see `Blockchain::is_next_block()`.
+    pub(super) fn extends_tip(&self, block: &Block) -> bool {
+        self.blockchain.is_next_block(block).is_ok()
+    }
+    /// ### Add a block built elsewhere to the node's chain - see `Blockchain::replay_block()`.
+    /// Its txns are executed while it's replayed, and removed from the txn pool once it's added.
+    pub(super) fn apply_block(&mut self, block: Block) -> ledger::Result<()> {
+        let block = self.blockchain.replay_block(block)?;
+        let txns: Vec<_> = block.txns().values().cloned().collect();
+        for txn in txns {
+            let _ = self.txn_pool.remove_txn(&txn);
+        }
+
+        Ok(())
+    }
+    /// ### Validate a txn against the node's chain, then add it to the txn pool.
+    ///
+    /// 1. Assert the txn is for the node's chain - `TxnError`
+    /// 1. Assert the txn is authorized by its sending account (see `Blockchain::is_txn_authorized()`) - `TxnError`
+    /// 1. Assert the txn isn't on the chain, or in the pool, already - `TxnPoolError::DuplicateTxn`
+    /// 1. Add to the pool - see `TxnPool::add_txn()`
+    fn admit_txn(&mut self, txn: Txn) -> ledger::Result<()> {
+        txn.is_for_chain(self.blockchain.chain_id())?;
+        self.blockchain.is_txn_authorized(&txn)?;
+        if self.blockchain.txn_location(txn.id()).is_some() {
+            return Err(TxnPoolError::DuplicateTxn.into());
+        }
+
+        self.txn_pool.add_txn(txn)
+    }
+}
@@ -1,14 +1,25 @@
-mod error;
+pub mod error;
 mod getters;
+pub mod gossip;
 pub mod p2p;
 mod setters;
+pub mod sync;
 pub mod types;
 mod utils;
 
 use crate::{
-    ledger::{blockchain::Blockchain, txn_pool::TxnPool, wallet::Wallet},
-    node::p2p::P2P,
+    ledger::{
+        blockchain::{types::BlockMapKey, Blockchain},
+        txn_pool::{TxnMapKey, TxnPool},
+        wallet::Wallet,
+    },
+    node::{
+        gossip::{SeenCache, MAX_SEEN_BLOCKS, MAX_SEEN_TXNS},
+        p2p::{message::NetworkEvent, P2P},
+        sync::SyncManager,
+    },
 };
+use tokio::sync::mpsc::UnboundedReceiver;
 
 /// ## An instance of a `Node`.
 /// A node has a wallet, an instance of blockchain, and a transaction pool.\
@@ -26,9 +37,17 @@ pub struct Node {
     blockchain: Blockchain,
     txn_pool: TxnPool,
     p2p: P2P,
+    /// Events from the p2p swarm task - `None` until started (see `start_p2p()`)
+    network_events: Option<UnboundedReceiver<NetworkEvent>>,
+    /// Txns the node admitted to its pool, submitted or gossiped - each is gossiped only once
+    seen_txns: SeenCache<TxnMapKey>,
+    /// Blocks propagated to the node and added to its chain - later copies are ignored
+    seen_blocks: SeenCache<BlockMapKey>,
+    /// Sync of the blockchain with the node's peers - see `sync_blockchain()`
+    sync: SyncManager,
 }
 
-// @todo store node info locally so that it can be retrieved on startup
+// @todo store peers locally so they can be retrieved on startup - the chain and txn pool are, see `Blockchain::open()` and `TxnPool::open()`
 impl Node {
     /// ### Create a new `Node` instance.
     ///
@@ -42,6 +61,10 @@ impl Node {
             blockchain: Blockchain::new(),
             txn_pool: TxnPool::new(),
             p2p,
+            network_events: None,
+            seen_txns: SeenCache::new(MAX_SEEN_TXNS),
+            seen_blocks: SeenCache::new(MAX_SEEN_BLOCKS),
+            sync: SyncManager::new(),
         }
     }
 }
@@ -0,0 +1,90 @@
+use crate::node::p2p::{
+    error::P2PError,
+    message::MAX_MESSAGE_BYTES,
+    sync_codec::{SyncCodec, SyncProtocol},
+    types::Result,
+};
+use libp2p::{
+    gossipsub::{
+        Gossipsub, GossipsubConfigBuilder, GossipsubMessage, IdentTopic, MessageAuthenticity,
+        MessageId, ValidationMode,
+    },
+    identity::Keypair,
+    mdns,
+    request_response::{ProtocolSupport, RequestResponse, RequestResponseConfig},
+    swarm::NetworkBehaviour,
+};
+
+/// Gossipsub topic nodes publish their network messages to, unless a message has a topic of its own.
+pub const NODES_TOPIC: &str = "nodes";
+/// Gossipsub topic new txns are gossiped on (see `Node::submit_txn()`).
+pub const TXNS_TOPIC: &str = "txns";
+/// Gossipsub topic new blocks are propagated on.
+pub const BLOCKS_TOPIC: &str = "blocks";
+
+/// ## Network behaviours of a node's swarm, combined - events come out as `NodeBehaviourEvent`s.
+///
+/// 1. `gossipsub` - publish network messages to, and receive them from, the peers subscribed to each topic
+///    (`NODES_TOPIC`, `TXNS_TOPIC` and `BLOCKS_TOPIC`)
+/// 1. `mdns` - discover peers on the local network - will be changed in the future
+/// 1. `sync` - ask peers for their chain status and blocks, and answer them (see `SyncCodec`)
+#[derive(NetworkBehaviour)]
+pub struct NodeBehaviour {
+    pub gossipsub: Gossipsub,
+    pub mdns: mdns::tokio::Behaviour,
+    pub sync: RequestResponse<SyncCodec>,
+}
+
+impl NodeBehaviour {
+    /// ### Create the behaviours for the node with keypair `kp`, subscribed to every topic.
+    ///
+    /// Gossipsub messages are signed by their publisher, identified by `message_id()`,
+    /// and only relayed once the node validated them (see `event_loop::handle_swarm_event()`).
+    pub fn new(kp: Keypair) -> Result<Self> {
+        let config = GossipsubConfigBuilder::default()
+            .validation_mode(ValidationMode::Strict)
+            .validate_messages()
+            .max_transmit_size(MAX_MESSAGE_BYTES)
+            .message_id_fn(message_id)
+            .build()
+            .map_err(P2PError::Gossipsub)?;
+        let mut gossipsub =
+            Gossipsub::new(MessageAuthenticity::Signed(kp), config).map_err(P2PError::Gossipsub)?;
+        for topic in [NODES_TOPIC, TXNS_TOPIC, BLOCKS_TOPIC] {
+            gossipsub
+                .subscribe(&IdentTopic::new(topic))
+                .map_err(|_| P2PError::Gossipsub("Could not subscribe to topic"))?;
+        }
+        let mdns = mdns::tokio::Behaviour::new(mdns::Config::default())?;
+        let sync = RequestResponse::new(
+            SyncCodec,
+            [(SyncProtocol, ProtocolSupport::Full)],
+            RequestResponseConfig::default(),
+        );
+
+        Ok(Self {
+            gossipsub,
+            mdns,
+            sync,
+        })
+    }
+}
+
+/// ### Identify a gossipsub message, for de-duplication.
+///
+/// New blocks and txns are identified by a hash of the whole message, so each is relayed once per node -
+/// whoever published it, however often. The message isn't decoded: ids mustn't depend on unvalidated content,
+/// and copies differing in any byte (i.e. signatures) are told apart.\
+/// Other messages are unique per publish: publisher and sequence number.
+fn message_id(message: &GossipsubMessage) -> MessageId {
+    let is_content = [TXNS_TOPIC, BLOCKS_TOPIC]
+        .iter()
+        .any(|topic| message.topic == IdentTopic::new(*topic).hash());
+    if is_content {
+        return MessageId::new(blake3::hash(&message.data).as_bytes());
+    }
+    let mut id = message.source.map(|p| p.to_bytes()).unwrap_or_default();
+    id.extend(message.sequence_number.unwrap_or_default().to_be_bytes());
+
+    MessageId::new(&id)
+}
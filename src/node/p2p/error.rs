@@ -1,9 +1,60 @@
+use crate::{
+    ledger::{block::error::BlockError, error::CryptoError, txn::error::TxnError},
+    node::p2p::{message::PROTOCOL_VERSION, peer_score::Penalty},
+};
+
 #[derive(Debug, thiserror::Error)]
 pub enum P2PError {
     #[error("Misc P2P error.")]
     P2P,
     #[error("P2P module not initialized.")]
     InitP2P,
+    #[error("P2P module already started.")]
+    AlreadyStarted,
+    #[error("Gossipsub: {0}")]
+    Gossipsub(&'static str),
+    #[error("Transport: {0}")]
+    Transport(#[from] libp2p::TransportError<std::io::Error>),
+    #[error("Protocol: {0}")]
+    Protocol(#[from] ProtocolError),
     #[error("IoError.")]
     IoError(#[from] std::io::Error),
+    #[error("Crypto: {0}")]
+    Crypto(#[from] CryptoError),
+}
+
+/// ## Errors decoding (or encoding) a `NetworkMessage` - see `NetworkMessage::from_bytes()` and `NetworkMessage::pre_validate()`.
+#[derive(Debug, thiserror::Error)]
+pub enum ProtocolError {
+    #[error("ProtocolError::Empty- Message has no bytes")]
+    Empty,
+    #[error(
+        "ProtocolError::UnsupportedVersion- Protocol version {0}, expected {PROTOCOL_VERSION}"
+    )]
+    UnsupportedVersion(u8),
+    #[error("ProtocolError::TooLarge- Message is {size} bytes, the limit is {max}")]
+    TooLarge { size: usize, max: usize },
+    #[error("ProtocolError::Malformed- {0}")]
+    Malformed(#[from] bincode::Error),
+    #[error("ProtocolError::InvalidMessage- {0}")]
+    InvalidMessage(&'static str),
+    #[error("ProtocolError::InvalidTxn- {0}")]
+    InvalidTxn(#[from] TxnError),
+    #[error("ProtocolError::InvalidBlock- {0}")]
+    InvalidBlock(#[from] BlockError),
+}
+impl ProtocolError {
+    /// ### Get the penalty for the peer that sent a message failing to decode (or pre-validate) with this error.
+    pub fn penalty(&self) -> Penalty {
+        match self {
+            // the peer may run a newer version
+            Self::UnsupportedVersion(_) => Penalty::Minor,
+            Self::Empty
+            | Self::Malformed(_)
+            | Self::InvalidMessage(_)
+            | Self::InvalidTxn(_)
+            | Self::InvalidBlock(_) => Penalty::Major,
+            Self::TooLarge { .. } => Penalty::Fatal,
+        }
+    }
 }
@@ -0,0 +1,206 @@
+use crate::node::p2p::{
+    behaviour::{NodeBehaviour, NodeBehaviourEvent},
+    message::{NetworkCommand, NetworkEvent, NetworkMessage},
+    peer_score::{PeerScores, Penalty},
+};
+use {
+    futures::StreamExt,
+    libp2p::{
+        gossipsub::{GossipsubEvent, IdentTopic, MessageAcceptance},
+        mdns,
+        request_response::{
+            RequestId, RequestResponseEvent, RequestResponseMessage, ResponseChannel,
+        },
+        swarm::SwarmEvent,
+        PeerId, Swarm,
+    },
+    std::collections::{BTreeSet, HashMap},
+    tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender},
+};
+
+/// ### Drive the swarm until `P2P` drops its command sender (see `P2P::stop_p2p()`).
+///
+/// Runs commands from `P2P` against the swarm, and reports swarm events to the node as `NetworkEvent`s.
+/// Events are dropped if the node no longer listens for them.\
+/// Sync requests from peers wait in `responses` until the node answers them (see `P2P::respond()`).
+pub(super) async fn run_swarm(
+    mut swarm: Swarm<NodeBehaviour>,
+    mut commands: UnboundedReceiver<NetworkCommand>,
+    events: UnboundedSender<NetworkEvent>,
+) {
+    let mut scores = PeerScores::new();
+    let mut responses = HashMap::new();
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(command) => {
+                    handle_command(&mut swarm, &mut scores, &mut responses, command, &events)
+                }
+                None => break,
+            },
+            event = swarm.select_next_some() => {
+                handle_swarm_event(&mut swarm, &mut scores, &mut responses, event, &events)
+            }
+        }
+    }
+}
+
+/// ### Run a command from `P2P` against the swarm.
+fn handle_command(
+    swarm: &mut Swarm<NodeBehaviour>,
+    scores: &mut PeerScores,
+    responses: &mut HashMap<RequestId, ResponseChannel<NetworkMessage>>,
+    command: NetworkCommand,
+    events: &UnboundedSender<NetworkEvent>,
+) {
+    match command {
+        NetworkCommand::Publish(topic, bytes) => {
+            // fails without subscribed peers, or for a block or txn already published (see `behaviour::message_id()`)
+            let _ = swarm
+                .behaviour_mut()
+                .gossipsub
+                .publish(IdentTopic::new(topic), bytes);
+        }
+        NetworkCommand::DialPeer(peer_id) => {
+            if swarm.dial(peer_id).is_err() {
+                let _ = events.send(NetworkEvent::DialFailed(peer_id));
+            }
+        }
+        NetworkCommand::DialAddr(addr) => {
+            // failures are reported as `SwarmEvent::OutgoingConnectionError`, without a peer id
+            let _ = swarm.dial(addr);
+        }
+        NetworkCommand::Penalize(peer_id, penalty) => {
+            penalize(swarm, scores, peer_id, penalty, events)
+        }
+        NetworkCommand::Request(peer_id, message) => {
+            swarm.behaviour_mut().sync.send_request(&peer_id, message);
+        }
+        NetworkCommand::Respond(request_id, message) => {
+            // the peer may have given up on the request
+            if let Some(channel) = responses.remove(&request_id) {
+                let _ = swarm.behaviour_mut().sync.send_response(channel, message);
+            }
+        }
+    }
+}
+
+/// ### Update the behaviours for a swarm event, and report it to the node.
+fn handle_swarm_event<E>(
+    swarm: &mut Swarm<NodeBehaviour>,
+    scores: &mut PeerScores,
+    responses: &mut HashMap<RequestId, ResponseChannel<NetworkMessage>>,
+    event: SwarmEvent<NodeBehaviourEvent, E>,
+    events: &UnboundedSender<NetworkEvent>,
+) {
+    let report = |event| {
+        let _ = events.send(event);
+    };
+    match event {
+        SwarmEvent::NewListenAddr { address, .. } => report(NetworkEvent::Listening(address)),
+        SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+            report(NetworkEvent::PeerConnected(peer_id))
+        }
+        SwarmEvent::ConnectionClosed {
+            peer_id,
+            num_established: 0,
+            ..
+        } => report(NetworkEvent::PeerDisconnected(peer_id)),
+        SwarmEvent::OutgoingConnectionError {
+            peer_id: Some(peer_id),
+            ..
+        } => report(NetworkEvent::DialFailed(peer_id)),
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Mdns(mdns::Event::Discovered(found))) => {
+            // a peer is reported once per address
+            let peers: BTreeSet<_> = found.map(|(peer_id, _)| peer_id).collect();
+            for peer_id in peers {
+                if !swarm.is_connected(&peer_id) {
+                    let _ = swarm.dial(peer_id);
+                }
+                report(NetworkEvent::PeerDiscovered(peer_id));
+            }
+        }
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Mdns(mdns::Event::Expired(expired))) => {
+            let peers: BTreeSet<_> = expired.map(|(peer_id, _)| peer_id).collect();
+            for peer_id in peers {
+                if !swarm.behaviour().mdns.has_node(&peer_id) {
+                    report(NetworkEvent::PeerExpired(peer_id));
+                }
+            }
+        }
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Gossipsub(GossipsubEvent::Message {
+            propagation_source,
+            message_id,
+            message,
+        })) => {
+            // only relay messages that decode and pass pre-validation
+            let decoded = NetworkMessage::from_bytes(&message.data).and_then(|decoded| {
+                decoded.pre_validate()?;
+                Ok(decoded)
+            });
+            let acceptance = match decoded {
+                Ok(decoded) => {
+                    report(NetworkEvent::Message {
+                        source: propagation_source,
+                        message: decoded,
+                    });
+                    MessageAcceptance::Accept
+                }
+                Err(e) => {
+                    penalize(swarm, scores, propagation_source, e.penalty(), events);
+                    MessageAcceptance::Reject
+                }
+            };
+            let _ = swarm
+                .behaviour_mut()
+                .gossipsub
+                .report_message_validation_result(&message_id, &propagation_source, acceptance);
+        }
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Sync(RequestResponseEvent::Message {
+            peer,
+            message,
+        })) => match message {
+            RequestResponseMessage::Request {
+                request_id,
+                request,
+                channel,
+            } => {
+                responses.insert(request_id, channel);
+                report(NetworkEvent::Request {
+                    peer,
+                    request_id,
+                    message: request,
+                });
+            }
+            RequestResponseMessage::Response { response, .. } => report(NetworkEvent::Response {
+                peer,
+                message: response,
+            }),
+        },
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Sync(
+            RequestResponseEvent::OutboundFailure { peer, .. },
+        )) => report(NetworkEvent::RequestFailed(peer)),
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Sync(RequestResponseEvent::InboundFailure {
+            request_id,
+            ..
+        })) => {
+            responses.remove(&request_id);
+        }
+        _ => {}
+    }
+}
+
+/// ### Take a penalty off a peer's score, banning it once the score reaches `BAN_SCORE`.
+fn penalize(
+    swarm: &mut Swarm<NodeBehaviour>,
+    scores: &mut PeerScores,
+    peer_id: PeerId,
+    penalty: Penalty,
+    events: &UnboundedSender<NetworkEvent>,
+) {
+    if scores.penalize(peer_id, penalty) {
+        swarm.ban_peer_id(peer_id);
+        swarm.behaviour_mut().gossipsub.blacklist_peer(&peer_id);
+        let _ = events.send(NetworkEvent::PeerBanned(peer_id));
+    }
+}
@@ -2,7 +2,10 @@ use crate::{
     ledger::general::{PbKey, KP},
     node::p2p::P2P,
 };
-use {libp2p::PeerId, std::net::SocketAddr};
+use {
+    libp2p::{multiaddr::Protocol, Multiaddr, PeerId},
+    std::net::{IpAddr, SocketAddr},
+};
 
 impl P2P {
     /// ### Get property `P2P.socket_addr`.
@@ -10,6 +13,21 @@ impl P2P {
     pub fn socket_addr(&self) -> &SocketAddr {
         &self.socket_addr
     }
+    /// ### Get the address the swarm listens on, from `P2P.socket_addr`.
+    pub fn listen_addr(&self) -> Multiaddr {
+        let ip = match self.socket_addr.ip() {
+            IpAddr::V4(ip) => Protocol::Ip4(ip),
+            IpAddr::V6(ip) => Protocol::Ip6(ip),
+        };
+
+        Multiaddr::empty()
+            .with(ip)
+            .with(Protocol::Tcp(self.socket_addr.port()))
+    }
+    /// ### Whether the swarm task is running (see `P2P::start_p2p()`).
+    pub fn is_running(&self) -> bool {
+        self.task.as_ref().is_some_and(|task| !task.is_finished())
+    }
     fn kp(&self) -> &KP {
         &self.kp
     }
@@ -17,8 +35,15 @@ impl P2P {
     pub fn pbkey(&self) -> PbKey {
         self.kp().public.into()
     }
+    /// ### Get the node's keypair as a libp2p identity keypair.
+    pub(super) fn libp2p_kp(&self) -> libp2p::identity::Keypair {
+        let kp = libp2p::identity::ed25519::Keypair::decode(&mut self.kp().to_bytes())
+            .expect("Node keypair is a valid ed25519 keypair");
+
+        libp2p::identity::Keypair::Ed25519(kp)
+    }
     /// ### Get property `P2P.peer_id`.
     pub fn peer_id(&self) -> PeerId {
-        PeerId::from_public_key(&self.pbkey().to_owned().into())
+        self.libp2p_kp().public().to_peer_id()
     }
 }
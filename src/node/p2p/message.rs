@@ -0,0 +1,241 @@
+use crate::{
+    ledger::{
+        block::{block_id::BlockId, block_signature::BlockSignature, Block},
+        blockchain::Blockchain,
+        chain_id::ChainId,
+        general::PbKey,
+        txn::{error::TxnError, Txn},
+        wallet::Wallet,
+    },
+    node::p2p::{
+        behaviour::{BLOCKS_TOPIC, NODES_TOPIC, TXNS_TOPIC},
+        error::ProtocolError,
+        peer_score::Penalty,
+    },
+};
+use {
+    bincode::Options,
+    libp2p::{request_response::RequestId, Multiaddr, PeerId},
+    serde::{Deserialize, Serialize},
+};
+
+/// Version of the wire protocol - the first byte of every encoded `NetworkMessage`.
+pub const PROTOCOL_VERSION: u8 = 1;
+/// Largest encoded message accepted, in bytes - 8 MiB, room for a few blocks at the default block byte limit.
+pub const MAX_MESSAGE_BYTES: usize = 8 << 20;
+/// Most blocks asked for (or returned) at once.
+pub const MAX_BLOCKS_PER_REQUEST: u32 = 64;
+
+/// ## Message sent between nodes over the p2p network - on gossip topics and in request-response exchanges.
+///
+/// Encoded as `PROTOCOL_VERSION || bincode(message)`, at most `MAX_MESSAGE_BYTES` (see `to_bytes()`).
+/// New variants are only added at the end, so older nodes fail to decode them (`ProtocolError::Malformed`)
+/// rather than misreading them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkMessage {
+    /// A txn to add to the txn pool
+    NewTxn(Box<Txn>),
+    /// A block to add to the chain
+    NewBlock(Box<Block>),
+    /// Ask a peer for blocks
+    BlockRequest(BlockRequest),
+    /// Blocks asked for with a `BlockRequest`, in height order - fewer than asked for if the peer has no more
+    BlockResponse(Vec<Block>),
+    /// Handshake - the sender's view of the chain
+    Status(ChainStatus),
+    /// A validator's finality vote for a block
+    Vote(Vote),
+}
+
+impl NetworkMessage {
+    /// ### Get the pubsub topic the message is published on.
+    pub fn topic(&self) -> &'static str {
+        match self {
+            Self::NewTxn(_) => TXNS_TOPIC,
+            Self::NewBlock(_) => BLOCKS_TOPIC,
+            _ => NODES_TOPIC,
+        }
+    }
+    /// ### Encode the message for the wire - `ProtocolError::TooLarge` over `MAX_MESSAGE_BYTES`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ProtocolError> {
+        let mut bytes = vec![PROTOCOL_VERSION];
+        Self::codec().serialize_into(&mut bytes, self)?;
+        if bytes.len() > MAX_MESSAGE_BYTES {
+            return Err(ProtocolError::TooLarge {
+                size: bytes.len(),
+                max: MAX_MESSAGE_BYTES,
+            });
+        }
+
+        Ok(bytes)
+    }
+    /// ### Decode a message received from the wire.
+    ///
+    /// 1. Assert the message is within `MAX_MESSAGE_BYTES` - `TooLarge`
+    /// 1. Assert the version byte is `PROTOCOL_VERSION` - `Empty`, `UnsupportedVersion`
+    /// 1. Decode the rest, with no bytes left over - `Malformed`
+    /// 1. Assert request and response sizes are within `MAX_BLOCKS_PER_REQUEST` - `InvalidMessage`
+    /// 1. Assert txns and blocks (and the txns in them) have ids - `InvalidTxn`, `InvalidBlock`
+    ///
+    /// Map errors to a penalty for the sending peer with `ProtocolError::penalty()`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolError> {
+        if bytes.len() > MAX_MESSAGE_BYTES {
+            return Err(ProtocolError::TooLarge {
+                size: bytes.len(),
+                max: MAX_MESSAGE_BYTES,
+            });
+        }
+        let (version, payload) = bytes.split_first().ok_or(ProtocolError::Empty)?;
+        if *version != PROTOCOL_VERSION {
+            return Err(ProtocolError::UnsupportedVersion(*version));
+        }
+        let message: Self = Self::codec().deserialize(payload)?;
+        message.validate()?;
+
+        Ok(message)
+    }
+
+    /// ### Check what can be checked without the chain, before a gossiped message is relayed.
+    ///
+    /// - New txns: id and signature (see `Txn::is_valid()`) - `ProtocolError::InvalidTxn`
+    /// - New blocks: id and the leader's signature over the header (see `Block::is_valid()`) - `ProtocolError::InvalidBlock`
+    ///
+    /// The node validates the rest against its chain, i.e. `Blockchain::replay_block()`.
+    pub fn pre_validate(&self) -> Result<(), ProtocolError> {
+        match self {
+            Self::NewTxn(txn) => Ok(txn.is_valid()?),
+            Self::NewBlock(block) => Ok(block.is_valid(block.leader())?),
+            _ => Ok(()),
+        }
+    }
+
+    /// ### Check limits the encoding can't express, and that ids are set - the id getters panic otherwise.
+    fn validate(&self) -> Result<(), ProtocolError> {
+        match self {
+            Self::NewTxn(txn) if !txn.has_id() => Err(TxnError::EmptyId.into()),
+            Self::NewBlock(block) => Ok(block.has_ids()?),
+            Self::BlockRequest(request)
+                if request.limit == 0 || request.limit > MAX_BLOCKS_PER_REQUEST =>
+            {
+                Err(ProtocolError::InvalidMessage(
+                    "block request limit out of range",
+                ))
+            }
+            Self::BlockResponse(blocks) if blocks.len() > MAX_BLOCKS_PER_REQUEST as usize => {
+                Err(ProtocolError::InvalidMessage("too many blocks in response"))
+            }
+            Self::BlockResponse(blocks) => {
+                blocks.iter().try_for_each(Block::has_ids)?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+    /// ### Bincode options for the wire - varint integers, bounded allocations, no trailing bytes.
+    fn codec() -> impl Options {
+        bincode::DefaultOptions::new().with_limit(MAX_MESSAGE_BYTES as u64)
+    }
+}
+
+/// ## Ask a peer for up to `limit` blocks, starting at height `from`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRequest {
+    pub from: u128,
+    pub limit: u32,
+}
+
+/// ## A node's view of the chain - exchanged when peers connect, to find out who is ahead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainStatus {
+    pub chain_id: ChainId,
+    pub genesis_id: BlockId,
+    pub height: u128,
+    pub tip_id: BlockId,
+    /// Height the node can no longer roll back past - see `Blockchain::finalized_height()`
+    pub finalized_height: u128,
+}
+impl From<&Blockchain> for ChainStatus {
+    fn from(value: &Blockchain) -> Self {
+        let tip = value.last_block();
+
+        Self {
+            chain_id: value.chain_id().clone(),
+            genesis_id: value.block_at(1).unwrap_or(tip).id(),
+            height: *tip.blockheight(),
+            tip_id: tip.id(),
+            finalized_height: value.finalized_height(),
+        }
+    }
+}
+
+/// ## A validator's finality vote for a block - collected into a `FinalityCertificate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vote {
+    pub block_id: BlockId,
+    pub validator: PbKey,
+    pub signature: BlockSignature,
+}
+impl Vote {
+    /// ### Vote for a block with the wallet owner's key.
+    pub fn sign(block_id: BlockId, wallet: &Wallet) -> Self {
+        Self {
+            block_id,
+            validator: wallet.pbkey(),
+            signature: wallet.sign_finality(&block_id),
+        }
+    }
+}
+
+/// ## Event reported by the swarm task to the node (see `P2P::start_p2p()`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkEvent {
+    /// The swarm is listening on a new address
+    Listening(Multiaddr),
+    /// A peer was found on the local network
+    PeerDiscovered(PeerId),
+    /// A peer found on the local network is no longer reachable
+    PeerExpired(PeerId),
+    /// A connection to a peer was opened
+    PeerConnected(PeerId),
+    /// The last connection to a peer was closed
+    PeerDisconnected(PeerId),
+    /// A peer could not be dialed
+    DialFailed(PeerId),
+    /// A peer's score reached `BAN_SCORE` - its connections are closed and refused
+    PeerBanned(PeerId),
+    /// A message received from `source` - the peer that relayed it to the node (see `NetworkMessage::pre_validate()`)
+    Message {
+        source: PeerId,
+        message: NetworkMessage,
+    },
+    /// A sync request from a peer - answer it with `P2P::respond()`
+    Request {
+        peer: PeerId,
+        request_id: RequestId,
+        message: NetworkMessage,
+    },
+    /// A peer's answer to a sync request (see `P2P::request()`)
+    Response {
+        peer: PeerId,
+        message: NetworkMessage,
+    },
+    /// A sync request to a peer failed, i.e. timed out or the connection closed
+    RequestFailed(PeerId),
+}
+
+/// ## Command sent by `P2P` to the swarm task.
+#[derive(Debug)]
+pub(super) enum NetworkCommand {
+    /// Publish an encoded `NetworkMessage` to a topic (see `NetworkMessage::topic()`)
+    Publish(&'static str, Vec<u8>),
+    /// Dial a peer by id - its addresses come from the behaviours (i.e. mdns)
+    DialPeer(PeerId),
+    /// Dial a peer by address
+    DialAddr(Multiaddr),
+    /// Penalize a peer, i.e. for sending an invalid txn or block
+    Penalize(PeerId, Penalty),
+    /// Send a sync request to a peer
+    Request(PeerId, NetworkMessage),
+    /// Answer a peer's sync request
+    Respond(RequestId, NetworkMessage),
+}
@@ -1,22 +1,36 @@
+pub mod behaviour;
 pub mod error;
+mod event_loop;
 mod getters;
+pub mod message;
+pub mod peer_score;
+pub mod sync_codec;
 mod types;
-mod utils;
 
 use crate::{
     ledger::general::{PbKey, KP},
-    node::p2p::utils::ListResponse,
+    node::p2p::{
+        behaviour::NodeBehaviour,
+        error::P2PError,
+        message::{NetworkCommand, NetworkEvent, NetworkMessage},
+        peer_score::Penalty,
+        types::Result,
+    },
 };
 use {
     libp2p::{
         core::transport::upgrade,
-        mdns,
         mplex::MplexConfig,
         noise::{NoiseConfig, X25519Spec},
+        request_response::RequestId,
         swarm::SwarmBuilder,
-        tcp, PeerId, Swarm, Transport,
+        tcp, Multiaddr, PeerId, Swarm, Transport,
     },
     std::net::{IpAddr, SocketAddr},
+    tokio::{
+        sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+        task::JoinHandle,
+    },
 };
 
 /// ## Peer-to-peer connection.
@@ -24,6 +38,10 @@ use {
 pub struct P2P {
     socket_addr: SocketAddr,
     kp: KP,
+    /// Sends commands to the swarm task - `None` until started (see `start_p2p()`)
+    commands: Option<UnboundedSender<NetworkCommand>>,
+    /// The swarm task
+    task: Option<JoinHandle<()>>,
 }
 
 impl P2P {
@@ -33,77 +51,111 @@ impl P2P {
         Self {
             socket_addr: SocketAddr::new(host, port),
             kp,
+            commands: None,
+            task: None,
         }
     }
 
     /////////////////////////////////////////////////////
     /////////////////////// ACTIONS /////////////////////
+    /// ### Start the swarm task, listening on `socket_addr` - returns the receiver of its `NetworkEvent`s.
+    ///
+    /// The task owns the swarm and runs until `stop_p2p()` (or until `P2P` is dropped).
+    /// Must be called from within a tokio runtime.
+    pub fn start_p2p(&mut self) -> Result<UnboundedReceiver<NetworkEvent>> {
+        if self.is_running() {
+            return Err(P2PError::AlreadyStarted);
+        }
+        let mut swarm = self.build_swarm()?;
+        swarm.listen_on(self.listen_addr())?;
+
+        let (command_sender, command_receiver) = mpsc::unbounded_channel();
+        let (event_sender, event_receiver) = mpsc::unbounded_channel();
+        self.task = Some(tokio::spawn(event_loop::run_swarm(
+            swarm,
+            command_receiver,
+            event_sender,
+        )));
+        self.commands = Some(command_sender);
+
+        Ok(event_receiver)
+    }
+    /// ### Stop the swarm task, closing its connections.
+    pub async fn stop_p2p(&mut self) -> Result<()> {
+        // the task stops once its command channel closes
+        self.commands = None;
+        if let Some(task) = self.task.take() {
+            task.await.map_err(|_| P2PError::P2P)?;
+        }
+
+        Ok(())
+    }
+    /// ### Publish a message to the peers subscribed to its topic (see `NetworkMessage::topic()`).
+    pub fn publish(&self, message: &NetworkMessage) -> Result<()> {
+        self.send_command(NetworkCommand::Publish(
+            message.topic(),
+            message.to_bytes()?,
+        ))
+    }
     /// ### Discover peer using public key.
     ///
     /// 1. Get `peer_id` from peer's public key
-    /// 1. Connect to peer using `peer_id`
-    pub fn discover_peer(&self, peer: &PbKey) {
-        let _peer_id: PeerId = PeerId::from_public_key(&peer.to_owned().into());
-        let (_response_sender, mut _response_rcv) =
-            tokio::sync::mpsc::unbounded_channel::<ListResponse>();
+    /// 1. Connect to peer using `peer_id` - its addresses are found by the swarm's behaviours (i.e. mdns)
+    pub fn discover_peer(&self, peer: &PbKey) -> Result<()> {
+        let peer_id: PeerId = PeerId::from_public_key(&peer.to_owned().try_into()?);
 
+        self.send_command(NetworkCommand::DialPeer(peer_id))
+    }
+    /// ### Connect to a peer at a known address.
+    pub fn dial(&self, addr: Multiaddr) -> Result<()> {
+        self.send_command(NetworkCommand::DialAddr(addr))
+    }
+    /// ### Send a sync request (`NetworkMessage::Status` or `NetworkMessage::BlockRequest`) to a peer.
+    /// The answer is reported as `NetworkEvent::Response`, or `NetworkEvent::RequestFailed`.
+    pub fn request(&self, peer_id: PeerId, message: NetworkMessage) -> Result<()> {
+        self.send_command(NetworkCommand::Request(peer_id, message))
+    }
+    /// ### Answer a peer's sync request, reported as `NetworkEvent::Request`.
+    pub fn respond(&self, request_id: RequestId, message: NetworkMessage) -> Result<()> {
+        self.send_command(NetworkCommand::Respond(request_id, message))
+    }
+    /// ### Penalize a peer for misbehaving, i.e. sending an invalid txn or block - see `PeerScores`.
+    /// Reported as `NetworkEvent::PeerBanned` if the peer gets banned.
+    pub fn penalize(&self, peer_id: PeerId, penalty: Penalty) -> Result<()> {
+        self.send_command(NetworkCommand::Penalize(peer_id, penalty))
+    }
+    /////////////////////// ACTIONS /////////////////////
+    /////////////////////////////////////////////////////
+
+    /// ### Build the swarm: transport and network behaviours.
+    ///
+    /// Transport:
+    /// 1. use tcp with tokio's async implementation
+    /// 1. once connection is established, secure the connection by upgrading with `noise` protocol
+    /// 1. use `NoiseConfig:xx` handshake pattern - its the only one compatible with libp2p
+    /// 1. multiplex the transport with `Mplex`
+    ///
+    /// Network behaviour - see `NodeBehaviour`.
+    fn build_swarm(&self) -> Result<Swarm<NodeBehaviour>> {
         // set up the auth key for the transport
-        // @todo implement from-conversion
-        let kp = libp2p::identity::ed25519::Keypair::decode(&mut self.kp.to_bytes()).unwrap();
-        let kp = libp2p::identity::Keypair::Ed25519(kp);
         let kp = libp2p::noise::Keypair::<X25519Spec>::new()
-            .into_authentic(&kp)
+            .into_authentic(&self.libp2p_kp())
             .expect("should be able to create authentic-key");
-
-        // create transport:
-        // 1. use tcp with tokio's async implementation
-        // 2. once connection is established, secure the connection by upgrading with `noise` protocol
-        // 3. use `NoiseConfig:xx` handshake pattern - its the only one compatible with libp2p
-        // 4. multiplex the transport with `Mplex`
         let transport = libp2p::tcp::tokio::Transport::new(tcp::Config::default().nodelay(true))
-            .upgrade(upgrade::Version::V1) // V1Lazy
-            .authenticate(NoiseConfig::xx(kp.clone()).into_authenticated())
+            .upgrade(upgrade::Version::V1)
+            .authenticate(NoiseConfig::xx(kp).into_authenticated())
             .multiplex(MplexConfig::new())
             .boxed();
+        let behaviour = NodeBehaviour::new(self.libp2p_kp())?;
 
-        // define logic of network behaviour with `NetworkBehaviour`
-        // 1. handle network behavior with `floodsub` - publish/subscribe to topics
-        let mut floodsub_behaviour = libp2p::floodsub::Floodsub::new(self.peer_id());
-        let topic = libp2p::floodsub::Topic::new("nodes");
-        // 1.1 subscribe to `nodes` topic, to send and receive events
-        floodsub_behaviour.subscribe(topic.clone());
-
-        // 2. discover nodes with mdns protocol - will be changed in the future
-        let _mdns_behaviour = libp2p::mdns::tokio::Behaviour::new(mdns::Config::default()).unwrap();
-
-        // 3. create `swarm` connection manager
-        // this handles using the transport and executes network behavior
-        // i.e. triggering and receiving events
-        let mut swarm =
-            SwarmBuilder::with_tokio_executor(transport, floodsub_behaviour, self.peer_id())
-                .build();
-        // let mut swarm = SwarmBuilder::with_executor(
-        //     transport,
-        //     floodsub_behaviour,
-        //     self.peer_id(),
-        //     Box::new(|fut| {
-        //         tokio::spawn(fut);
-        //     }),
-        // )
-        // .build();
-        Swarm::listen_on(
-            &mut swarm,
-            format!(
-                "/ip4/{}/tcp/{}",
-                self.socket_addr().ip(),
-                self.socket_addr().port()
-            )
-            .parse()
-            .expect("should be able to get a local socket"),
-        )
-        .expect("should be able to start swarm");
+        Ok(SwarmBuilder::with_tokio_executor(transport, behaviour, self.peer_id()).build())
+    }
+    /// ### Send a command to the swarm task - `P2PError::InitP2P` if it isn't running.
+    fn send_command(&self, command: NetworkCommand) -> Result<()> {
+        self.commands
+            .as_ref()
+            .ok_or(P2PError::InitP2P)?
+            .send(command)
+            .map_err(|_| P2PError::InitP2P)
     }
-    pub async fn handle_list_peers() {}
-    /////////////////////// ACTIONS /////////////////////
-    /////////////////////////////////////////////////////
 }
@@ -0,0 +1,54 @@
+use {libp2p::PeerId, std::collections::HashMap};
+
+/// Score at or below which a peer is banned - peers start at 0.
+pub const BAN_SCORE: i32 = -100;
+
+/// ## How badly a peer misbehaved - i.e. for a message that doesn't decode (see `ProtocolError::penalty()`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Penalty {
+    /// Likely harmless, i.e. a peer on another protocol version
+    Minor,
+    /// Invalid data a correct peer wouldn't send
+    Major,
+    /// Abuse, i.e. oversized messages - banned right away
+    Fatal,
+}
+impl Penalty {
+    /// ### Get the points the penalty takes off a peer's score.
+    pub fn points(&self) -> i32 {
+        match self {
+            Self::Minor => 10,
+            Self::Major => 50,
+            Self::Fatal => -BAN_SCORE,
+        }
+    }
+}
+
+/// ## Score of each peer that was penalized - peers are banned once their score reaches `BAN_SCORE`.
+#[derive(Debug, Clone, Default)]
+pub struct PeerScores {
+    scores: HashMap<PeerId, i32>,
+}
+
+impl PeerScores {
+    /// ### Initialize an empty `PeerScores` - every peer at 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ### Get a peer's score - 0 unless penalized.
+    pub fn score(&self, peer_id: &PeerId) -> i32 {
+        self.scores.get(peer_id).copied().unwrap_or(0)
+    }
+    /// ### Whether a peer's score reached `BAN_SCORE`.
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.score(peer_id) <= BAN_SCORE
+    }
+    /// ### Take a penalty off a peer's score - returns true if this got the peer banned.
+    pub fn penalize(&mut self, peer_id: PeerId, penalty: Penalty) -> bool {
+        let was_banned = self.is_banned(&peer_id);
+        *self.scores.entry(peer_id).or_insert(0) -= penalty.points();
+
+        !was_banned && self.is_banned(&peer_id)
+    }
+}
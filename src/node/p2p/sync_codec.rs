@@ -0,0 +1,90 @@
+use crate::node::p2p::message::{NetworkMessage, MAX_MESSAGE_BYTES};
+use {
+    async_trait::async_trait,
+    futures::{AsyncRead, AsyncWrite, AsyncWriteExt},
+    libp2p::{
+        core::upgrade::{read_length_prefixed, write_length_prefixed},
+        request_response::{ProtocolName, RequestResponseCodec},
+    },
+    std::io,
+};
+
+/// Name of the request-response protocol nodes sync their chains with.
+pub const SYNC_PROTOCOL: &[u8] = b"/posbc/sync/1";
+
+/// ## The sync protocol - see `SYNC_PROTOCOL`.
+#[derive(Debug, Clone, Default)]
+pub struct SyncProtocol;
+impl ProtocolName for SyncProtocol {
+    fn protocol_name(&self) -> &[u8] {
+        SYNC_PROTOCOL
+    }
+}
+
+/// ## Reads and writes sync requests and responses - length-prefixed `NetworkMessage`s, like gossip.
+///
+/// Requests are `NetworkMessage::Status` or `NetworkMessage::BlockRequest`,
+/// answered with a `NetworkMessage::Status` or `NetworkMessage::BlockResponse`.
+#[derive(Debug, Clone, Default)]
+pub struct SyncCodec;
+
+#[async_trait]
+impl RequestResponseCodec for SyncCodec {
+    type Protocol = SyncProtocol;
+    type Request = NetworkMessage;
+    type Response = NetworkMessage;
+
+    async fn read_request<T>(&mut self, _: &SyncProtocol, io: &mut T) -> io::Result<NetworkMessage>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_message(io).await
+    }
+    async fn read_response<T>(&mut self, _: &SyncProtocol, io: &mut T) -> io::Result<NetworkMessage>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_message(io).await
+    }
+    async fn write_request<T>(
+        &mut self,
+        _: &SyncProtocol,
+        io: &mut T,
+        request: NetworkMessage,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_message(io, &request).await
+    }
+    async fn write_response<T>(
+        &mut self,
+        _: &SyncProtocol,
+        io: &mut T,
+        response: NetworkMessage,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_message(io, &response).await
+    }
+}
+
+/// ### Read a message of at most `MAX_MESSAGE_BYTES` - messages that don't decode are `InvalidData`.
+async fn read_message<T: AsyncRead + Unpin + Send>(io: &mut T) -> io::Result<NetworkMessage> {
+    let bytes = read_length_prefixed(io, MAX_MESSAGE_BYTES).await?;
+
+    NetworkMessage::from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+/// ### Write a message, then close the stream.
+async fn write_message<T: AsyncWrite + Unpin + Send>(
+    io: &mut T,
+    message: &NetworkMessage,
+) -> io::Result<()> {
+    let bytes = message
+        .to_bytes()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    write_length_prefixed(io, bytes).await?;
+
+    io.close().await
+}
@@ -1,22 +1,22 @@
-use crate::node::{types::Result, Node};
+use crate::{
+    ledger::{blockchain::Blockchain, txn_pool::TxnPool},
+    node::{types::Result, Node},
+};
 
 impl Node {
     //////////////////////////////////////////////////
     ////////////////// INITIALIZERS //////////////////
 
-    /// ### INCOMPLETE Initialize the blockchain pulled from the peer to peer network.
-    ///
-    /// 1. Connect to p2p network
-    /// 1. Check with other nodes
-    /// 1. Fetch the hash of the blockchain
-    /// 1. Update blockchain if hash mismatch (get missing blocks)
-    /// Another setter for blockchain would be `set_blockchain_sync`
-    #[deprecated = "Not implemented"]
-    pub fn sync_blockchain(&mut self) -> Result<()> {
-        todo!();
-        // Ok(())
+    /// ### Set property `Node.blockchain`.
+    /// i.e. to the chain stored on disk (see `Blockchain::open()`), so the node restarts where it stopped.
+    pub fn set_blockchain(&mut self, blockchain: Blockchain) {
+        self.blockchain = blockchain;
+    }
+    /// ### Set property `Node.txn_pool`.
+    /// i.e. to a pool on a storage backend (see `TxnPool::open()`), so pending txns survive a restart.
+    pub fn set_txn_pool(&mut self, txn_pool: TxnPool) {
+        self.txn_pool = txn_pool;
     }
-
     /// ### INCOMPLETE Initialize the transaction pool pulled from the peer to peer network.
     ///
     /// 1. Connect to p2p network
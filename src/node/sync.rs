@@ -0,0 +1,423 @@
+use crate::{
+    ledger::block::Block,
+    node::{
+        error::NodeError,
+        p2p::{
+            error::P2PError,
+            message::{
+                BlockRequest, ChainStatus, NetworkEvent, NetworkMessage, MAX_BLOCKS_PER_REQUEST,
+            },
+            peer_score::Penalty,
+        },
+        types::Result,
+        Node,
+    },
+    storage::error::StorageError,
+};
+use {
+    libp2p::{request_response::RequestId, PeerId},
+    std::collections::{BTreeMap, BTreeSet},
+};
+
+/// ## Where a node is in catching up with its peers' chains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncState {
+    /// Not synced yet, or fell behind a gossiped block - see `Node::sync_blockchain()`
+    #[default]
+    Idle,
+    /// Requesting blocks from peers, and applying them in order
+    Syncing,
+    /// Caught up with the peers - new blocks come by gossip
+    Following,
+}
+
+/// ## What a sync request in flight asked a peer for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncRequest {
+    Status,
+    Blocks(BlockRequest),
+}
+
+/// ## Tracks the sync of a node's chain with its peers - see `Node::sync_blockchain()`.
+///
+/// Peers exchange a `ChainStatus` when they connect. While syncing, the heights missing up to the best peer's
+/// are requested in batches of up to `MAX_BLOCKS_PER_REQUEST` blocks, one batch in flight per peer,
+/// and the batches received are applied in height order.
+#[derive(Debug, Default)]
+pub struct SyncManager {
+    state: SyncState,
+    /// Peers with an open connection
+    connected: BTreeSet<PeerId>,
+    /// Last status of each connected peer on the node's chain (same chain id and genesis)
+    statuses: BTreeMap<PeerId, ChainStatus>,
+    /// The request each peer is answering
+    in_flight: BTreeMap<PeerId, SyncRequest>,
+    /// Blocks received and not applied yet, by the height of the first block
+    received: BTreeMap<u128, (PeerId, Vec<Block>)>,
+    /// Peers that failed a request during the current sync - they're not asked again
+    failed: BTreeSet<PeerId>,
+    /// Error storing the received blocks, which stopped the current sync - see `abort()`
+    error: Option<StorageError>,
+}
+
+impl SyncManager {
+    /// ### Create a sync manager without peers, in `SyncState::Idle`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `SyncManager.state`.
+    pub fn state(&self) -> SyncState {
+        self.state
+    }
+    /// ### Get the last status of a peer on the node's chain.
+    pub fn peer_status(&self, peer_id: &PeerId) -> Option<&ChainStatus> {
+        self.statuses.get(peer_id)
+    }
+    /// ### Get the greatest height reported by a peer on the node's chain - 0 without peers.
+    ///
+    /// Peers that failed a request during the current sync don't count - a peer can't stall the sync
+    /// by claiming blocks it doesn't serve.
+    pub fn best_height(&self) -> u128 {
+        self.statuses
+            .iter()
+            .filter(|(peer_id, _)| !self.failed.contains(peer_id))
+            .map(|(_, status)| status.height)
+            .max()
+            .unwrap_or(0)
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Start syncing - peers that failed an earlier sync are asked again.
+    fn start(&mut self) {
+        self.state = SyncState::Syncing;
+        self.failed.clear();
+        self.error = None;
+    }
+    /// ### Stop syncing after the received blocks couldn't be stored - the peers aren't at fault.
+    fn abort(&mut self, error: StorageError) {
+        self.state = SyncState::Idle;
+        self.received.clear();
+        self.error = Some(error);
+    }
+    /// ### Record a new connection.
+    fn peer_connected(&mut self, peer_id: PeerId) {
+        self.connected.insert(peer_id);
+    }
+    /// ### Forget a peer once its last connection closed - its batch in flight is requested again.
+    fn peer_disconnected(&mut self, peer_id: &PeerId) {
+        self.connected.remove(peer_id);
+        self.statuses.remove(peer_id);
+        self.in_flight.remove(peer_id);
+    }
+    /// ### Mark a status request to a peer in flight - false if the peer is answering another request.
+    fn status_requested(&mut self, peer_id: PeerId) -> bool {
+        if self.in_flight.contains_key(&peer_id) {
+            return false;
+        }
+        self.in_flight.insert(peer_id, SyncRequest::Status);
+
+        true
+    }
+    /// ### Record a peer's status - peers on another chain (see `own`) are not synced with.
+    fn on_status(&mut self, peer_id: PeerId, status: ChainStatus, own: &ChainStatus) {
+        if status.chain_id == own.chain_id && status.genesis_id == own.genesis_id {
+            self.statuses.insert(peer_id, status);
+        } else {
+            self.statuses.remove(&peer_id);
+        }
+    }
+    /// ### Record a peer's answer to the request it was sent - false if the answer is invalid.
+    ///
+    /// Block responses must hold consecutive blocks from the requested height, at most as many as requested.
+    /// A peer with an empty or invalid answer, or one not answering its request, isn't asked again during this sync.
+    fn on_response(&mut self, peer_id: PeerId, message: NetworkMessage, own: &ChainStatus) -> bool {
+        match (self.in_flight.remove(&peer_id), message) {
+            (Some(SyncRequest::Status), NetworkMessage::Status(status)) => {
+                self.on_status(peer_id, status, own);
+                true
+            }
+            (Some(SyncRequest::Blocks(request)), NetworkMessage::BlockResponse(blocks)) => {
+                let is_valid = blocks.len() <= request.limit as usize
+                    && blocks
+                        .iter()
+                        .zip(request.from..)
+                        .all(|(block, height)| *block.blockheight() == height);
+                if !is_valid || blocks.is_empty() {
+                    self.failed.insert(peer_id);
+                } else {
+                    self.received.insert(request.from, (peer_id, blocks));
+                }
+                is_valid
+            }
+            _ => {
+                self.failed.insert(peer_id);
+                false
+            }
+        }
+    }
+    /// ### Record a failed request (or an invalid batch) - the peer isn't asked again during this sync.
+    fn on_failure(&mut self, peer_id: PeerId) {
+        self.in_flight.remove(&peer_id);
+        self.failed.insert(peer_id);
+    }
+    /// ### Note a gossiped block above the chain's tip - the node has to sync again.
+    pub(super) fn fell_behind(&mut self) {
+        if self.state == SyncState::Following {
+            self.state = SyncState::Idle;
+        }
+    }
+
+    /// ### Plan block requests for the heights missing above `height` - at most one per peer without a request in flight.
+    ///
+    /// Heights already requested, or received, are skipped. Each peer is asked for heights it reported having.
+    fn next_requests(&mut self, height: u128) -> Vec<(PeerId, BlockRequest)> {
+        let idle: Vec<_> = self
+            .statuses
+            .iter()
+            .filter(|(peer_id, _)| {
+                !self.in_flight.contains_key(peer_id) && !self.failed.contains(peer_id)
+            })
+            .map(|(peer_id, status)| (*peer_id, status.height))
+            .collect();
+        // (from, to) of every height requested or received, to skip
+        let mut covered: Vec<(u128, u128)> = self
+            .in_flight
+            .values()
+            .filter_map(|request| match request {
+                SyncRequest::Blocks(request) => Some((
+                    request.from,
+                    request.from.saturating_add(request.limit as u128),
+                )),
+                SyncRequest::Status => None,
+            })
+            .chain(
+                self.received
+                    .iter()
+                    .map(|(from, (_, blocks))| (*from, from.saturating_add(blocks.len() as u128))),
+            )
+            .collect();
+
+        let mut requests = vec![];
+        for (peer_id, peer_height) in idle {
+            let mut from = height + 1;
+            while let Some(&(_, to)) = covered
+                .iter()
+                .find(|(start, to)| (*start..*to).contains(&from))
+            {
+                from = to;
+            }
+            if from > peer_height {
+                continue;
+            }
+            let gap_end = covered
+                .iter()
+                .map(|(start, _)| *start)
+                .filter(|start| *start > from)
+                .min()
+                .unwrap_or(u128::MAX);
+            let limit = (MAX_BLOCKS_PER_REQUEST as u128)
+                .min(gap_end - from)
+                .min((peer_height - from).saturating_add(1)) as u32;
+            let request = BlockRequest { from, limit };
+            covered.push((from, from.saturating_add(limit as u128)));
+            self.in_flight.insert(peer_id, SyncRequest::Blocks(request));
+            requests.push((peer_id, request));
+        }
+
+        requests
+    }
+    /// ### Take the next received blocks for a chain at `height` - blocks at or below it are dropped.
+    fn next_batch(&mut self, height: u128) -> Option<(PeerId, Vec<Block>)> {
+        while let Some(entry) = self.received.first_entry() {
+            let from = *entry.key();
+            if from > height + 1 {
+                return None;
+            }
+            let (peer_id, blocks) = entry.remove();
+            let blocks: Vec<_> = blocks
+                .into_iter()
+                .skip((height + 1 - from) as usize)
+                .collect();
+            if !blocks.is_empty() {
+                return Some((peer_id, blocks));
+            }
+        }
+
+        None
+    }
+    /// ### Finish the sync once the chain at `height` caught up with the best peer - or once no peer can help.
+    ///
+    /// Waits for the status requests in flight, so a sync started right after connecting knows who is ahead.
+    fn update_state(&mut self, height: u128) {
+        let awaiting_status = self
+            .in_flight
+            .values()
+            .any(|request| request == &SyncRequest::Status);
+        if self.state != SyncState::Syncing || awaiting_status {
+            return;
+        }
+        if height >= self.best_height() {
+            self.state = SyncState::Following;
+            self.received.clear();
+        } else if self.in_flight.is_empty() && self.received.is_empty() {
+            // every peer ahead failed
+            self.state = SyncState::Idle;
+        }
+    }
+}
+
+impl Node {
+    /// ### Sync the blockchain with the node's peers, then follow new blocks by gossip - returns the number of blocks added.
+    ///
+    /// 1. Ask every connected peer for its `ChainStatus` (peers also exchange it when they connect)
+    /// 1. Request the missing heights, up to the best peer's, in batches from several peers in parallel
+    /// 1. Validate and add the received blocks in height order - see `Blockchain::replay_block()`
+    /// 1. Once caught up, switch to `SyncState::Following` - blocks gossiped by peers extend the chain from there
+    ///
+    /// A peer answering with invalid blocks is penalized (`Penalty::Major`), and its batch is requested from another peer.\
+    /// Network events are handled while syncing, like in `next_network_event()`.
+    /// The chain must hold its genesis block - `NodeError::InitBlockchain`; p2p must be running - `P2PError::InitP2P`.
+    /// Fails with `NodeError::SyncStalled` if the peers ahead can't serve the missing blocks,
+    /// and with the `StorageError` (`NodeError::Ledger`) if the blocks can't be stored.
+    pub async fn sync_blockchain(&mut self) -> Result<u128> {
+        if self.blockchain.height() == 0 {
+            return Err(NodeError::InitBlockchain);
+        }
+        if !self.p2p.is_running() {
+            return Err(P2PError::InitP2P.into());
+        }
+        let start = self.blockchain.height();
+        self.sync.start();
+        let peers: Vec<_> = self.sync.connected.iter().copied().collect();
+        for peer_id in peers {
+            self.request_status(peer_id);
+        }
+        self.sync_step();
+        // every request in flight is answered, or fails after a timeout
+        while self.sync.state() == SyncState::Syncing {
+            if self.next_network_event().await.is_none() {
+                return Err(P2PError::InitP2P.into());
+            }
+        }
+
+        if let Some(e) = self.sync.error.take() {
+            return Err(NodeError::Ledger(e.into()));
+        }
+        match self.sync.state() {
+            SyncState::Following => Ok(self.blockchain.height() - start),
+            _ => Err(NodeError::SyncStalled(self.blockchain.height())),
+        }
+    }
+
+    /// ### Update the sync for a network event - peers' statuses, requests and responses.
+    pub(super) fn handle_sync_event(&mut self, event: &NetworkEvent) {
+        match event {
+            NetworkEvent::PeerConnected(peer_id) => {
+                self.sync.peer_connected(*peer_id);
+                self.request_status(*peer_id);
+            }
+            NetworkEvent::PeerDisconnected(peer_id) => self.sync.peer_disconnected(peer_id),
+            NetworkEvent::Request {
+                peer,
+                request_id,
+                message,
+            } => self.answer_request(*peer, *request_id, message),
+            NetworkEvent::Response { peer, message } => {
+                if self.blockchain.height() > 0 {
+                    let own = ChainStatus::from(&self.blockchain);
+                    if !self.sync.on_response(*peer, message.clone(), &own) {
+                        let _ = self.p2p.penalize(*peer, Penalty::Major);
+                    }
+                }
+            }
+            NetworkEvent::RequestFailed(peer_id) => self.sync.on_failure(*peer_id),
+            _ => return,
+        }
+        self.sync_step();
+    }
+    /// ### Ask a peer for its status, sending the node's own - skipped if the peer is answering another request.
+    fn request_status(&mut self, peer_id: PeerId) {
+        if self.blockchain.height() == 0 || !self.sync.status_requested(peer_id) {
+            return;
+        }
+        let status = NetworkMessage::Status(ChainStatus::from(&self.blockchain));
+        if self.p2p.request(peer_id, status).is_err() {
+            self.sync.on_failure(peer_id);
+        }
+    }
+    /// ### Answer a peer's sync request - requests are left unanswered until the chain holds its genesis block.
+    fn answer_request(&mut self, peer_id: PeerId, request_id: RequestId, message: &NetworkMessage) {
+        if self.blockchain.height() == 0 {
+            return;
+        }
+        let own = ChainStatus::from(&self.blockchain);
+        let response = match message {
+            NetworkMessage::Status(status) => {
+                self.sync.on_status(peer_id, status.clone(), &own);
+                NetworkMessage::Status(own)
+            }
+            NetworkMessage::BlockRequest(request) => {
+                NetworkMessage::BlockResponse(self.requested_blocks(request))
+            }
+            _ => {
+                let _ = self.p2p.penalize(peer_id, Penalty::Major);
+                return;
+            }
+        };
+        let _ = self.p2p.respond(request_id, response);
+    }
+    /// ### Get the blocks a peer requested - none if their txns were pruned (see `PruningMode::HeadersOnly`).
+    fn requested_blocks(&self, request: &BlockRequest) -> Vec<Block> {
+        if request.from <= self.blockchain.pruned_height() {
+            return vec![];
+        }
+        let limit = request.limit.min(MAX_BLOCKS_PER_REQUEST) as u128;
+
+        (request.from..request.from.saturating_add(limit))
+            .map_while(|height| self.blockchain.block_at(height).cloned())
+            .collect()
+    }
+    /// ### Add the received blocks that extend the chain, then request more - see `SyncManager`.
+    fn sync_step(&mut self) {
+        if self.sync.state() != SyncState::Syncing {
+            return;
+        }
+        while let Some((peer_id, blocks)) = self.sync.next_batch(self.blockchain.height()) {
+            for block in blocks {
+                if !self.extends_tip(&block) {
+                    let _ = self.p2p.penalize(peer_id, Penalty::Major);
+                    self.sync.on_failure(peer_id);
+                    break;
+                }
+                if let Err(e) = self.apply_block(block) {
+                    // storage errors aren't the peer's fault - but the sync can't go on
+                    match e.downcast::<StorageError>() {
+                        Ok(e) => self.sync.abort(e),
+                        Err(_) => {
+                            let _ = self.p2p.penalize(peer_id, Penalty::Major);
+                            self.sync.on_failure(peer_id);
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+        if self.sync.state() != SyncState::Syncing {
+            return;
+        }
+        for (peer_id, request) in self.sync.next_requests(self.blockchain.height()) {
+            if self
+                .p2p
+                .request(peer_id, NetworkMessage::BlockRequest(request))
+                .is_err()
+            {
+                self.sync.on_failure(peer_id);
+            }
+        }
+        self.sync.update_state(self.blockchain.height());
+    }
+}
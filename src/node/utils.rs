@@ -1,19 +1,49 @@
 use crate::{
     ledger::wallet::Wallet,
-    node::{types::Result, Node},
+    node::{
+        p2p::message::{NetworkEvent, NetworkMessage},
+        types::Result,
+        Node,
+    },
 };
 
 impl Node {
-    /// ### Start the p2p connection.
-    /// @todo call libp2p
+    /// ### Start the p2p connection - see `P2P::start_p2p()`.
+    /// The swarm's events are read with `next_network_event()`.
     pub fn start_p2p(&mut self) -> Result<()> {
-        // check if p2p is initialized
-        let _p2p_module = self.p2p();
-        // start the connection
-        // p2p_module.start_connection()?;
+        self.network_events = Some(self.p2p.start_p2p()?);
 
         Ok(())
     }
+    /// ### Stop the p2p connection - see `P2P::stop_p2p()`.
+    pub async fn stop_p2p(&mut self) -> Result<()> {
+        self.p2p.stop_p2p().await?;
+        self.network_events = None;
+
+        Ok(())
+    }
+    /// ### Wait for the next event from the p2p network.
+    /// Txns gossiped by peers are added to the txn pool (see `submit_txn()`), and blocks propagated by peers
+    /// to the chain (see `receive_block()`), before the event is returned.
+    /// Sync requests from peers are answered, and sync responses applied (see `sync_blockchain()`).\
+    /// Returns `None` if the p2p connection isn't running.
+    pub async fn next_network_event(&mut self) -> Option<NetworkEvent> {
+        let event = self.network_events.as_mut()?.recv().await?;
+        match &event {
+            NetworkEvent::Message { source, message } => match message {
+                NetworkMessage::NewTxn(txn) => self.receive_txn(*source, *txn.clone()),
+                NetworkMessage::NewBlock(block) => self.receive_block(*source, *block.clone()),
+                _ => {}
+            },
+            event => self.handle_sync_event(event),
+        }
+
+        Some(event)
+    }
+    /// ### Publish a message to the node's peers.
+    pub fn broadcast(&self, message: &NetworkMessage) -> Result<()> {
+        Ok(self.p2p.publish(message)?)
+    }
     /// ### Set the wallet for the node with a filepath.
     /// Load a wallet from keypair file.\
     /// This is currently just a wrapper for Wallet::new_from_file().\
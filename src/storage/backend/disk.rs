@@ -0,0 +1,233 @@
+use crate::{
+    accounts::accounts::Accounts,
+    ledger::{
+        block::{block_id::BlockId, Block},
+        blockchain::pruning::PruningMode,
+        receipt::BlockReceipts,
+        txn::Txn,
+        txn_pool::TxnMapKey,
+    },
+    storage::{
+        backend::{ChainBackend, TxnPoolBackend},
+        block_store::{BlockStore, ChainMeta, StoredBlock},
+        config::{StorageConfig, BLOCKS_DIR, STATE_DIR},
+        error::StorageError,
+        segment_log::{RecordPos, SegmentLog},
+        state_db::{StateCheckpoint, StateDb},
+    },
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeMap, path::Path},
+};
+
+/// Minimum number of removed txns in a txn pool log before it is compacted.
+pub const TXN_POOL_COMPACT_MIN: usize = 1_000;
+
+/// ## `ChainBackend` on disk - blocks in a `BlockStore`, account state in a `StateDb`.
+///
+/// With `PruningMode::HeadersOnly`, the txns and receipts of the blocks up to the stored state
+/// are dropped from the block store as its segments fill up (see `BlockStore::prune_bodies()`).
+#[derive(Debug)]
+pub struct DiskChainBackend {
+    blocks: BlockStore,
+    state: StateDb,
+    /// How much history the chain keeps (see `StorageConfig::pruning()`)
+    pruning: PruningMode,
+}
+
+impl DiskChainBackend {
+    /// ### Open the backend in `dir`, creating it if needed.
+    /// Blocks are stored in `dir/BLOCKS_DIR`, and the account state in `dir/STATE_DIR`.
+    pub fn open(dir: impl AsRef<Path>, config: &StorageConfig) -> Result<Self, StorageError> {
+        let dir = dir.as_ref();
+
+        Ok(Self {
+            blocks: BlockStore::open_with_segment_bytes(
+                dir.join(BLOCKS_DIR),
+                config.segment_bytes(),
+            )?,
+            state: StateDb::open(
+                dir.join(STATE_DIR),
+                config.segment_bytes(),
+                config.state_snapshot_interval(),
+            )?,
+            pruning: config.pruning(),
+        })
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `DiskChainBackend.blocks`.
+    pub fn block_store(&self) -> &BlockStore {
+        &self.blocks
+    }
+    /// ### Get property `DiskChainBackend.state`.
+    pub fn state_db(&self) -> &StateDb {
+        &self.state
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+}
+
+impl ChainBackend for DiskChainBackend {
+    fn meta(&self) -> Option<&ChainMeta> {
+        self.blocks.meta()
+    }
+    fn put_meta(&mut self, meta: ChainMeta) -> Result<(), StorageError> {
+        self.blocks.put_meta(meta)
+    }
+
+    fn height(&self) -> u128 {
+        self.blocks.height()
+    }
+    fn block_id_at(&self, height: u128) -> Option<BlockId> {
+        self.blocks.block_id_at(height).copied()
+    }
+    fn block_at(&self, height: u128) -> Result<StoredBlock, StorageError> {
+        self.blocks.block_at(height)
+    }
+    fn append_block(
+        &mut self,
+        block: &Block,
+        receipts: &BlockReceipts,
+    ) -> Result<(), StorageError> {
+        // blocks above the stored state keep their bodies - they are replayed if the chain is reopened
+        if !self.pruning.keeps_bodies() {
+            self.blocks.prune_bodies(self.state.height())?;
+        }
+        self.blocks.append_block(block, receipts)
+    }
+    fn rollback_to(&mut self, height: u128) -> Result<(), StorageError> {
+        self.blocks.rollback_to(height)
+    }
+
+    fn state_height(&self) -> u128 {
+        self.state.height()
+    }
+    fn load_state(&self) -> Result<Option<StateCheckpoint>, StorageError> {
+        self.state.load()
+    }
+    fn commit_state(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        prev: &Accounts,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError> {
+        self.state.commit(height, block_id, prev, accounts)
+    }
+    fn reset_state(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError> {
+        self.state.reset(height, block_id, accounts)
+    }
+}
+
+/// ## A record in the txn pool's log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum PoolRecord {
+    Put(Box<Txn>),
+    Remove(TxnMapKey),
+}
+
+/// ## `TxnPoolBackend` on disk, backed by a `SegmentLog` of added and removed txns.
+///
+/// Once the log holds more removed txns than pending ones (and at least `TXN_POOL_COMPACT_MIN`),
+/// the pending txns are rewritten to a new segment and the old segments are deleted.
+/// A crash mid-compaction leaves the old segments, which replay to the same txns.
+#[derive(Debug)]
+pub struct DiskTxnPoolBackend {
+    log: SegmentLog,
+    /// Record of each pending txn
+    txns: BTreeMap<TxnMapKey, RecordPos>,
+    /// Records of removed txns still in the log
+    removed_ct: usize,
+}
+
+impl DiskTxnPoolBackend {
+    /// ### Open the backend in `dir`, creating it if needed.
+    pub fn open(dir: impl AsRef<Path>, config: &StorageConfig) -> Result<Self, StorageError> {
+        let log = SegmentLog::open(dir, config.segment_bytes())?;
+        let mut txns = BTreeMap::new();
+        let mut removed_ct = 0;
+        for pos in log.records() {
+            match serde_json::from_slice(&log.read(pos)?)? {
+                PoolRecord::Put(txn) => {
+                    txns.insert(txn.id_key(), *pos);
+                }
+                PoolRecord::Remove(key) => {
+                    txns.remove(&key);
+                    removed_ct += 1;
+                }
+            }
+        }
+
+        Ok(Self {
+            log,
+            txns,
+            removed_ct,
+        })
+    }
+    /// ### Get property `DiskTxnPoolBackend.log`.
+    pub fn log(&self) -> &SegmentLog {
+        &self.log
+    }
+    /// ### Rewrite the pending txns to a new segment, deleting the old ones.
+    pub fn compact(&mut self) -> Result<(), StorageError> {
+        let txns = self.txns()?;
+        let segment = self.log.start_segment()?;
+        self.txns.clear();
+        for txn in txns.iter() {
+            self.put_txn(txn)?;
+        }
+        self.log.remove_segments_before(segment)?;
+        self.removed_ct = 0;
+
+        Ok(())
+    }
+    /// ### Read a txn record from the log.
+    fn read_txn(&self, pos: &RecordPos) -> Result<Txn, StorageError> {
+        match serde_json::from_slice(&self.log.read(pos)?)? {
+            PoolRecord::Put(txn) => Ok(*txn),
+            PoolRecord::Remove(_) => Err(StorageError::Corrupt {
+                path: self.log.dir().to_path_buf(),
+                offset: pos.offset,
+            }),
+        }
+    }
+}
+
+impl TxnPoolBackend for DiskTxnPoolBackend {
+    fn put_txn(&mut self, txn: &Txn) -> Result<(), StorageError> {
+        let pos = self
+            .log
+            .append(&serde_json::to_vec(&PoolRecord::Put(Box::new(
+                txn.clone(),
+            )))?)?;
+        self.txns.insert(txn.id_key(), pos);
+
+        Ok(())
+    }
+    fn remove_txn(&mut self, key: &TxnMapKey) -> Result<(), StorageError> {
+        if !self.txns.contains_key(key) {
+            return Ok(());
+        }
+        self.log
+            .append(&serde_json::to_vec(&PoolRecord::Remove(key.clone()))?)?;
+        self.txns.remove(key);
+        self.removed_ct += 1;
+
+        if self.removed_ct >= TXN_POOL_COMPACT_MIN && self.removed_ct > self.txns.len() {
+            self.compact()?;
+        }
+
+        Ok(())
+    }
+    fn txns(&self) -> Result<Vec<Txn>, StorageError> {
+        self.txns.values().map(|pos| self.read_txn(pos)).collect()
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{
+    accounts::accounts::Accounts,
+    ledger::{
+        block::{block_id::BlockId, Block},
+        receipt::BlockReceipts,
+        txn::Txn,
+        txn_pool::{TxnMap, TxnMapKey},
+    },
+    storage::{
+        backend::{ChainBackend, TxnPoolBackend},
+        block_store::{ChainMeta, StoredBlock},
+        error::StorageError,
+        state_db::StateCheckpoint,
+    },
+};
+use std::collections::BTreeMap;
+
+/// ## `ChainBackend` held in memory - nothing is written to disk.
+/// A chain can still be "restarted" on it, by reopening it with the backend taken from the old chain
+/// (see `Blockchain::into_backend()`).
+#[derive(Debug, Default)]
+pub struct MemoryChainBackend {
+    meta: Option<ChainMeta>,
+    blocks: BTreeMap<u128, StoredBlock>,
+    state: Option<StateCheckpoint>,
+}
+
+impl MemoryChainBackend {
+    /// ### Initialize an empty `MemoryChainBackend`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ChainBackend for MemoryChainBackend {
+    fn meta(&self) -> Option<&ChainMeta> {
+        self.meta.as_ref()
+    }
+    fn put_meta(&mut self, meta: ChainMeta) -> Result<(), StorageError> {
+        self.meta = Some(meta);
+
+        Ok(())
+    }
+
+    fn height(&self) -> u128 {
+        self.blocks.keys().next_back().copied().unwrap_or(0)
+    }
+    fn block_id_at(&self, height: u128) -> Option<BlockId> {
+        self.blocks.get(&height).map(|stored| stored.block.id())
+    }
+    fn block_at(&self, height: u128) -> Result<StoredBlock, StorageError> {
+        self.blocks
+            .get(&height)
+            .cloned()
+            .ok_or(StorageError::UnknownHeight(height))
+    }
+    fn append_block(
+        &mut self,
+        block: &Block,
+        receipts: &BlockReceipts,
+    ) -> Result<(), StorageError> {
+        let expected = self.height() + 1;
+        let found = *block.blockheight();
+        if found != expected {
+            return Err(StorageError::NonSequentialBlock { expected, found });
+        }
+        self.blocks.insert(
+            found,
+            StoredBlock {
+                block: block.clone(),
+                receipts: receipts.clone(),
+            },
+        );
+
+        Ok(())
+    }
+    fn rollback_to(&mut self, height: u128) -> Result<(), StorageError> {
+        if !self.blocks.contains_key(&height) {
+            return Err(StorageError::UnknownHeight(height));
+        }
+        self.blocks.split_off(&(height + 1));
+
+        Ok(())
+    }
+
+    fn state_height(&self) -> u128 {
+        self.state.as_ref().map_or(0, |state| state.height)
+    }
+    fn load_state(&self) -> Result<Option<StateCheckpoint>, StorageError> {
+        Ok(self.state.clone())
+    }
+    fn commit_state(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        _prev: &Accounts,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError> {
+        let expected = self.state_height() + 1;
+        if height != expected {
+            return Err(StorageError::NonSequentialBlock {
+                expected,
+                found: height,
+            });
+        }
+
+        self.reset_state(height, block_id, accounts)
+    }
+    fn reset_state(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError> {
+        self.state = Some(StateCheckpoint {
+            height,
+            block_id,
+            accounts: accounts.clone(),
+        });
+
+        Ok(())
+    }
+}
+
+/// ## `TxnPoolBackend` held in memory - nothing is written to disk.
+#[derive(Debug, Default)]
+pub struct MemoryTxnPoolBackend {
+    txns: TxnMap,
+}
+
+impl MemoryTxnPoolBackend {
+    /// ### Initialize an empty `MemoryTxnPoolBackend`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TxnPoolBackend for MemoryTxnPoolBackend {
+    fn put_txn(&mut self, txn: &Txn) -> Result<(), StorageError> {
+        self.txns.insert(txn.id_key(), txn.clone());
+
+        Ok(())
+    }
+    fn remove_txn(&mut self, key: &TxnMapKey) -> Result<(), StorageError> {
+        self.txns.remove(key);
+
+        Ok(())
+    }
+    fn txns(&self) -> Result<Vec<Txn>, StorageError> {
+        Ok(self.txns.values().cloned().collect())
+    }
+}
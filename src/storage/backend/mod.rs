@@ -0,0 +1,75 @@
+pub mod disk;
+pub mod memory;
+
+use crate::{
+    accounts::accounts::Accounts,
+    ledger::{
+        block::{block_id::BlockId, Block},
+        receipt::BlockReceipts,
+        txn::Txn,
+        txn_pool::TxnMapKey,
+    },
+    storage::{
+        block_store::{ChainMeta, StoredBlock},
+        error::StorageError,
+        state_db::StateCheckpoint,
+    },
+};
+use std::fmt::Debug;
+
+/// ## Storage a `Blockchain` is written against - its blocks, chain metadata and account state.
+///
+/// Implemented by `MemoryChainBackend` (i.e. for tests) and `DiskChainBackend` (for nodes),
+/// so the same chain logic (and the same tests) run against both - see `Blockchain::open_with_backend()`.\
+/// Writes must be durable once they return: a chain reopened on the same backend restarts where it stopped.
+pub trait ChainBackend: Debug + Send {
+    /// ### Get the chain metadata - `None` for a new backend.
+    fn meta(&self) -> Option<&ChainMeta>;
+    /// ### Record the chain metadata.
+    fn put_meta(&mut self, meta: ChainMeta) -> Result<(), StorageError>;
+
+    /// ### Get the height of the highest stored block - 0 if there are none.
+    fn height(&self) -> u128;
+    /// ### Get the id of the stored block at `height`.
+    fn block_id_at(&self, height: u128) -> Option<BlockId>;
+    /// ### Get the stored block at `height`, with its receipts.
+    fn block_at(&self, height: u128) -> Result<StoredBlock, StorageError>;
+    /// ### Store the block above the highest stored block - `StorageError::NonSequentialBlock` otherwise.
+    fn append_block(&mut self, block: &Block, receipts: &BlockReceipts)
+        -> Result<(), StorageError>;
+    /// ### Remove the stored blocks above `height`.
+    fn rollback_to(&mut self, height: u128) -> Result<(), StorageError>;
+
+    /// ### Get the height of the stored account state - 0 if none.
+    fn state_height(&self) -> u128;
+    /// ### Get the stored account state - `None` if none.
+    fn load_state(&self) -> Result<Option<StateCheckpoint>, StorageError>;
+    /// ### Store the account state after the block at `height` - the block after the stored state.
+    /// `prev` is the stored state, so only the changes need to be written.
+    fn commit_state(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        prev: &Accounts,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError>;
+    /// ### Replace the stored account state (i.e. after a rollback).
+    fn reset_state(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError>;
+}
+
+/// ## Storage a `TxnPool` is written against - its pending txns.
+///
+/// Implemented by `MemoryTxnPoolBackend` and `DiskTxnPoolBackend` - see `TxnPool::open()`.
+pub trait TxnPoolBackend: Debug + Send {
+    /// ### Store a pending txn.
+    fn put_txn(&mut self, txn: &Txn) -> Result<(), StorageError>;
+    /// ### Remove a pending txn - no-op if it isn't stored.
+    fn remove_txn(&mut self, key: &TxnMapKey) -> Result<(), StorageError>;
+    /// ### Get every stored txn.
+    fn txns(&self) -> Result<Vec<Txn>, StorageError>;
+}
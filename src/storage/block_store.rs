@@ -0,0 +1,292 @@
+use crate::{
+    ledger::{
+        block::{block_id::BlockId, Block},
+        genesis::GenesisSpec,
+        receipt::BlockReceipts,
+    },
+    storage::{
+        error::StorageError,
+        segment_log::{RecordPos, SegmentLog, DEFAULT_SEGMENT_BYTES},
+    },
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::{BTreeMap, BTreeSet},
+        path::Path,
+    },
+};
+
+/// ## Chain metadata - the first record in a `BlockStore`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainMeta {
+    /// Id of the genesis block built from `spec`
+    pub genesis_id: BlockId,
+    /// Genesis spec the chain was started from
+    pub spec: GenesisSpec,
+}
+
+/// ## A stored block, with the receipts of its txns - so the chain can be restored without executing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredBlock {
+    pub block: Block,
+    pub receipts: BlockReceipts,
+}
+impl StoredBlock {
+    /// ### Whether the block's txns or receipts are stored - not if they were pruned (see `BlockStore::prune_bodies()`).
+    pub fn has_body(&self) -> bool {
+        !self.block.txns().is_empty() || !self.receipts.is_empty()
+    }
+}
+
+/// ## A record in the block store's log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum StoreRecord {
+    Meta(ChainMeta),
+    Block(Box<StoredBlock>),
+    /// Blocks above `height` were removed (see `Blockchain::rollback_to()`)
+    Rollback {
+        height: u128,
+    },
+}
+
+/// ## On-disk store of a chain's blocks, backed by a `SegmentLog`.
+///
+/// Blocks are appended in height order, so the log can be replayed to rebuild the chain on startup.
+/// A rollback is appended as its own record rather than rewriting the log -
+/// on `open()` the height index is rebuilt by replaying every record.
+///
+/// The only rewrite is `prune_bodies()`: full segments whose blocks' txns and receipts are no longer kept
+/// are rewritten without them to a new segment, and deleted.
+/// A crash mid-rewrite leaves the old segments, which hold no records still in use once reopened -
+/// they are deleted by the next `prune_bodies()`.
+#[derive(Debug)]
+pub struct BlockStore {
+    log: SegmentLog,
+    meta: Option<ChainMeta>,
+    /// Segment holding the `meta` record
+    meta_segment: Option<u64>,
+    /// Location of each stored block's record, by height
+    heights: BTreeMap<u128, (BlockId, RecordPos)>,
+    /// Heights of the stored blocks whose record holds txns or receipts
+    bodies: BTreeSet<u128>,
+    /// Segments holding a rollback record
+    rollbacks: BTreeSet<u64>,
+    /// Segments found holding no record in use on `open()` - i.e. left by an interrupted `prune_bodies()`
+    dead: BTreeSet<u64>,
+}
+
+impl BlockStore {
+    /// ### Open the block store in `dir`, creating it if needed.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, StorageError> {
+        Self::open_with_segment_bytes(dir, DEFAULT_SEGMENT_BYTES)
+    }
+    /// ### Open the block store in `dir`, starting new segments once one reaches `max_segment_bytes`.
+    pub fn open_with_segment_bytes(
+        dir: impl AsRef<Path>,
+        max_segment_bytes: u64,
+    ) -> Result<Self, StorageError> {
+        let log = SegmentLog::open(dir, max_segment_bytes)?;
+        let mut meta = None;
+        let mut meta_segment = None;
+        let mut heights = BTreeMap::new();
+        let mut bodies = BTreeSet::new();
+        let mut rollbacks = BTreeSet::new();
+        for pos in log.records() {
+            match serde_json::from_slice(&log.read(pos)?)? {
+                StoreRecord::Meta(chain_meta) => {
+                    meta = Some(chain_meta);
+                    meta_segment = Some(pos.segment);
+                }
+                StoreRecord::Block(stored) => {
+                    let height = *stored.block.blockheight();
+                    heights.insert(height, (stored.block.id(), *pos));
+                    if stored.has_body() {
+                        bodies.insert(height);
+                    } else {
+                        bodies.remove(&height);
+                    }
+                }
+                StoreRecord::Rollback { height } => {
+                    heights.split_off(&(height + 1));
+                    bodies.split_off(&(height + 1));
+                    rollbacks.insert(pos.segment);
+                }
+            }
+        }
+
+        let active = *log.segments().last().unwrap();
+        let live: BTreeSet<u64> = heights
+            .values()
+            .map(|(_, pos)| pos.segment)
+            .chain(meta_segment)
+            .chain([active])
+            .collect();
+        let dead = log
+            .segments()
+            .iter()
+            .filter(|segment| !live.contains(segment))
+            .copied()
+            .collect();
+
+        Ok(Self {
+            log,
+            meta,
+            meta_segment,
+            heights,
+            bodies,
+            rollbacks,
+            dead,
+        })
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `BlockStore.log`.
+    pub fn log(&self) -> &SegmentLog {
+        &self.log
+    }
+    /// ### Get property `BlockStore.meta` - `None` for a new store.
+    pub fn meta(&self) -> Option<&ChainMeta> {
+        self.meta.as_ref()
+    }
+    /// ### Get the height of the highest stored block - 0 if there are none.
+    pub fn height(&self) -> u128 {
+        self.heights.keys().next_back().copied().unwrap_or(0)
+    }
+    /// ### Get the id of the stored block at `height`.
+    pub fn block_id_at(&self, height: u128) -> Option<&BlockId> {
+        self.heights.get(&height).map(|(block_id, _)| block_id)
+    }
+    /// ### Read the stored block at `height` (and its receipts) from disk.
+    pub fn block_at(&self, height: u128) -> Result<StoredBlock, StorageError> {
+        let (_, pos) = self
+            .heights
+            .get(&height)
+            .ok_or(StorageError::UnknownHeight(height))?;
+
+        match serde_json::from_slice(&self.log.read(pos)?)? {
+            StoreRecord::Block(stored) => Ok(*stored),
+            _ => Err(StorageError::Corrupt {
+                path: self.log.dir().to_path_buf(),
+                offset: pos.offset,
+            }),
+        }
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Record the chain's metadata.
+    pub fn put_meta(&mut self, meta: ChainMeta) -> Result<(), StorageError> {
+        let pos = self.append(&StoreRecord::Meta(meta.clone()))?;
+        self.meta = Some(meta);
+        self.meta_segment = Some(pos.segment);
+
+        Ok(())
+    }
+    /// ### Append the block above the highest stored block, with its receipts.
+    pub fn append_block(
+        &mut self,
+        block: &Block,
+        receipts: &BlockReceipts,
+    ) -> Result<(), StorageError> {
+        let expected = self.height() + 1;
+        let found = *block.blockheight();
+        if found != expected {
+            return Err(StorageError::NonSequentialBlock { expected, found });
+        }
+        let stored = StoredBlock {
+            block: block.clone(),
+            receipts: receipts.clone(),
+        };
+        let pos = self.append(&StoreRecord::Block(Box::new(stored.clone())))?;
+        self.heights.insert(found, (block.id(), pos));
+        if stored.has_body() {
+            self.bodies.insert(found);
+        }
+
+        Ok(())
+    }
+    /// ### Remove the stored blocks above `height`.
+    pub fn rollback_to(&mut self, height: u128) -> Result<(), StorageError> {
+        if !self.heights.contains_key(&height) {
+            return Err(StorageError::UnknownHeight(height));
+        }
+        let pos = self.append(&StoreRecord::Rollback { height })?;
+        self.heights.split_off(&(height + 1));
+        self.bodies.split_off(&(height + 1));
+        self.rollbacks.insert(pos.segment);
+
+        Ok(())
+    }
+    /// ### Drop the txns and receipts of the stored blocks up to `height` from disk, keeping their headers.
+    ///
+    /// Only full segments are rewritten: those holding a body up to `height` and none above it.
+    /// Their records still in use are appended without bodies to a new segment, then the old segments are deleted -
+    /// so bodies in the active segment are dropped once it fills up (see `StorageConfig::segment_bytes()`).
+    /// Segments holding only headers are left as they are.
+    pub fn prune_bodies(&mut self, height: u128) -> Result<(), StorageError> {
+        // blocks are stored in height order, so the bodies to keep are all in the segments from `keep_from` on
+        let active = *self.log.segments().last().unwrap();
+        let keep_from = self
+            .bodies
+            .range(height.saturating_add(1)..)
+            .next()
+            .map_or(active, |height| self.heights[height].1.segment)
+            .min(active);
+        let mut removed: BTreeSet<u64> = self
+            .bodies
+            .range(..=height)
+            .map(|height| self.heights[height].1.segment)
+            .filter(|segment| *segment < keep_from)
+            .collect();
+        if removed.is_empty() {
+            return Ok(());
+        }
+        removed.extend(self.dead.iter().filter(|segment| **segment < keep_from));
+
+        self.log.start_segment()?;
+        if self
+            .meta_segment
+            .is_some_and(|segment| removed.contains(&segment))
+        {
+            let meta = self.meta.clone().unwrap();
+            self.put_meta(meta)?;
+        }
+        let moved: Vec<u128> = self
+            .heights
+            .iter()
+            .filter(|(_, (_, pos))| removed.contains(&pos.segment))
+            .map(|(height, _)| *height)
+            .collect();
+        for height in moved {
+            let mut stored = self.block_at(height)?;
+            stored.block.prune_txns();
+            stored.receipts.clear();
+            let pos = self.append(&StoreRecord::Block(Box::new(stored)))?;
+            self.heights.get_mut(&height).unwrap().1 = pos;
+            self.bodies.remove(&height);
+        }
+        // blocks rolled back in the kept segments must stay removed
+        if removed
+            .iter()
+            .any(|segment| self.rollbacks.contains(segment))
+        {
+            let pos = self.append(&StoreRecord::Rollback {
+                height: self.height(),
+            })?;
+            self.rollbacks.insert(pos.segment);
+        }
+        // new blocks go to their own segment, so the headers aren't rewritten again
+        self.log.start_segment()?;
+        self.log.remove_segments(&removed)?;
+        self.rollbacks.retain(|segment| !removed.contains(segment));
+        self.dead.retain(|segment| !removed.contains(segment));
+
+        Ok(())
+    }
+    /// ### Encode and append a record to the log.
+    fn append(&mut self, record: &StoreRecord) -> Result<RecordPos, StorageError> {
+        self.log.append(&serde_json::to_vec(record)?)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    ledger::blockchain::pruning::PruningMode,
+    storage::{segment_log::DEFAULT_SEGMENT_BYTES, state_db::DEFAULT_SNAPSHOT_INTERVAL},
+};
+
+/// Directory of the block store, within a chain's storage directory.
+pub const BLOCKS_DIR: &str = "blocks";
+/// Directory of the state db, within a chain's storage directory.
+pub const STATE_DIR: &str = "state";
+/// Directory of the txn pool, within a node's storage directory.
+pub const TXN_POOL_DIR: &str = "txn_pool";
+
+/// ## Settings for a chain (or txn pool) stored on disk (see `Blockchain::open_with_config()`).
+/// Local to the node - unlike `ChainParams`, nodes don't need to agree on them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageConfig {
+    /// Size at which the block store, state log and txn pool log start a new segment file
+    segment_bytes: u64,
+    /// Blocks between snapshots of the account state
+    snapshot_interval: u128,
+    /// How much history the chain keeps
+    pruning: PruningMode,
+}
+
+impl StorageConfig {
+    pub fn new(segment_bytes: u64, snapshot_interval: u128, pruning: PruningMode) -> Self {
+        Self {
+            segment_bytes,
+            snapshot_interval,
+            pruning,
+        }
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `StorageConfig.segment_bytes`.
+    pub fn segment_bytes(&self) -> u64 {
+        self.segment_bytes
+    }
+    /// ### Get property `StorageConfig.snapshot_interval`.
+    pub fn snapshot_interval(&self) -> u128 {
+        self.snapshot_interval
+    }
+    /// ### Get the blocks between snapshots of the account state, for the state db.
+    ///
+    /// A `PruningMode::Pruned` node snapshots at least once per kept history, so state changes older than it
+    /// are deleted from disk along with the log before the snapshot.
+    /// (`HeadersOnly` nodes keep the configured interval - a snapshot per block would write the full state each time.)
+    pub fn state_snapshot_interval(&self) -> u128 {
+        match self.pruning {
+            PruningMode::Pruned(blocks) => self.snapshot_interval.min(blocks.max(1)),
+            _ => self.snapshot_interval,
+        }
+    }
+    /// ### Get property `StorageConfig.pruning`.
+    pub fn pruning(&self) -> PruningMode {
+        self.pruning
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+}
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_SEGMENT_BYTES,
+            DEFAULT_SNAPSHOT_INTERVAL,
+            PruningMode::default(),
+        )
+    }
+}
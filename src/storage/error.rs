@@ -0,0 +1,26 @@
+use crate::ledger::block::block_id::BlockId;
+use std::path::PathBuf;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("StorageError::Io- {0}")]
+    Io(#[from] std::io::Error),
+    #[error("StorageError::Corrupt- Invalid record in {path:?} at offset {offset}")]
+    Corrupt { path: PathBuf, offset: u64 },
+    #[error("StorageError::RecordTooLarge- Record of {0} bytes is over the limit")]
+    RecordTooLarge(usize),
+    #[error("StorageError::InvalidRecord- {0}")]
+    InvalidRecord(#[from] serde_json::Error),
+    #[error(
+        "StorageError::NonSequentialBlock- Expected block at height {expected}, found {found}"
+    )]
+    NonSequentialBlock { expected: u128, found: u128 },
+    #[error("StorageError::UnknownHeight- No stored block at height {0}")]
+    UnknownHeight(u128),
+    #[error(
+        "StorageError::StateMismatch- Stored state at height {0} does not match its state root"
+    )]
+    StateMismatch(u128),
+    #[error("StorageError::GenesisMismatch- Store holds the chain with genesis {0}, which does not match the genesis spec")]
+    GenesisMismatch(BlockId),
+}
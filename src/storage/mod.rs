@@ -0,0 +1,6 @@
+pub mod backend;
+pub mod block_store;
+pub mod config;
+pub mod error;
+pub mod segment_log;
+pub mod state_db;
@@ -0,0 +1,289 @@
+use crate::storage::error::StorageError;
+use {
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::BTreeSet,
+        fs::{self, File, OpenOptions},
+        io::{Read, Seek, SeekFrom, Write},
+        path::{Path, PathBuf},
+    },
+};
+
+/// Bytes before each record's payload - payload length (u32 BE) followed by its checksum (u64 BE).
+pub const RECORD_HEADER_LEN: usize = 12;
+/// Largest payload a single record can hold - 256 MiB.
+pub const MAX_RECORD_LEN: usize = 256 << 20;
+/// Default size at which a new segment is started - 64 MiB.
+pub const DEFAULT_SEGMENT_BYTES: u64 = 64 << 20;
+/// File extension of segment files.
+pub const SEGMENT_EXT: &str = "seg";
+
+/// ### Checksum of a record's payload - the first 8 bytes of its blake3 hash.
+pub fn checksum(payload: &[u8]) -> u64 {
+    let hash = blake3::hash(payload);
+
+    u64::from_be_bytes(hash.as_bytes()[..8].try_into().unwrap())
+}
+
+/// ### Frame a record - `len (u32 BE) || checksum (u64 BE) || payload`.
+pub fn encode_record(payload: &[u8]) -> Vec<u8> {
+    let mut record = Vec::with_capacity(RECORD_HEADER_LEN + payload.len());
+    record.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    record.extend_from_slice(&checksum(payload).to_be_bytes());
+    record.extend_from_slice(payload);
+
+    record
+}
+/// ### Split a record header into the payload length and checksum.
+pub fn parse_record_header(header: &[u8; RECORD_HEADER_LEN]) -> (usize, u64) {
+    let len = u32::from_be_bytes(header[..4].try_into().unwrap()) as usize;
+    let sum = u64::from_be_bytes(header[4..].try_into().unwrap());
+
+    (len, sum)
+}
+
+/// ## Location of a record in a `SegmentLog`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RecordPos {
+    /// Segment number
+    pub segment: u64,
+    /// Byte offset of the record's header within the segment
+    pub offset: u64,
+}
+
+/// ## Append-only log of byte records, split over numbered segment files in one directory.
+///
+/// Each record is written as `len (u32 BE) || checksum (u64 BE) || payload` and synced to disk
+/// before `append()` returns. Once a segment reaches its size limit, the next record starts a new one.
+///
+/// On `open()`, every record is checked. A crash mid-write can only damage the end of the last segment,
+/// so an incomplete or mismatched record there is cut off (see `truncated_bytes()`) -
+/// anywhere else it is reported as `StorageError::Corrupt`.
+#[derive(Debug)]
+pub struct SegmentLog {
+    dir: PathBuf,
+    max_segment_bytes: u64,
+    /// Segment numbers, ascending - never empty
+    segments: Vec<u64>,
+    /// Last segment, opened for appending
+    active: File,
+    /// Size of the last segment
+    active_len: u64,
+    /// Every record in the log, in order
+    records: Vec<RecordPos>,
+    /// Bytes cut off the end of the log when it was opened
+    truncated_bytes: u64,
+}
+
+impl SegmentLog {
+    /// ### Open the log in `dir`, creating it if needed.
+    /// New segments are started once a segment reaches `max_segment_bytes`.
+    pub fn open(dir: impl AsRef<Path>, max_segment_bytes: u64) -> Result<Self, StorageError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let mut segments = vec![];
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXT) {
+                continue;
+            }
+            if let Some(segment) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            {
+                segments.push(segment);
+            }
+        }
+        segments.sort_unstable();
+        if segments.is_empty() {
+            segments.push(0);
+        }
+
+        let mut records = vec![];
+        let mut truncated_bytes = 0;
+        let last_segment = *segments.last().unwrap();
+        for &segment in segments.iter() {
+            let path = Self::segment_path(&dir, segment);
+            let bytes = match fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+                Err(e) => return Err(e.into()),
+            };
+            let valid_len = Self::scan(&bytes, segment, &mut records);
+            if valid_len < bytes.len() {
+                if segment != last_segment {
+                    return Err(StorageError::Corrupt {
+                        path,
+                        offset: valid_len as u64,
+                    });
+                }
+                // torn write at the end of the log - drop it
+                let file = OpenOptions::new().write(true).open(&path)?;
+                file.set_len(valid_len as u64)?;
+                file.sync_all()?;
+                truncated_bytes = (bytes.len() - valid_len) as u64;
+            }
+        }
+
+        let active_path = Self::segment_path(&dir, last_segment);
+        let active = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&active_path)?;
+        let active_len = active.metadata()?.len();
+
+        Ok(Self {
+            dir,
+            max_segment_bytes,
+            segments,
+            active,
+            active_len,
+            records,
+            truncated_bytes,
+        })
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `SegmentLog.dir`.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+    /// ### Get property `SegmentLog.records` - every record in the log, in order.
+    pub fn records(&self) -> &[RecordPos] {
+        &self.records
+    }
+    /// ### Get property `SegmentLog.segments` - segment numbers, ascending.
+    pub fn segments(&self) -> &[u64] {
+        &self.segments
+    }
+    /// ### Get property `SegmentLog.truncated_bytes` - bytes of a torn record dropped on `open()`.
+    pub fn truncated_bytes(&self) -> u64 {
+        self.truncated_bytes
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Append a record and sync it to disk.
+    pub fn append(&mut self, payload: &[u8]) -> Result<RecordPos, StorageError> {
+        if payload.len() > MAX_RECORD_LEN {
+            return Err(StorageError::RecordTooLarge(payload.len()));
+        }
+        let record_len = (RECORD_HEADER_LEN + payload.len()) as u64;
+        if self.active_len > 0 && self.active_len + record_len > self.max_segment_bytes {
+            self.start_segment()?;
+        }
+
+        self.active.write_all(&encode_record(payload))?;
+        self.active.sync_data()?;
+
+        let pos = RecordPos {
+            segment: *self.segments.last().unwrap(),
+            offset: self.active_len,
+        };
+        self.active_len += record_len;
+        self.records.push(pos);
+
+        Ok(pos)
+    }
+    /// ### Read the record at `pos`, checking its checksum.
+    pub fn read(&self, pos: &RecordPos) -> Result<Vec<u8>, StorageError> {
+        let path = Self::segment_path(&self.dir, pos.segment);
+        let corrupt = || StorageError::Corrupt {
+            path: path.clone(),
+            offset: pos.offset,
+        };
+        let mut file = File::open(&path)?;
+        file.seek(SeekFrom::Start(pos.offset))?;
+
+        let mut header = [0u8; RECORD_HEADER_LEN];
+        file.read_exact(&mut header).map_err(|_| corrupt())?;
+        let (len, sum) = parse_record_header(&header);
+        if len > MAX_RECORD_LEN {
+            return Err(corrupt());
+        }
+        let mut payload = vec![0u8; len];
+        file.read_exact(&mut payload).map_err(|_| corrupt())?;
+        if checksum(&payload) != sum {
+            return Err(corrupt());
+        }
+
+        Ok(payload)
+    }
+    /// ### Close the active segment and start the next one - returns the new segment's number.
+    pub fn start_segment(&mut self) -> Result<u64, StorageError> {
+        let segment = self.segments.last().unwrap() + 1;
+        self.active = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::segment_path(&self.dir, segment))?;
+        self.active_len = 0;
+        self.segments.push(segment);
+
+        Ok(segment)
+    }
+    /// ### Delete the segments before `segment`, along with their records.
+    /// The active segment is never deleted.
+    pub fn remove_segments_before(&mut self, segment: u64) -> Result<(), StorageError> {
+        let segment = segment.min(*self.segments.last().unwrap());
+        for &old in self.segments.iter().filter(|&&old| old < segment) {
+            match fs::remove_file(Self::segment_path(&self.dir, old)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        self.segments.retain(|&old| old >= segment);
+        self.records.retain(|pos| pos.segment >= segment);
+
+        Ok(())
+    }
+    /// ### Delete the given segments, along with their records.
+    /// The active segment is never deleted.
+    pub fn remove_segments(&mut self, segments: &BTreeSet<u64>) -> Result<(), StorageError> {
+        let active = *self.segments.last().unwrap();
+        for &old in segments.iter().filter(|&&old| old != active) {
+            match fs::remove_file(Self::segment_path(&self.dir, old)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        self.segments
+            .retain(|old| *old == active || !segments.contains(old));
+        self.records
+            .retain(|pos| pos.segment == active || !segments.contains(&pos.segment));
+
+        Ok(())
+    }
+
+    /// ### Collect the positions of the valid records at the start of a segment.
+    /// Returns the length of the valid prefix - stops at the first incomplete or mismatched record.
+    fn scan(bytes: &[u8], segment: u64, records: &mut Vec<RecordPos>) -> usize {
+        let mut offset = 0;
+        while bytes.len() - offset >= RECORD_HEADER_LEN {
+            let header: &[u8; RECORD_HEADER_LEN] = bytes[offset..offset + RECORD_HEADER_LEN]
+                .try_into()
+                .unwrap();
+            let (len, sum) = parse_record_header(header);
+            let start = offset + RECORD_HEADER_LEN;
+            if len > MAX_RECORD_LEN || bytes.len() - start < len {
+                break;
+            }
+            if checksum(&bytes[start..start + len]) != sum {
+                break;
+            }
+            records.push(RecordPos {
+                segment,
+                offset: offset as u64,
+            });
+            offset = start + len;
+        }
+
+        offset
+    }
+    /// ### Get the path of a segment file - numbered, zero-padded so they sort by name.
+    fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+        dir.join(format!("{segment:020}.{SEGMENT_EXT}"))
+    }
+}
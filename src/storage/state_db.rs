@@ -0,0 +1,329 @@
+use crate::{
+    accounts::accounts::{AccountChanges, Accounts},
+    ledger::block::block_id::BlockId,
+    storage::{
+        error::StorageError,
+        segment_log::{RecordPos, SegmentLog},
+    },
+    utils::hash::BlakeHash,
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::{
+        fs::{self, File},
+        io::Write,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Default number of blocks between snapshots of the account state.
+pub const DEFAULT_SNAPSHOT_INTERVAL: u128 = 1_000;
+/// File extension of snapshot files.
+pub const SNAPSHOT_EXT: &str = "snap";
+/// File extension of a snapshot being written - renamed once complete.
+const SNAPSHOT_TMP_EXT: &str = "tmp";
+/// Directory of the write-ahead log, within the state db's directory.
+const WAL_DIR: &str = "wal";
+/// Directory of the snapshots, within the state db's directory.
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// ## The account state after the block at `height`.
+#[derive(Debug, Clone)]
+pub struct StateCheckpoint {
+    pub height: u128,
+    pub block_id: BlockId,
+    pub accounts: Accounts,
+}
+
+/// ## A `StateCheckpoint` as written to disk - every account, as the changes since an empty state.
+/// `Accounts` maps aren't keyed by strings, so they can't be written as JSON objects.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredCheckpoint {
+    height: u128,
+    block_id: BlockId,
+    accounts: AccountChanges,
+}
+impl From<&StateCheckpoint> for StoredCheckpoint {
+    fn from(value: &StateCheckpoint) -> Self {
+        Self {
+            height: value.height,
+            block_id: value.block_id,
+            accounts: value.accounts.changes_since(&Accounts::new()),
+        }
+    }
+}
+impl From<StoredCheckpoint> for StateCheckpoint {
+    fn from(value: StoredCheckpoint) -> Self {
+        let mut accounts = Accounts::new();
+        accounts.apply_changes(value.accounts);
+
+        Self {
+            height: value.height,
+            block_id: value.block_id,
+            accounts,
+        }
+    }
+}
+
+/// ## A record in the state db's write-ahead log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum StateRecord {
+    /// Changes made by the block at `height` - `state_root` is the state after applying them
+    Commit {
+        height: u128,
+        block_id: BlockId,
+        state_root: BlakeHash,
+        changes: AccountChanges,
+    },
+    /// The full state, replacing whatever came before (i.e. after a rollback)
+    Reset(StoredCheckpoint),
+}
+
+/// ## Crash-safe store of the account state, so a restarted node doesn't re-execute every block.
+///
+/// Each block's account changes are appended to a write-ahead log (a `SegmentLog`) as one record, so a commit
+/// is either fully on disk or dropped on open - the state is always the state after some block.\
+/// Every `snapshot_interval` blocks, the full state is written to a snapshot and the log before it is deleted:
+/// 1. the log starts a new segment;
+/// 1. the snapshot is written to a temp file, synced, and renamed to the new segment's number;
+/// 1. older snapshots and log segments are deleted.
+///
+/// A crash at any step leaves either the previous snapshot and its log, or the new one.
+/// The state is recovered from the latest snapshot and the log records after it (see `load()`).
+#[derive(Debug)]
+pub struct StateDb {
+    dir: PathBuf,
+    wal: SegmentLog,
+    snapshot_interval: u128,
+    /// Height of the last committed state - 0 if none
+    height: u128,
+    /// Height of the latest snapshot - 0 if none
+    snapshot_height: u128,
+}
+
+impl StateDb {
+    /// ### Open the state db in `dir`, creating it if needed.
+    /// The account state is snapshotted every `snapshot_interval` blocks.
+    pub fn open(
+        dir: impl AsRef<Path>,
+        segment_bytes: u64,
+        snapshot_interval: u128,
+    ) -> Result<Self, StorageError> {
+        let dir = dir.as_ref().to_path_buf();
+        let wal = SegmentLog::open(dir.join(WAL_DIR), segment_bytes)?;
+        fs::create_dir_all(dir.join(SNAPSHOTS_DIR))?;
+
+        let mut state_db = Self {
+            dir,
+            wal,
+            snapshot_interval: snapshot_interval.max(1),
+            height: 0,
+            snapshot_height: 0,
+        };
+        // left behind by a crash mid-snapshot
+        state_db.remove_unfinished_snapshots()?;
+        if let Some(segment) = state_db.latest_snapshot()? {
+            state_db.snapshot_height = state_db.read_snapshot(segment)?.height;
+            // left behind by a crash after the snapshot was written
+            state_db.remove_before(segment)?;
+        }
+        state_db.height = state_db.load()?.map_or(0, |checkpoint| checkpoint.height);
+
+        Ok(state_db)
+    }
+
+    /////////////////////////////////////////////////////////////////////
+    ////////////////////////////// GETTERS //////////////////////////////
+    /// ### Get property `StateDb.wal` - the write-ahead log.
+    pub fn wal(&self) -> &SegmentLog {
+        &self.wal
+    }
+    /// ### Get property `StateDb.height` - height of the last committed state, 0 if none.
+    pub fn height(&self) -> u128 {
+        self.height
+    }
+    /// ### Get property `StateDb.snapshot_height` - height of the latest snapshot, 0 if none.
+    pub fn snapshot_height(&self) -> u128 {
+        self.snapshot_height
+    }
+    ////////////////////////////// GETTERS //////////////////////////////
+    /////////////////////////////////////////////////////////////////////
+
+    /// ### Recover the last committed state from disk - `None` if nothing was committed.
+    ///
+    /// Loads the latest snapshot, then applies the log records written after it, checking each commit
+    /// follows the one before and results in its recorded state root.
+    pub fn load(&self) -> Result<Option<StateCheckpoint>, StorageError> {
+        let snapshot_segment = self.latest_snapshot()?;
+        let mut state = match snapshot_segment {
+            Some(segment) => Some(self.read_snapshot(segment)?),
+            None => None,
+        };
+
+        for pos in self
+            .wal
+            .records()
+            .iter()
+            .filter(|pos| Some(pos.segment) >= snapshot_segment)
+        {
+            match serde_json::from_slice(&self.wal.read(pos)?)? {
+                StateRecord::Reset(checkpoint) => state = Some(checkpoint.into()),
+                StateRecord::Commit {
+                    height,
+                    block_id,
+                    state_root,
+                    changes,
+                } => {
+                    let checkpoint = state.as_mut().ok_or_else(|| self.corrupt(pos))?;
+                    if height != checkpoint.height + 1 {
+                        return Err(self.corrupt(pos));
+                    }
+                    checkpoint.accounts.apply_changes(changes);
+                    if checkpoint.accounts.state_root() != state_root {
+                        return Err(StorageError::StateMismatch(height));
+                    }
+                    checkpoint.height = height;
+                    checkpoint.block_id = block_id;
+                }
+            }
+        }
+
+        Ok(state)
+    }
+    /// ### Commit the state after the block at `height` - the block after the last committed state.
+    /// Only the changes since `prev` (the last committed state) are logged. Snapshots the state when due.
+    pub fn commit(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        prev: &Accounts,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError> {
+        let expected = self.height + 1;
+        if height != expected {
+            return Err(StorageError::NonSequentialBlock {
+                expected,
+                found: height,
+            });
+        }
+        self.append(&StateRecord::Commit {
+            height,
+            block_id,
+            state_root: accounts.state_root(),
+            changes: accounts.changes_since(prev),
+        })?;
+        self.height = height;
+
+        if height - self.snapshot_height >= self.snapshot_interval {
+            self.snapshot(&StateCheckpoint {
+                height,
+                block_id,
+                accounts: accounts.clone(),
+            })?;
+        }
+
+        Ok(())
+    }
+    /// ### Replace the state with the full state after the block at `height` (i.e. after a rollback).
+    pub fn reset(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError> {
+        self.append(&StateRecord::Reset(StoredCheckpoint {
+            height,
+            block_id,
+            accounts: accounts.changes_since(&Accounts::new()),
+        }))?;
+        self.height = height;
+        self.snapshot_height = self.snapshot_height.min(height);
+
+        Ok(())
+    }
+    /// ### Write a snapshot of the state, then delete the log and snapshots it replaces.
+    pub fn snapshot(&mut self, checkpoint: &StateCheckpoint) -> Result<(), StorageError> {
+        let segment = self.wal.start_segment()?;
+        let path = self.snapshot_path(segment);
+        let tmp_path = path.with_extension(SNAPSHOT_TMP_EXT);
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(&serde_json::to_vec(&StoredCheckpoint::from(checkpoint))?)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+        // make the rename durable
+        File::open(self.dir.join(SNAPSHOTS_DIR))?.sync_all()?;
+
+        self.snapshot_height = checkpoint.height;
+        self.remove_before(segment)
+    }
+
+    /// ### Encode and append a record to the log.
+    fn append(&mut self, record: &StateRecord) -> Result<RecordPos, StorageError> {
+        self.wal.append(&serde_json::to_vec(record)?)
+    }
+    /// ### Get the number of the latest snapshot - the log segment started when it was taken.
+    fn latest_snapshot(&self) -> Result<Option<u64>, StorageError> {
+        Ok(self.snapshots()?.into_iter().max())
+    }
+    /// ### Get the numbers of the snapshots on disk.
+    fn snapshots(&self) -> Result<Vec<u64>, StorageError> {
+        let mut snapshots = vec![];
+        for entry in fs::read_dir(self.dir.join(SNAPSHOTS_DIR))? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SNAPSHOT_EXT) {
+                continue;
+            }
+            if let Some(segment) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            {
+                snapshots.push(segment);
+            }
+        }
+
+        Ok(snapshots)
+    }
+    /// ### Read a snapshot from disk.
+    fn read_snapshot(&self, segment: u64) -> Result<StateCheckpoint, StorageError> {
+        let path = self.snapshot_path(segment);
+        let bytes = fs::read(&path)?;
+
+        serde_json::from_slice::<StoredCheckpoint>(&bytes)
+            .map(StateCheckpoint::from)
+            .map_err(|_| StorageError::Corrupt { path, offset: 0 })
+    }
+    /// ### Delete the snapshots and log segments older than snapshot `segment`.
+    fn remove_before(&mut self, segment: u64) -> Result<(), StorageError> {
+        for old in self.snapshots()?.into_iter().filter(|&old| old < segment) {
+            fs::remove_file(self.snapshot_path(old))?;
+        }
+
+        self.wal.remove_segments_before(segment)
+    }
+    /// ### Delete snapshot temp files - never renamed, so never loaded.
+    fn remove_unfinished_snapshots(&self) -> Result<(), StorageError> {
+        for entry in fs::read_dir(self.dir.join(SNAPSHOTS_DIR))? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some(SNAPSHOT_TMP_EXT) {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+    /// ### Get the path of a snapshot file.
+    fn snapshot_path(&self, segment: u64) -> PathBuf {
+        self.dir
+            .join(SNAPSHOTS_DIR)
+            .join(format!("{segment:020}.{SNAPSHOT_EXT}"))
+    }
+    /// ### Error for an out-of-place record in the log.
+    fn corrupt(&self, pos: &RecordPos) -> StorageError {
+        StorageError::Corrupt {
+            path: self.wal.dir().to_path_buf(),
+            offset: pos.offset,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+/// Version byte for account addresses (`PbKey`, `AccountId`).
+pub const ADDRESS_VERSION: u8 = 0x1c;
+/// Version byte for transaction ids (`TxnId`).
+pub const TXN_ID_VERSION: u8 = 0x41;
+/// Version byte for block ids (`BlockId`).
+pub const BLOCK_ID_VERSION: u8 = 0x19;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AddressError {
+    #[error("AddressError::InvalidEncoding- {0}")]
+    InvalidEncoding(#[from] bs58::decode::Error),
+    #[error("AddressError::InvalidLength- expected {expected} bytes, found {found}")]
+    InvalidLength { expected: usize, found: usize },
+}
+
+/// ### Encode bytes as a base58check string.
+///
+/// Layout: base58(`version` || `bytes` || checksum), where the checksum is the
+/// first 4 bytes of sha256(sha256(`version` || `bytes`)).\
+/// The version byte keeps different identifier types from being mistaken for each other,
+/// and the checksum catches typos.
+pub fn encode_check(version: u8, bytes: &[u8]) -> String {
+    bs58::encode(bytes)
+        .with_check_version(version)
+        .into_string()
+}
+
+/// ### Decode a base58check string into a fixed-size byte array.
+///
+/// Fails if the string is not base58, the checksum doesn't match, the version byte differs from `version`,
+/// or the payload is empty or not `N` bytes.
+pub fn decode_check<const N: usize>(version: u8, encoded: &str) -> Result<[u8; N], AddressError> {
+    let decoded = bs58::decode(encoded).with_check(Some(version)).into_vec()?;
+    // first byte is the version, checked along with the checksum - the body must follow it
+    let found = decoded.len().saturating_sub(1);
+    if found == 0 || found != N {
+        return Err(AddressError::InvalidLength { expected: N, found });
+    }
+
+    Ok(decoded[1..].try_into().expect("payload length checked"))
+}
@@ -0,0 +1,142 @@
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum EncodingError {
+    #[error("EncodingError::UnsupportedVersion- expected {expected}, found {found}")]
+    UnsupportedVersion { expected: u8, found: u8 },
+    #[error("EncodingError::UnexpectedEnd- Input ended before all fields were read")]
+    UnexpectedEnd,
+    #[error("EncodingError::LengthMismatch- expected {expected} bytes, found {found}")]
+    LengthMismatch { expected: usize, found: usize },
+    #[error("EncodingError::TrailingBytes- {0} unread bytes after last field")]
+    TrailingBytes(usize),
+    #[error("EncodingError::InvalidValue- {0}")]
+    InvalidValue(&'static str),
+}
+
+/// ## Writes values in the canonical binary encoding.
+///
+/// Used for every byte string that gets hashed or signed (i.e. `TxnHeader`, `BlockHeader`),
+/// so ids don't depend on a serde format and can be reproduced by other implementations.
+///
+/// Layout rules:
+/// 1. The first byte is the encoding version of the encoded type;
+/// 1. Fields are written in a fixed, documented order;
+/// 1. Integers are fixed-width, big-endian;
+/// 1. Byte strings are prefixed with their length as a big-endian `u32`;
+/// 1. Sequences are prefixed with their item count as a big-endian `u32`.
+#[derive(Debug)]
+pub struct CanonicalEncoder {
+    buf: Vec<u8>,
+}
+
+impl CanonicalEncoder {
+    /// ### Start a new encoding, beginning with the type's version byte.
+    pub fn new(version: u8) -> Self {
+        Self { buf: vec![version] }
+    }
+    pub fn put_u8(&mut self, value: u8) -> &mut Self {
+        self.buf.push(value);
+        self
+    }
+    pub fn put_u32(&mut self, value: u32) -> &mut Self {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+    pub fn put_u64(&mut self, value: u64) -> &mut Self {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+    pub fn put_u128(&mut self, value: u128) -> &mut Self {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+    /// ### Write a length-prefixed byte string.
+    pub fn put_bytes(&mut self, value: &[u8]) -> &mut Self {
+        let len: u32 = value
+            .len()
+            .try_into()
+            .expect("Byte string too long to encode");
+        self.put_u32(len);
+        self.buf.extend_from_slice(value);
+        self
+    }
+    /// ### Write the item count for a sequence that follows.
+    pub fn put_len(&mut self, len: usize) -> &mut Self {
+        let len: u32 = len.try_into().expect("Sequence too long to encode");
+        self.put_u32(len)
+    }
+    /// ### Return the encoded bytes.
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+/// ## Reads values written by `CanonicalEncoder`.
+#[derive(Debug)]
+pub struct CanonicalDecoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> CanonicalDecoder<'a> {
+    /// ### Start decoding, checking the version byte against `version`.
+    pub fn new(bytes: &'a [u8], version: u8) -> Result<Self, EncodingError> {
+        let mut decoder = Self { bytes };
+        let found = decoder.get_u8()?;
+        if found != version {
+            return Err(EncodingError::UnsupportedVersion {
+                expected: version,
+                found,
+            });
+        }
+
+        Ok(decoder)
+    }
+    fn take(&mut self, len: usize) -> Result<&'a [u8], EncodingError> {
+        if self.bytes.len() < len {
+            return Err(EncodingError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(taken)
+    }
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], EncodingError> {
+        Ok(self.take(N)?.try_into().expect("Length checked by take"))
+    }
+    pub fn get_u8(&mut self) -> Result<u8, EncodingError> {
+        Ok(self.take(1)?[0])
+    }
+    pub fn get_u32(&mut self) -> Result<u32, EncodingError> {
+        Ok(u32::from_be_bytes(self.take_array()?))
+    }
+    pub fn get_u64(&mut self) -> Result<u64, EncodingError> {
+        Ok(u64::from_be_bytes(self.take_array()?))
+    }
+    pub fn get_u128(&mut self) -> Result<u128, EncodingError> {
+        Ok(u128::from_be_bytes(self.take_array()?))
+    }
+    /// ### Read a length-prefixed byte string.
+    pub fn get_bytes(&mut self) -> Result<&'a [u8], EncodingError> {
+        let len = self.get_u32()? as usize;
+        self.take(len)
+    }
+    /// ### Read a length-prefixed byte string of a known, fixed length.
+    pub fn get_array<const N: usize>(&mut self) -> Result<[u8; N], EncodingError> {
+        let bytes = self.get_bytes()?;
+
+        bytes.try_into().map_err(|_| EncodingError::LengthMismatch {
+            expected: N,
+            found: bytes.len(),
+        })
+    }
+    /// ### Read the item count of a sequence.
+    pub fn get_len(&mut self) -> Result<usize, EncodingError> {
+        Ok(self.get_u32()? as usize)
+    }
+    /// ### Finish decoding - all input must have been consumed.
+    pub fn finish(self) -> Result<(), EncodingError> {
+        match self.bytes.len() {
+            0 => Ok(()),
+            n => Err(EncodingError::TrailingBytes(n)),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::utils::hash::BlakeHash;
+use serde::{Deserialize, Serialize};
+
+/// Domain separator for leaf hashes - keeps leaves from being passed off as inner nodes.
+const MERKLE_LEAF_PREFIX: u8 = 0;
+/// Domain separator for inner node hashes.
+const MERKLE_NODE_PREFIX: u8 = 1;
+
+/// ### Root of a tree with no leaves.
+pub fn empty_root() -> BlakeHash {
+    BlakeHash::from_bytes([0u8; 32])
+}
+/// ### Hash a leaf's data.
+pub fn hash_leaf(data: &[u8]) -> BlakeHash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[MERKLE_LEAF_PREFIX]);
+    hasher.update(data);
+
+    hasher.finalize().into()
+}
+/// ### Hash two child nodes into their parent.
+pub fn hash_node(left: &BlakeHash, right: &BlakeHash) -> BlakeHash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[MERKLE_NODE_PREFIX]);
+    hasher.update(left.as_bytes());
+    hasher.update(right.as_bytes());
+
+    hasher.finalize().into()
+}
+
+/// ## Binary Merkle tree over an ordered list of leaves.
+///
+/// Each level pairs up nodes left to right; a node without a pair is promoted to the next level as-is
+/// (it is not duplicated, so two different leaf lists can't produce the same root).
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    /// All levels, from the leaf hashes (first) up to the root (last).
+    levels: Vec<Vec<BlakeHash>>,
+}
+
+impl MerkleTree {
+    /// ### Build a tree from the leaves' data, in order.
+    pub fn new<T: AsRef<[u8]>>(leaves: impl IntoIterator<Item = T>) -> Self {
+        let mut level: Vec<BlakeHash> = leaves
+            .into_iter()
+            .map(|leaf| hash_leaf(leaf.as_ref()))
+            .collect();
+        let mut levels = vec![];
+
+        while level.len() > 1 {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(level);
+            level = next;
+        }
+        levels.push(level);
+
+        Self { levels }
+    }
+    /// ### Get the number of leaves.
+    pub fn leaf_ct(&self) -> usize {
+        self.levels[0].len()
+    }
+    /// ### Get the root hash - `empty_root()` if there are no leaves.
+    pub fn root(&self) -> BlakeHash {
+        match self.levels.last().and_then(|level| level.first()) {
+            Some(root) => *root,
+            None => empty_root(),
+        }
+    }
+    /// ### Create an inclusion proof for the leaf at `index`.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.leaf_ct() {
+            return None;
+        }
+        let mut siblings = vec![];
+        let mut idx = index;
+        for level in self.levels.iter().take(self.levels.len() - 1) {
+            let sibling_idx = idx ^ 1;
+            if let Some(sibling) = level.get(sibling_idx) {
+                siblings.push(*sibling);
+            }
+            idx /= 2;
+        }
+
+        Some(MerkleProof {
+            index: index as u64,
+            leaf_ct: self.leaf_ct() as u64,
+            siblings,
+        })
+    }
+}
+
+/// ## Proof that a leaf is included in a `MerkleTree` with a given root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Position of the leaf
+    index: u64,
+    /// Number of leaves in the tree - determines where nodes were promoted without a pair
+    leaf_ct: u64,
+    /// Sibling hashes from the leaf level up
+    siblings: Vec<BlakeHash>,
+}
+
+impl MerkleProof {
+    /// ### Get property `MerkleProof.index`.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+    /// ### Get property `MerkleProof.leaf_ct`.
+    pub fn leaf_ct(&self) -> u64 {
+        self.leaf_ct
+    }
+    /// ### Check that `leaf` is at `index` in the tree with root `root`.
+    pub fn verify(&self, root: &BlakeHash, leaf: &[u8]) -> bool {
+        if self.index >= self.leaf_ct {
+            return false;
+        }
+        let mut siblings = self.siblings.iter();
+        let mut hash = hash_leaf(leaf);
+        let mut idx = self.index;
+        let mut level_len = self.leaf_ct;
+
+        while level_len > 1 {
+            if idx % 2 == 1 {
+                match siblings.next() {
+                    Some(left) => hash = hash_node(left, &hash),
+                    None => return false,
+                }
+            } else if idx + 1 < level_len {
+                match siblings.next() {
+                    Some(right) => hash = hash_node(&hash, right),
+                    None => return false,
+                }
+            }
+            idx /= 2;
+            level_len = level_len.div_ceil(2);
+        }
+
+        siblings.next().is_none() && &hash == root
+    }
+}
@@ -1 +1,5 @@
+pub mod address;
+pub mod encoding;
 pub mod hash;
+pub mod merkle;
+pub mod sparse_merkle;
@@ -0,0 +1,161 @@
+use crate::utils::{
+    hash::BlakeHash,
+    merkle::{empty_root, hash_leaf, hash_node},
+};
+use {
+    serde::{Deserialize, Serialize},
+    std::collections::BTreeMap,
+};
+
+/// Key size in bytes - a key's bits are its path from the root.
+pub const SMT_KEY_LEN: usize = 32;
+/// Maximum depth of the tree (one level per key bit).
+pub const SMT_DEPTH: usize = SMT_KEY_LEN * 8;
+
+pub type SmtKey = [u8; SMT_KEY_LEN];
+
+/// ### Get bit `depth` of a key, most significant bit first (`false` = left).
+pub fn key_bit(key: &SmtKey, depth: usize) -> bool {
+    (key[depth / 8] >> (7 - depth % 8)) & 1 == 1
+}
+/// ### Hash a leaf - commits to both the key and the value.
+pub fn hash_smt_leaf(key: &SmtKey, value_hash: &BlakeHash) -> BlakeHash {
+    let mut data = key.to_vec();
+    data.extend_from_slice(value_hash.as_bytes());
+
+    hash_leaf(&data)
+}
+
+/// ## Sparse Merkle tree over 256-bit keys.
+///
+/// Each key's bits are its path from the root. Empty subtrees hash to `empty_root()`, and a
+/// subtree holding a single leaf is replaced by that leaf, so the tree is only as deep as needed
+/// to tell its keys apart. The root is independent of insertion order.
+#[derive(Debug, Clone, Default)]
+pub struct SparseMerkleTree {
+    /// Hash of each key's value
+    leaves: BTreeMap<SmtKey, BlakeHash>,
+}
+
+impl SparseMerkleTree {
+    /// ### Build a tree from (key, value hash) pairs.
+    pub fn new(leaves: impl IntoIterator<Item = (SmtKey, BlakeHash)>) -> Self {
+        Self {
+            leaves: leaves.into_iter().collect(),
+        }
+    }
+    /// ### Get the number of leaves.
+    pub fn leaf_ct(&self) -> usize {
+        self.leaves.len()
+    }
+    /// ### Insert or replace the value hash stored at `key`.
+    pub fn insert(&mut self, key: SmtKey, value_hash: BlakeHash) {
+        self.leaves.insert(key, value_hash);
+    }
+    /// ### Get the root hash - `empty_root()` if there are no leaves.
+    pub fn root(&self) -> BlakeHash {
+        let leaves: Vec<(&SmtKey, &BlakeHash)> = self.leaves.iter().collect();
+
+        Self::subtree_root(&leaves, 0)
+    }
+    /// ### Hash the subtree at `depth` holding `leaves` (sorted by key).
+    fn subtree_root(leaves: &[(&SmtKey, &BlakeHash)], depth: usize) -> BlakeHash {
+        match leaves {
+            [] => empty_root(),
+            [(key, value_hash)] => hash_smt_leaf(key, value_hash),
+            _ => {
+                // keys are sorted, so the left subtree (bit = 0) is a prefix
+                let split = leaves.partition_point(|(key, _)| !key_bit(key, depth));
+                let (left, right) = leaves.split_at(split);
+
+                hash_node(
+                    &Self::subtree_root(left, depth + 1),
+                    &Self::subtree_root(right, depth + 1),
+                )
+            }
+        }
+    }
+    /// ### Create a proof that `key` is in the tree, or that it is not.
+    pub fn proof(&self, key: &SmtKey) -> SparseMerkleProof {
+        let mut leaves: Vec<(&SmtKey, &BlakeHash)> = self.leaves.iter().collect();
+        let mut siblings = vec![];
+        let mut depth = 0;
+
+        // walk down the key's path until the subtree holds at most one leaf
+        while leaves.len() > 1 {
+            let split = leaves.partition_point(|(leaf_key, _)| !key_bit(leaf_key, depth));
+            let (left, right) = leaves.split_at(split);
+            let (path, sibling) = match key_bit(key, depth) {
+                false => (left, right),
+                true => (right, left),
+            };
+            siblings.push(Self::subtree_root(sibling, depth + 1));
+            leaves = path.to_vec();
+            depth += 1;
+        }
+
+        SparseMerkleProof {
+            siblings,
+            leaf: leaves
+                .first()
+                .map(|(leaf_key, value_hash)| (**leaf_key, **value_hash)),
+        }
+    }
+}
+
+/// ## Proof that a key is (or is not) in a `SparseMerkleTree` with a given root.
+///
+/// Holds the sibling hashes along the key's path and the leaf the path ends at:
+/// - the key's own leaf - the key is in the tree;
+/// - another key's leaf, or no leaf (empty subtree) - the key is not in the tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseMerkleProof {
+    /// Sibling hashes from the root down
+    siblings: Vec<BlakeHash>,
+    /// Leaf at the end of the path - (key, value hash)
+    leaf: Option<(SmtKey, BlakeHash)>,
+}
+
+impl SparseMerkleProof {
+    /// ### Check that `key` maps to `value_hash` in the tree with root `root` - or, for `None`, that `key` is absent.
+    pub fn verify(&self, root: &BlakeHash, key: &SmtKey, value_hash: Option<&BlakeHash>) -> bool {
+        let depth = self.siblings.len();
+        if depth > SMT_DEPTH {
+            return false;
+        }
+        let leaf_hash = match (&self.leaf, value_hash) {
+            // inclusion: the path must end at this key's leaf
+            (Some((leaf_key, leaf_value)), Some(value_hash)) => {
+                if leaf_key != key || leaf_value != value_hash {
+                    return false;
+                }
+                hash_smt_leaf(leaf_key, leaf_value)
+            }
+            // exclusion: the path ends at a different key sharing the same prefix
+            (Some((leaf_key, leaf_value)), None) => {
+                let same_prefix = (0..depth).all(|d| key_bit(leaf_key, d) == key_bit(key, d));
+                if leaf_key == key || !same_prefix {
+                    return false;
+                }
+                hash_smt_leaf(leaf_key, leaf_value)
+            }
+            // exclusion: the path ends at an empty subtree
+            (None, None) => empty_root(),
+            (None, Some(_)) => return false,
+        };
+
+        let computed_root =
+            self.siblings
+                .iter()
+                .enumerate()
+                .rev()
+                .fold(leaf_hash, |hash, (depth, sibling)| {
+                    match key_bit(key, depth) {
+                        false => hash_node(&hash, sibling),
+                        true => hash_node(sibling, &hash),
+                    }
+                });
+
+        &computed_root == root
+    }
+}
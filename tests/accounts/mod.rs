@@ -1,6 +1,13 @@
 use posbc::{
-    accounts::{account::Account, accounts::Accounts},
-    ledger::general::Result,
+    accounts::{
+        account::Account, accounts::Accounts, multisig::MultisigPolicy, proof::AccountProof,
+    },
+    ledger::{
+        block::block_header::BlockHeader,
+        chain_id::ChainId,
+        general::{PbKey, Result},
+    },
+    utils::merkle::empty_root,
 };
 // test
 use crate::common::{create_transfer_txn, init_send_recv, init_users};
@@ -122,3 +129,80 @@ fn balance_decr_pass() -> Result<()> {
 
     Ok(())
 }
+
+/// State root golden vector - 0x01.. and 0x02.. share a 6-bit prefix, 0x81.. branches at the root.
+#[test]
+fn state_root_pass() {
+    let mut accounts = Accounts::new();
+    assert_eq!(accounts.state_root(), empty_root());
+
+    let accts = [
+        Account::new(&[0x81u8; 32].into(), Some(5)),
+        Account::new(&[2u8; 32].into(), Some(7)),
+        Account::new(&[1u8; 32].into(), Some(10)),
+    ];
+    for acct in accts {
+        accounts.add_acct(acct);
+    }
+
+    assert_eq!(
+        accounts.state_root().to_string(),
+        "02758baedad7e00d5c9430a7036505c14e5f1ce508a9dd015fdd00b333cd2436"
+    );
+}
+
+/// The state root commits to multisig accounts' signing policies - proofs carry the policy.
+#[test]
+fn state_root_multisig_policy_pass() -> Result<()> {
+    let users = init_users();
+    let policy = MultisigPolicy::new(2, vec![users.test1.pbkey(), users.test2.pbkey()])?;
+    let acct_id = policy.account_id();
+    let mut multisig = Accounts::new();
+    multisig.add_multisig_acct(policy.clone(), Some(1000));
+    // the same account and balance, without the policy
+    let mut plain = Accounts::new();
+    plain.add_acct(Account::new(&acct_id, Some(1000)));
+
+    assert_eq!(
+        multisig.get_acct(&acct_id).map(Account::state_hash),
+        plain.get_acct(&acct_id).map(Account::state_hash)
+    );
+    assert_ne!(multisig.state_root(), plain.state_root());
+
+    let header = |accounts: &Accounts| {
+        BlockHeader::genesis_at(
+            ChainId::default(),
+            PbKey([0u8; 32]),
+            accounts.state_root(),
+            0,
+        )
+    };
+    let proof = AccountProof::new(&multisig, &acct_id);
+    assert_eq!(proof.multisig_policy(), Some(&policy));
+    assert!(proof.verify(&header(&multisig)));
+    assert!(!proof.verify(&header(&plain)));
+    let proof = AccountProof::new(&plain, &acct_id);
+    assert_eq!(proof.multisig_policy(), None);
+    assert!(!proof.verify(&header(&multisig)));
+
+    Ok(())
+}
+
+/// Any balance change moves the state root.
+#[test]
+fn state_root_balance_change_pass() {
+    let mut accounts = Accounts::new();
+    for acct in util_init_accounts() {
+        accounts.add_acct(acct);
+    }
+    let root_pre = accounts.state_root();
+
+    let (send, _recv) = init_send_recv();
+    accounts
+        .get_acct_mut(&send.pbkey().into())
+        .unwrap()
+        .increase_balance(&create_transfer_txn(1))
+        .unwrap();
+
+    assert_ne!(accounts.state_root(), root_pre);
+}
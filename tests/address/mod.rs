@@ -0,0 +1,84 @@
+use posbc::{
+    accounts::account::{Account, AccountId},
+    ledger::{
+        block::{block_id::BlockId, types::BlockTxnMap, Block},
+        chain_id::ChainId,
+        general::PbKey,
+        txn::txn_id::TxnId,
+    },
+    utils::address::{decode_check, encode_check, AddressError, ADDRESS_VERSION},
+};
+use std::str::FromStr;
+// test
+use crate::common::{create_transfer_txn_default, init_users};
+
+#[test]
+fn address_round_trip_pass() {
+    let users = init_users();
+    let pbkey = users.send.pbkey();
+    let acct_id = AccountId::from(pbkey);
+    let txn_id = create_transfer_txn_default().id().to_owned();
+    let block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        users.main.pbkey(),
+        BlockId::from_bytes([0u8; 64]),
+        0,
+    );
+
+    assert_eq!(PbKey::from_str(&pbkey.to_string()).unwrap(), pbkey);
+    assert_eq!(AccountId::from_str(&acct_id.to_string()).unwrap(), acct_id);
+    assert_eq!(txn_id.to_string().parse::<TxnId>().unwrap(), txn_id);
+    assert_eq!(
+        BlockId::from_str(&block.id().to_string()).unwrap(),
+        block.id()
+    );
+
+    // a key and its account share an address
+    assert_eq!(pbkey.to_string(), acct_id.to_string());
+    let acct = Account::new(&acct_id, None);
+    assert_eq!(acct.id_str(), pbkey.to_string());
+}
+
+/// Typos are caught by the checksum.
+#[test]
+fn address_fail_checksum() {
+    let users = init_users();
+    let address = users.send.pbkey().to_string();
+
+    // swap one character for a different valid base58 character
+    let mut chars: Vec<char> = address.chars().collect();
+    let idx = chars.len() / 2;
+    chars[idx] = if chars[idx] == '2' { '3' } else { '2' };
+    let typo: String = chars.into_iter().collect();
+
+    assert!(PbKey::from_str(&typo).is_err());
+    assert!(PbKey::from_str("").is_err());
+    assert!(PbKey::from_str("0OIl").is_err(), "Not base58");
+}
+
+/// Identifiers of one type do not parse as another.
+#[test]
+fn address_fail_wrong_type() {
+    let users = init_users();
+    let txn_id = create_transfer_txn_default().id().to_owned();
+    let block_id = BlockId::from_bytes([7u8; 64]);
+
+    assert!(PbKey::from_str(&txn_id.to_string()).is_err());
+    assert!(BlockId::from_str(&txn_id.to_string()).is_err());
+    assert!(BlockId::from_str(&users.send.pbkey().to_string()).is_err());
+    assert!(AccountId::from_str(&block_id.to_string()).is_err());
+}
+
+/// Payloads with a valid checksum but a missing or short body are rejected.
+#[test]
+fn address_fail_short_payload() {
+    for body in [&[][..], &[7u8; 31][..]] {
+        let encoded = encode_check(ADDRESS_VERSION, body);
+        assert!(matches!(
+            decode_check::<32>(ADDRESS_VERSION, &encoded),
+            Err(AddressError::InvalidLength { expected: 32, found }) if found == body.len()
+        ));
+        assert!(PbKey::from_str(&encoded).is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use posbc::{
+    ledger::{
+        block::{error::BlockError, Block},
+        blockchain::{
+            archive::{ArchiveHeader, ImportSummary, ARCHIVE_MAGIC, ARCHIVE_VERSION},
+            error::{ArchiveError, BlockchainError},
+            Blockchain,
+        },
+        general::Result,
+    },
+    storage::segment_log::encode_record,
+};
+use std::fs::{self, File};
+// test
+use crate::common::{
+    fxns::{add_transfer_block, init_genesis_spec, init_temp_dir},
+    init_users,
+};
+
+/// ### Write an archive by hand - `blocks` are JSON encoded blocks, i.e. tampered with.
+fn craft_archive(header: &ArchiveHeader, blocks: &[String]) -> Result<Vec<u8>> {
+    let mut archive = ARCHIVE_MAGIC.to_vec();
+    archive.extend(ARCHIVE_VERSION.to_be_bytes());
+    let header = format!(r#"{{"Header":{}}}"#, serde_json::to_string(header)?);
+    archive.extend(encode_record(header.as_bytes()));
+    for block in blocks {
+        archive.extend(encode_record(format!(r#"{{"Block":{block}}}"#).as_bytes()));
+    }
+
+    Ok(archive)
+}
+
+/// A chain exported to an archive file imports onto a fresh node from the same genesis.
+#[test]
+fn archive_round_trip_pass() -> Result<()> {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let mut source = Blockchain::from_genesis(&spec)?;
+    let mut txns = vec![];
+    for (recv, amt) in [(&users.recv, 100), (&users.test1, 50), (&users.test2, 25)] {
+        txns.push(add_transfer_block(&mut source, &users, recv, amt)?);
+    }
+
+    let dir = init_temp_dir("archive-round-trip");
+    let path = dir.join("chain.archive");
+    let header = source.export_archive(File::create(&path)?, 2, 4, true)?;
+    assert_eq!((header.from_height, header.to_height), (2, 4));
+    assert_eq!(header.genesis_id, source.block_at(1).unwrap().id());
+    assert!(header.has_state);
+    assert_eq!(&fs::read(&path)?[..8], ARCHIVE_MAGIC);
+
+    let mut imported = Blockchain::from_genesis(&spec)?;
+    let summary = imported.import_archive(File::open(&path)?)?;
+    assert_eq!(
+        summary,
+        ImportSummary {
+            imported: 3,
+            skipped: 0,
+            height: 4
+        }
+    );
+    assert_eq!(imported.last_block().id(), source.last_block().id());
+    assert_eq!(
+        imported.accounts().state_root(),
+        source.accounts().state_root()
+    );
+    for txn in txns.iter() {
+        assert!(imported.find_txn(txn.id())?.is_some());
+    }
+
+    // importing again only skips
+    let summary = imported.import_archive(File::open(&path)?)?;
+    assert_eq!((summary.imported, summary.skipped), (0, 3));
+    assert_eq!(summary.height, 4);
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+/// Archives import in parts, but only from the chain's next height.
+#[test]
+fn archive_partial_import_pass() -> Result<()> {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let mut source = Blockchain::from_genesis(&spec)?;
+    for amt in [10, 20, 30] {
+        add_transfer_block(&mut source, &users, &users.recv, amt)?;
+    }
+    let mut first = vec![];
+    source.export_archive(&mut first, 1, 2, false)?;
+    let mut second = vec![];
+    source.export_archive(&mut second, 3, 4, true)?;
+
+    let mut imported = Blockchain::from_genesis(&spec)?;
+    let err = imported.import_archive(second.as_slice()).unwrap_err();
+    assert!(matches!(
+        err,
+        ArchiveError::MissingBlocks {
+            expected: 2,
+            found: 3
+        }
+    ));
+    assert_eq!(imported.height(), 1);
+
+    // genesis is already on the chain
+    let summary = imported.import_archive(first.as_slice())?;
+    assert_eq!((summary.imported, summary.skipped), (1, 1));
+    let summary = imported.import_archive(second.as_slice())?;
+    assert_eq!((summary.imported, summary.height), (2, 4));
+    assert_eq!(imported.last_block().id(), source.last_block().id());
+
+    Ok(())
+}
+
+/// The first block that fails validation stops the import, keeping the blocks before it.
+#[test]
+fn archive_import_invalid_block_fail() -> Result<()> {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let mut source = Blockchain::from_genesis(&spec)?;
+    for amt in [10, 20] {
+        add_transfer_block(&mut source, &users, &users.recv, amt)?;
+    }
+    let mut archive = vec![];
+    source.export_archive(&mut archive, 2, 3, false)?;
+
+    // a fork sharing block 2, with a different block 3
+    let mut fork = Blockchain::from_genesis(&spec)?;
+    let mut shared = vec![];
+    source.export_archive(&mut shared, 2, 2, false)?;
+    fork.import_archive(shared.as_slice())?;
+    add_transfer_block(&mut fork, &users, &users.test1, 30)?;
+    add_transfer_block(&mut fork, &users, &users.test1, 40)?;
+    let mut forked = vec![];
+    fork.export_archive(&mut forked, 2, 4, false)?;
+
+    let mut imported = Blockchain::from_genesis(&spec)?;
+    imported.import_archive(archive.as_slice())?;
+    let err = imported.import_archive(forked.as_slice()).unwrap_err();
+    match err {
+        ArchiveError::InvalidBlock { height, source } => {
+            assert_eq!(height, 3);
+            assert!(matches!(
+                source.downcast_ref::<BlockchainError>(),
+                Some(BlockchainError::ConflictingBlock(3))
+            ));
+        }
+        err => panic!("Unexpected error: {err}"),
+    }
+    assert_eq!(imported.height(), 3);
+    assert_eq!(imported.last_block().id(), source.last_block().id());
+
+    Ok(())
+}
+
+/// Damaged archives, and archives of another chain, are rejected.
+#[test]
+fn archive_import_rejected_fail() -> Result<()> {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let mut source = Blockchain::from_genesis(&spec)?;
+    add_transfer_block(&mut source, &users, &users.recv, 10)?;
+    let mut archive = vec![];
+    source.export_archive(&mut archive, 1, 2, true)?;
+
+    // flipped byte in the last record
+    let mut corrupted = archive.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 0xff;
+    let mut imported = Blockchain::from_genesis(&spec)?;
+    assert!(matches!(
+        imported.import_archive(corrupted.as_slice()).unwrap_err(),
+        ArchiveError::Corrupt(_)
+    ));
+
+    // cut off mid-record
+    let truncated = &archive[..archive.len() - 4];
+    assert!(matches!(
+        imported.import_archive(truncated).unwrap_err(),
+        ArchiveError::Corrupt(_)
+    ));
+
+    // unknown version
+    let mut versioned = archive.clone();
+    versioned[8..12].copy_from_slice(&99u32.to_be_bytes());
+    assert!(matches!(
+        imported.import_archive(versioned.as_slice()).unwrap_err(),
+        ArchiveError::UnsupportedVersion(99)
+    ));
+
+    // another genesis
+    let mut json: serde_json::Value = serde_json::from_str(&spec.to_json()?)?;
+    json["chain_id"] = "posbc-other".into();
+    let mut other = Blockchain::from_genesis(&serde_json::from_value(json)?)?;
+    assert!(matches!(
+        other.import_archive(archive.as_slice()).unwrap_err(),
+        ArchiveError::GenesisMismatch(id) if id == source.block_at(1).unwrap().id()
+    ));
+    assert_eq!(other.height(), 1);
+
+    // heights past the tip
+    assert!(matches!(
+        source.export_archive(vec![], 2, 3, false).unwrap_err(),
+        ArchiveError::InvalidRange { from: 2, to: 3 }
+    ));
+
+    Ok(())
+}
+
+/// Archives with well-formed records but invalid heights or blocks are rejected without panicking.
+#[test]
+fn archive_import_malformed_fail() -> Result<()> {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let mut source = Blockchain::from_genesis(&spec)?;
+    add_transfer_block(&mut source, &users, &users.recv, 10)?;
+    let block: &Block = source.last_block();
+    let header = ArchiveHeader {
+        chain_id: block.chain_id().clone(),
+        genesis_id: source.block_at(1).unwrap().id(),
+        from_height: 2,
+        to_height: 2,
+        has_state: false,
+    };
+    let block_json = serde_json::to_string(block)?;
+    let mut imported = Blockchain::from_genesis(&spec)?;
+
+    // heights must be a range from 1 up
+    for (from_height, to_height) in [(0, 2), (3, 2)] {
+        let archive = craft_archive(
+            &ArchiveHeader {
+                from_height,
+                to_height,
+                ..header.clone()
+            },
+            std::slice::from_ref(&block_json),
+        )?;
+        assert!(matches!(
+            imported.import_archive(archive.as_slice()).unwrap_err(),
+            ArchiveError::Corrupt(_)
+        ));
+    }
+
+    // the block's id is serialized last, after its txns' ids
+    let id_at = block_json.rfind(r#""id":["#).unwrap();
+    let id_end = id_at + block_json[id_at..].find(']').unwrap() + 1;
+    let no_id = [&block_json[..id_at], r#""id":null"#, &block_json[id_end..]].concat();
+    let archive = craft_archive(&header, &[no_id])?;
+    match imported.import_archive(archive.as_slice()).unwrap_err() {
+        ArchiveError::InvalidBlock { height, source } => {
+            assert_eq!(height, 2);
+            assert!(matches!(
+                source.downcast_ref::<BlockError>(),
+                Some(BlockError::EmptyId)
+            ));
+        }
+        err => panic!("Unexpected error: {err}"),
+    }
+    assert_eq!(imported.height(), 1);
+
+    // the last height can't be reached, without overflowing
+    let archive = craft_archive(
+        &ArchiveHeader {
+            to_height: u128::MAX,
+            ..header.clone()
+        },
+        &[block_json],
+    )?;
+    assert!(matches!(
+        imported.import_archive(archive.as_slice()).unwrap_err(),
+        ArchiveError::Incomplete { expected, found: 2 } if expected == u128::MAX
+    ));
+
+    Ok(())
+}
+
+/// Signed blocks that don't extend the chain's tip are rejected.
+#[test]
+fn archive_import_unlinked_fail() -> Result<()> {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let mut source = Blockchain::from_genesis(&spec)?;
+    for amt in [10, 20] {
+        add_transfer_block(&mut source, &users, &users.recv, amt)?;
+    }
+    let mut archive = vec![];
+    source.export_archive(&mut archive, 3, 3, false)?;
+
+    // another block 2 - the archive's block 3 doesn't link to it
+    let mut imported = Blockchain::from_genesis(&spec)?;
+    add_transfer_block(&mut imported, &users, &users.test1, 30)?;
+    match imported.import_archive(archive.as_slice()).unwrap_err() {
+        ArchiveError::InvalidBlock { height, source } => {
+            assert_eq!(height, 3);
+            assert!(matches!(
+                source.downcast_ref::<BlockchainError>(),
+                Some(BlockchainError::InvalidPrevBlockId(3))
+            ));
+        }
+        err => panic!("Unexpected error: {err}"),
+    }
+    assert_eq!(imported.height(), 2);
+
+    // nor blocks above the next height
+    let mut fresh = Blockchain::from_genesis(&spec)?;
+    let err = fresh.replay_block(source.last_block().clone()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockchainError>(),
+        Some(BlockchainError::InvalidBlockHeight {
+            expected: 2,
+            found: 3
+        })
+    ));
+
+    Ok(())
+}
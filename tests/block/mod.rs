@@ -1,5 +1,10 @@
 use posbc::ledger::{
-    block::{block_id::BlockId, types::BlockTxnMap, Block},
+    block::{
+        block_id::BlockId, block_signature::BlockSignature, error::BlockError, types::BlockTxnMap,
+        Block,
+    },
+    chain_id::ChainId,
+    general::PbKey,
     txn::{Txn, TxnType},
 };
 // test
@@ -23,7 +28,13 @@ fn add_sample_txns_to_block(num_txns: u8, block: &mut Block) {
     for x in 0..num_txns {
         let amt_to_send = (x as u128) + 1;
         // create sample txn
-        let new_txn = Txn::new_signed(&send.wallet, recv.pbkey(), amt_to_send, TxnType::Transfer);
+        let new_txn = Txn::new_signed(
+            &send.wallet,
+            ChainId::default(),
+            recv.pbkey(),
+            amt_to_send,
+            TxnType::Transfer,
+        );
 
         // add txn to map
         block.add_txn(new_txn);
@@ -39,7 +50,13 @@ fn create_empty_block_pass() {
     let prev_block_id = BlockId::from_bytes([0u8; 64]);
     let prev_blockheight = 0;
     let leader = main.pbkey();
-    let mut block = Block::new(BlockTxnMap::new(), leader, prev_block_id, prev_blockheight);
+    let mut block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        leader,
+        prev_block_id,
+        prev_blockheight,
+    );
     // create txn map
     add_sample_txns_to_block(0, &mut block);
 
@@ -56,7 +73,13 @@ fn create_full_block_pass() {
     let prev_block_id = BlockId::from_bytes([0u8; 64]);
     let prev_blockheight = 0;
     let leader = main.pbkey();
-    let mut block = Block::new(BlockTxnMap::new(), leader, prev_block_id, prev_blockheight);
+    let mut block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        leader,
+        prev_block_id,
+        prev_blockheight,
+    );
 
     // sign
     block.sign(&main.wallet);
@@ -81,7 +104,13 @@ fn is_signature_valid_pass() {
     let prev_block_id = BlockId::from_bytes([0u8; 64]);
     let prev_blockheight = 0;
     let leader = main.pbkey();
-    let mut block = Block::new(BlockTxnMap::new(), leader, prev_block_id, prev_blockheight);
+    let mut block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        leader,
+        prev_block_id,
+        prev_blockheight,
+    );
     block.sign(&main.wallet);
 
     assert_eq!(
@@ -97,3 +126,116 @@ fn is_signature_valid_pass() {
         "{block:?}",
     );
 }
+
+/// Malformed signatures and keys (i.e. from a peer) are errors, not panics.
+#[test]
+fn is_signature_valid_fail_malformed() {
+    let users: UsersInfo = init_users();
+    let main = users.main;
+
+    let prev_block_id = BlockId::from_bytes([0u8; 64]);
+    let mut block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        main.pbkey(),
+        prev_block_id,
+        0,
+    );
+
+    // unsigned
+    assert!(block.is_signature_valid(&main.pbkey()).is_err());
+
+    // signer key is not a valid curve point
+    block.sign(&main.wallet);
+    assert!(block.is_signature_valid(&PbKey([0xffu8; 32])).is_err());
+
+    // truncated signature bytes
+    assert!(ed25519::Signature::try_from(BlockSignature(vec![1, 2, 3])).is_err());
+}
+
+#[test]
+fn txn_proof_pass() {
+    let users: UsersInfo = init_users();
+    let main = users.main;
+
+    let prev_block_id = BlockId::from_bytes([0u8; 64]);
+    let mut block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        main.pbkey(),
+        prev_block_id,
+        0,
+    );
+    add_sample_txns_to_block(5, &mut block);
+    block.sign(&main.wallet);
+
+    // every txn in the body is provable against the header alone
+    for (position, txn) in block.txns().values().enumerate() {
+        let proof = block.txn_proof(txn.id()).unwrap();
+
+        assert_eq!(proof.position(), position as u64);
+        assert!(proof.verify(block.header()));
+    }
+    assert!(block.is_valid(&main.pbkey()).is_ok());
+}
+
+#[test]
+fn txn_proof_fail() {
+    let users: UsersInfo = init_users();
+    let main = users.main;
+
+    let prev_block_id = BlockId::from_bytes([0u8; 64]);
+    let mut block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        main.pbkey(),
+        prev_block_id,
+        0,
+    );
+    add_sample_txns_to_block(3, &mut block);
+
+    // txn not in the block
+    let other_txn = Txn::new_signed(
+        &main.wallet,
+        ChainId::default(),
+        users.recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
+    assert!(block.txn_proof(other_txn.id()).is_none());
+
+    // proof does not hold once the block body changes
+    let txn_id = *block.txns().values().next().unwrap().id();
+    let proof = block.txn_proof(&txn_id).unwrap();
+    block.add_txn(other_txn);
+    assert!(!proof.verify(block.header()));
+}
+
+/// A block whose body doesn't match the header's `txn_root` is rejected.
+#[test]
+fn is_valid_fail_incorrect_txn_root() {
+    let users: UsersInfo = init_users();
+    let main = users.main;
+
+    let prev_block_id = BlockId::from_bytes([0u8; 64]);
+    let mut block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        main.pbkey(),
+        prev_block_id,
+        0,
+    );
+    add_sample_txns_to_block(3, &mut block);
+    block.sign(&main.wallet);
+
+    // drop the body, keeping the signed header
+    let mut block_json: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&block).unwrap()).unwrap();
+    block_json["txns"] = serde_json::json!({});
+    let stripped_block: Block = serde_json::from_value(block_json).unwrap();
+
+    assert!(matches!(
+        stripped_block.is_valid(&main.pbkey()),
+        Err(BlockError::IncorrectTxnRoot)
+    ));
+}
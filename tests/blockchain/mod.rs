@@ -1,18 +1,34 @@
-use posbc::ledger::{
-    block::{types::BlockTxnMap, Block},
-    general::Result,
-    txn::{Txn, TxnType},
-    txn_pool::{TxnMap, TxnPool},
+use posbc::{
+    accounts::account::AccountId,
+    ledger::{
+        block::{block_id::BlockId, error::BlockError, types::BlockTxnMap, Block},
+        blockchain::{address_index::HistoryQuery, error::BlockchainError, Blockchain},
+        chain_id::ChainId,
+        chain_params::{
+            ChainParams, DEFAULT_BLOCK_BYTE_LIMIT, DEFAULT_BLOCK_GAS_LIMIT, DEFAULT_GAS_PRICE,
+            TRANSFER_GAS,
+        },
+        general::Result,
+        txn::{error::TxnError, Txn, TxnType},
+        txn_pool::{TxnMap, TxnPool},
+    },
+    utils::merkle::empty_root,
 };
 use std::{thread, time};
 // test
-use crate::common::fxns::{create_block, init_blockchain, init_blockchain_and_accounts};
+use crate::common::{
+    fxns::{
+        build_block, create_block, create_block_from_last, init_account_map, init_blockchain,
+        init_blockchain_and_accounts,
+    },
+    init_users, UsersInfo,
+};
 
 #[test]
 fn create_blockchain_pass() {
     let (users, mut blockchain) = init_blockchain();
     let main = users.main;
-    let genesis = Block::new_genesis(&main.wallet);
+    let genesis = Block::new_genesis(ChainId::default(), &main.wallet);
 
     blockchain
         .add_block(genesis)
@@ -54,7 +70,7 @@ fn add_block_to_blockchain_pass() {
         "Blockchain must have 0 blocks after initializing"
     );
 
-    let genesis = Block::new_genesis(&main.wallet);
+    let genesis = Block::new_genesis(ChainId::default(), &main.wallet);
     blockchain
         .add_block(genesis)
         .expect("Error adding genesis block to blockchain.");
@@ -122,6 +138,7 @@ fn execute_txn_via_blockchain_pass() -> Result<()> {
     // populate the block with a transaction
     let txn_to_add = Txn::new_signed(
         &users.send.wallet,
+        ChainId::default(),
         users.recv.pbkey(),
         amt_to_send,
         TxnType::Transfer,
@@ -167,7 +184,7 @@ fn add_txn_to_blocks_pass() -> Result<()> {
     let send = users.send;
     let recv = users.recv;
 
-    let genesis = Block::new_genesis(&main.wallet);
+    let genesis = Block::new_genesis(ChainId::default(), &main.wallet);
     blockchain
         .add_block(genesis)
         .expect("Error adding genesis block to blockchain.");
@@ -187,7 +204,13 @@ fn add_txn_to_blocks_pass() -> Result<()> {
 
     for _ in 0..txn_ct {
         thread::sleep(ten_millis);
-        let txn: Txn = Txn::new_signed(&send.wallet, recv.pbkey(), amt_to_send, txn_type);
+        let txn: Txn = Txn::new_signed(
+            &send.wallet,
+            ChainId::default(),
+            recv.pbkey(),
+            amt_to_send,
+            txn_type,
+        );
 
         txn_pool.add_txn(txn.clone())?;
         // @todo only add txn id, not whole txn
@@ -202,6 +225,7 @@ fn add_txn_to_blocks_pass() -> Result<()> {
     // get the genesis block
     let prev_block = blockchain.last_block();
     let mut new_block = Block::new(
+        ChainId::default(),
         BlockTxnMap::new(),
         main.pbkey(),
         prev_block.id(),
@@ -231,3 +255,481 @@ fn add_txn_to_blocks_pass() -> Result<()> {
 
     Ok(())
 }
+
+/// ### Create `txn_ct` transfers from `send` to `recv`, of 1 to `txn_ct`.
+fn transfer_txns(users: &UsersInfo, txn_ct: u128) -> Vec<Txn> {
+    (1..=txn_ct)
+        .map(|amt| {
+            Txn::new_signed(
+                &users.send.wallet,
+                ChainId::default(),
+                users.recv.pbkey(),
+                amt,
+                TxnType::Transfer,
+            )
+        })
+        .collect()
+}
+
+/// A block built and executed by the leader replays to the same state on another node.
+#[test]
+fn replay_block_pass() -> Result<()> {
+    let (users, mut leader_chain) = init_blockchain_and_accounts();
+    let (_, mut peer_chain) = init_blockchain_and_accounts();
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    leader_chain.add_block(genesis.clone())?;
+    peer_chain.add_block(genesis)?;
+
+    let block = build_block(&users, &mut leader_chain, &transfer_txns(&users, 3))?;
+    assert_eq!(block.state_root(), &leader_chain.accounts().state_root());
+    leader_chain.add_block(block.clone())?;
+
+    peer_chain.replay_block(block)?;
+    assert_eq!(
+        peer_chain.accounts().state_root(),
+        leader_chain.accounts().state_root()
+    );
+    assert_eq!(peer_chain.blocks().len(), 2);
+
+    Ok(())
+}
+
+/// A block committing to the wrong state is rejected, and leaves the state untouched.
+#[test]
+fn replay_block_fail_incorrect_state_root() -> Result<()> {
+    let (users, mut leader_chain) = init_blockchain_and_accounts();
+    let (_, mut peer_chain) = init_blockchain_and_accounts();
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    leader_chain.add_block(genesis.clone())?;
+    peer_chain.add_block(genesis)?;
+
+    let mut block = build_block(&users, &mut leader_chain, &transfer_txns(&users, 3))?;
+    block.set_state_root(empty_root());
+    block.sign(&users.main.wallet);
+
+    let state_root_pre = peer_chain.accounts().state_root();
+    let err = peer_chain.replay_block(block).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockchainError>(),
+        Some(BlockchainError::IncorrectStateRoot)
+    ));
+    assert_eq!(peer_chain.accounts().state_root(), state_root_pre);
+    assert_eq!(peer_chain.blocks().len(), 1);
+
+    Ok(())
+}
+
+/// Account state at a block is provable against that block's header.
+#[test]
+fn account_proof_pass() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    let genesis_id = genesis.id();
+    blockchain.add_block(genesis)?;
+
+    let block = build_block(&users, &mut blockchain, &transfer_txns(&users, 3))?;
+    let block_id = block.id();
+    let header = block.header().clone();
+    blockchain.add_block(block)?;
+
+    // existing account, with its balance after the block
+    let recv_id = users.recv.pbkey().into();
+    let proof = blockchain.account_proof(&block_id, &recv_id).unwrap();
+    assert!(proof.verify(&header));
+    assert_eq!(proof.balance(), Some(1 + 2 + 3));
+
+    // account that doesn't exist
+    let test1_id = users.test1.pbkey().into();
+    let proof = blockchain.account_proof(&block_id, &test1_id).unwrap();
+    assert!(proof.verify(&header));
+    assert_eq!(proof.balance(), None);
+
+    // `recv` did not exist before the block
+    let proof = blockchain.account_proof(&genesis_id, &recv_id).unwrap();
+    assert_eq!(proof.balance(), None);
+
+    Ok(())
+}
+
+/// A proof only holds against the header of the block it was made for.
+#[test]
+fn account_proof_fail() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    let genesis_id = genesis.id();
+    blockchain.add_block(genesis)?;
+
+    let block = build_block(&users, &mut blockchain, &transfer_txns(&users, 3))?;
+    let header = block.header().clone();
+    blockchain.add_block(block)?;
+
+    let send_id = users.send.pbkey().into();
+    // state before the block, checked against the new header
+    let proof = blockchain.account_proof(&genesis_id, &send_id).unwrap();
+    assert!(!proof.verify(&header));
+    // unknown block
+    let unknown_id = BlockId::from_bytes([9u8; 64]);
+    assert!(matches!(
+        blockchain.account_proof(&unknown_id, &send_id),
+        Err(BlockchainError::UnknownBlock(id)) if id == unknown_id
+    ));
+
+    Ok(())
+}
+
+/// Block building stops at the gas limit, before executing the txn that would exceed it.
+#[test]
+fn add_txn_to_blocks_fail_over_gas_limit() -> Result<()> {
+    let users = init_users();
+    let params = ChainParams::new(
+        TRANSFER_GAS,
+        2 * TRANSFER_GAS,
+        DEFAULT_BLOCK_BYTE_LIMIT,
+        DEFAULT_GAS_PRICE,
+    );
+    let mut blockchain = Blockchain::with_params(params);
+    init_account_map(&mut blockchain);
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    blockchain.add_block(genesis)?;
+
+    let err = build_block(&users, &mut blockchain, &transfer_txns(&users, 3)).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockError>(),
+        Some(BlockError::OverCapacity { gas, .. }) if *gas == 3 * TRANSFER_GAS
+    ));
+    // only 2 of the 3 transfers (amts 1, 2, 3) were executed
+    let bal_recv = blockchain
+        .accounts()
+        .acct_balance(&users.recv.pbkey().into());
+    assert!((1 + 2..=2 + 3).contains(&bal_recv), "{bal_recv}");
+
+    Ok(())
+}
+
+/// Gas and fees that overflow are rejected, rather than wrapping around the chain's limits.
+#[test]
+fn add_txn_to_blocks_fail_overflow() -> Result<()> {
+    let users = init_users();
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    let init_chain = |params| -> Result<Blockchain> {
+        let mut blockchain = Blockchain::with_params(params);
+        init_account_map(&mut blockchain);
+        blockchain.add_block(genesis.clone())?;
+        Ok(blockchain)
+    };
+
+    // 2 transfers use more than u64::MAX gas
+    let transfer_gas = u64::MAX / 2 + 1;
+    let params = ChainParams::new(
+        transfer_gas,
+        u64::MAX,
+        DEFAULT_BLOCK_BYTE_LIMIT,
+        DEFAULT_GAS_PRICE,
+    );
+    let mut blockchain = init_chain(params.clone())?;
+    let err = build_block(&users, &mut blockchain, &transfer_txns(&users, 2)).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockError>(),
+        Some(BlockError::GasOverflow)
+    ));
+    assert!(matches!(
+        params.fee(&TxnType::Transfer),
+        Ok(DEFAULT_GAS_PRICE)
+    ));
+
+    // gas x gas price is over u128::MAX
+    let params = ChainParams::new(
+        TRANSFER_GAS,
+        DEFAULT_BLOCK_GAS_LIMIT,
+        DEFAULT_BLOCK_BYTE_LIMIT,
+        u128::MAX,
+    );
+    let mut blockchain = init_chain(params)?;
+    let err = build_block(&users, &mut blockchain, &transfer_txns(&users, 1)).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<TxnError>(),
+        Some(TxnError::FeeOverflow)
+    ));
+
+    Ok(())
+}
+
+/// Blocks over the byte-size limit are rejected.
+#[test]
+fn add_block_fail_over_byte_limit() -> Result<()> {
+    let (users, mut leader_chain) = init_blockchain_and_accounts();
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    leader_chain.add_block(genesis.clone())?;
+    let block = build_block(&users, &mut leader_chain, &transfer_txns(&users, 3))?;
+    assert_eq!(leader_chain.params().block_gas(&block)?, 3 * TRANSFER_GAS);
+
+    // a peer with a lower size limit
+    let params = ChainParams::new(
+        TRANSFER_GAS,
+        DEFAULT_BLOCK_GAS_LIMIT,
+        block.size() - 1,
+        DEFAULT_GAS_PRICE,
+    );
+    let mut peer_chain = Blockchain::with_params(params);
+    init_account_map(&mut peer_chain);
+    peer_chain.add_block(genesis)?;
+
+    let err = peer_chain.replay_block(block.clone()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockError>(),
+        Some(BlockError::OverCapacity { bytes, .. }) if *bytes == block.size()
+    ));
+    assert!(peer_chain.add_block(block).is_err());
+
+    Ok(())
+}
+
+/// Txns on the chain are found by id, with their block, position and confirmation depth.
+#[test]
+fn find_txn_pass() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    blockchain.add_block(Block::new_genesis(ChainId::default(), &users.main.wallet))?;
+
+    let block = build_block(&users, &mut blockchain, &transfer_txns(&users, 3))?;
+    let block_id = block.id();
+    blockchain.add_block(block.clone())?;
+
+    for (key, txn) in block.txns() {
+        let found = blockchain.find_txn(txn.id())?.expect("Txn not indexed.");
+        let proof = block.txn_proof(txn.id()).unwrap();
+        assert_eq!(found.txn.id_key(), *key);
+        assert_eq!(found.location.block_id, block_id);
+        assert_eq!(found.location.height, 2);
+        assert_eq!(found.location.position, proof.position());
+        assert_eq!(found.confirmations, 1);
+        assert!(blockchain.receipt(txn.id()).is_ok());
+    }
+
+    // confirmations grow as blocks are appended on top
+    let mut next_block = create_block(&users.main, &blockchain);
+    next_block.sign(&users.main.wallet);
+    blockchain.add_block(next_block)?;
+    let txn = block.txns().values().next().unwrap();
+    assert_eq!(blockchain.find_txn(txn.id())?.unwrap().confirmations, 2);
+
+    // txns not on the chain are not found
+    let unknown = Txn::new_signed(
+        &users.send.wallet,
+        ChainId::default(),
+        users.recv.pbkey(),
+        1,
+        TxnType::Transfer,
+    );
+    assert!(blockchain.find_txn(unknown.id())?.is_none());
+
+    Ok(())
+}
+
+/// Rolling back drops the blocks above a height from the index and restores the state,
+/// so another fork can be built on top.
+#[test]
+fn rollback_to_pass() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    blockchain.add_block(Block::new_genesis(ChainId::default(), &users.main.wallet))?;
+    let state_root_genesis = blockchain.accounts().state_root();
+
+    let block = build_block(&users, &mut blockchain, &transfer_txns(&users, 3))?;
+    blockchain.add_block(block.clone())?;
+    let mut next_block = create_block(&users.main, &blockchain);
+    next_block.sign(&users.main.wallet);
+    blockchain.add_block(next_block)?;
+    assert_eq!(blockchain.height(), 3);
+
+    let removed = blockchain.rollback_to(1)?;
+    assert_eq!(removed.len(), 2);
+    assert_eq!(removed[1].id(), block.id());
+    assert_eq!(blockchain.height(), 1);
+    assert_eq!(blockchain.blocks().len(), 1);
+    assert_eq!(blockchain.accounts().state_root(), state_root_genesis);
+    for txn in block.txns().values() {
+        assert!(blockchain.find_txn(txn.id())?.is_none());
+        assert!(matches!(
+            blockchain.receipt(txn.id()),
+            Err(BlockchainError::UnknownTxn(_))
+        ));
+    }
+
+    // build a fork at the same height
+    let fork_block = build_block(&users, &mut blockchain, &transfer_txns(&users, 2))?;
+    let fork_block_id = fork_block.id();
+    blockchain.add_block(fork_block.clone())?;
+    assert_eq!(blockchain.block_at(2).unwrap().id(), fork_block_id);
+    for txn in fork_block.txns().values() {
+        let found = blockchain.find_txn(txn.id())?.unwrap();
+        assert_eq!(found.location.block_id, fork_block_id);
+        assert_eq!(found.confirmations, 1);
+    }
+
+    Ok(())
+}
+
+/// Rolling back to a height with no block fails.
+#[test]
+fn rollback_to_fail_unknown_height() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    blockchain.add_block(Block::new_genesis(ChainId::default(), &users.main.wallet))?;
+
+    let err = blockchain.rollback_to(2).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockchainError>(),
+        Some(BlockchainError::UnknownHeight(2))
+    ));
+
+    Ok(())
+}
+
+/// A second block at an occupied height is rejected until the chain is rolled back.
+#[test]
+fn add_block_fail_conflicting_block() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    blockchain.add_block(genesis.clone())?;
+
+    let mut block = create_block_from_last(&users.main, &genesis);
+    block.sign(&users.main.wallet);
+    let mut other_block = create_block_from_last(&users.send, &genesis);
+    other_block.sign(&users.send.wallet);
+    blockchain.add_block(block.clone())?;
+
+    let err = blockchain.add_block(other_block.clone()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockchainError>(),
+        Some(BlockchainError::ConflictingBlock(2))
+    ));
+    // re-adding the same block is fine
+    blockchain.add_block(block)?;
+
+    blockchain.rollback_to(1)?;
+    blockchain.add_block(other_block.clone())?;
+    assert_eq!(blockchain.last_block().id(), other_block.id());
+
+    Ok(())
+}
+
+/// An account's history lists the txns it sent or received, by height, across pages.
+#[test]
+fn account_history_pass() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    blockchain.add_block(Block::new_genesis(ChainId::default(), &users.main.wallet))?;
+    let block = build_block(&users, &mut blockchain, &transfer_txns(&users, 3))?;
+    blockchain.add_block(block)?;
+
+    // blocks already on the chain are indexed when the index is enabled
+    blockchain.enable_address_index();
+    let block = build_block(&users, &mut blockchain, &transfer_txns(&users, 2))?;
+    blockchain.add_block(block)?;
+
+    let recv_id = AccountId::from(users.recv.pbkey());
+    let index = blockchain.address_index().unwrap();
+    assert_eq!(index.txn_ct(&recv_id), 5);
+    assert_eq!(index.txn_ct(&users.send.pbkey().into()), 5);
+    assert_eq!(index.txn_ct(&users.main.pbkey().into()), 0);
+
+    // page through, 2 at a time
+    let mut query = HistoryQuery::new(2);
+    let mut heights = vec![];
+    loop {
+        let page = blockchain.account_history(&recv_id, &query)?;
+        assert!(page.txns.len() <= 2);
+        heights.extend(page.txns.iter().map(|found| found.location.height));
+        match page.next {
+            Some(cursor) => query = query.after(cursor),
+            None => break,
+        }
+    }
+    assert_eq!(heights, vec![2, 2, 2, 3, 3]);
+
+    // an empty page would end paging early - a limit of 0 returns one txn
+    let page = blockchain.account_history(&recv_id, &HistoryQuery::new(0))?;
+    assert_eq!(page.txns.len(), 1);
+    assert!(page.next.is_some());
+
+    let page =
+        blockchain.account_history(&recv_id, &HistoryQuery::new(10).txn_type(TxnType::Transfer))?;
+    assert_eq!(page.txns.len(), 5);
+    assert!(page.next.is_none());
+
+    // rolled back txns leave the history
+    blockchain.rollback_to(2)?;
+    let page = blockchain.account_history(&recv_id, &HistoryQuery::new(10))?;
+    assert_eq!(page.txns.len(), 3);
+
+    Ok(())
+}
+
+/// Account history can't be queried unless the address index is enabled.
+#[test]
+fn account_history_fail_disabled() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    blockchain.add_block(Block::new_genesis(ChainId::default(), &users.main.wallet))?;
+
+    let err = blockchain
+        .account_history(&users.recv.pbkey().into(), &HistoryQuery::new(10))
+        .unwrap_err();
+    assert!(matches!(err, BlockchainError::AddressIndexDisabled));
+
+    Ok(())
+}
+
+/// Txns and blocks for another chain are rejected.
+#[test]
+fn add_block_fail_wrong_chain() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    blockchain.add_block(genesis.clone())?;
+    let testnet: ChainId = "posbc-testnet".parse().unwrap();
+
+    // txn
+    let txn = Txn::new_signed(
+        &users.send.wallet,
+        testnet.clone(),
+        users.recv.pbkey(),
+        1,
+        TxnType::Transfer,
+    );
+    let err = blockchain.add_txn_to_block(&txn).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<TxnError>(),
+        Some(TxnError::WrongChainId { expected, found }) if expected == blockchain.chain_id() && found == &testnet
+    ));
+
+    // block
+    let mut block = Block::new(
+        testnet.clone(),
+        BlockTxnMap::new(),
+        users.main.pbkey(),
+        genesis.id(),
+        *genesis.blockheight(),
+    );
+    block.sign(&users.main.wallet);
+    let err = blockchain.add_block(block).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockError>(),
+        Some(BlockError::WrongChainId { .. })
+    ));
+
+    // block on this chain holding a txn for another chain
+    let mut txns = BlockTxnMap::new();
+    txns.insert(txn.id_key(), txn);
+    let mut block = Block::new(
+        ChainId::default(),
+        txns,
+        users.main.pbkey(),
+        genesis.id(),
+        *genesis.blockheight(),
+    );
+    block.sign(&users.main.wallet);
+    let err = blockchain.replay_block(block).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockError>(),
+        Some(BlockError::WrongChainId { .. })
+    ));
+    assert_eq!(blockchain.height(), 1);
+
+    Ok(())
+}
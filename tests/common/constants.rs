@@ -5,3 +5,7 @@ pub const KEYPAIR_RECV: &str = "tests/keys/recv_ed25519.json";
 pub const KEYPAIR_TEST1: &str = "tests/keys/test1_ed25519.json";
 pub const KEYPAIR_TEST2: &str = "tests/keys/test2_ed25519.json";
 pub const KEYPAIR_TEST3: &str = "tests/keys/test3_ed25519.json";
+
+// genesis
+pub const GENESIS_CHAIN_ID: &str = "posbc-test";
+pub const GENESIS_TIMESTAMP: u64 = 1_700_000_000_000;
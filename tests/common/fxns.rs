@@ -6,12 +6,22 @@ use posbc::{
     ledger::{
         block::{block_id::BlockId, types::BlockTxnMap, Block},
         blockchain::Blockchain,
+        chain_params::ChainParams,
+        general::Result,
+        genesis::{GenesisAllocation, GenesisSpec, GenesisValidator},
+        txn::{Txn, TxnType},
+        txn_pool::{TxnMap, TxnPool},
     },
 };
+use std::{fs, path::PathBuf};
 // test
-use super::{init_users, UserInfo, UsersInfo};
+use super::{
+    constants::{GENESIS_CHAIN_ID, GENESIS_TIMESTAMP},
+    init_users, UserInfo, UsersInfo,
+};
 
 /// ### Creates an empty block using the leader and previous block.
+/// The block is for the same chain as the previous block.\
 /// Does not populate with transactions.
 pub fn create_block_from_last(leader: &UserInfo, prev_block: &Block) -> Block {
     let prev_block_id: BlockId = prev_block.id();
@@ -19,6 +29,7 @@ pub fn create_block_from_last(leader: &UserInfo, prev_block: &Block) -> Block {
     let leader = leader.pbkey();
 
     Block::new(
+        prev_block.chain_id().clone(),
         BlockTxnMap::new(),
         leader,
         prev_block_id,
@@ -31,16 +42,7 @@ pub fn create_block_from_last(leader: &UserInfo, prev_block: &Block) -> Block {
 /// Does not populate with transactions.
 pub fn create_block(leader: &UserInfo, blockchain: &Blockchain) -> Block {
     // assuming blockchain was created properly
-    let prev_block = get_last_block(blockchain).expect("Error getting last block in blockchain.");
-
-    create_block_from_last(leader, prev_block)
-}
-
-/// ### Get most recent block of the given blockchain.
-///
-/// @todo make this a getter method on `Blockchain`
-fn get_last_block(blockchain: &Blockchain) -> Option<&Block> {
-    blockchain.blocks().values().next_back()
+    create_block_from_last(leader, blockchain.last_block())
 }
 
 /// ### Initialize account map.
@@ -72,3 +74,80 @@ pub fn get_first_acct(accounts: &Accounts) -> Option<&Account> {
 
     accts.values().next()
 }
+
+/// ### Creates a genesis spec for chain `GENESIS_CHAIN_ID`.
+/// Allocates 1000 to `send` and 50 to `main`; validators are `main` (power 2) and `test1` (power 1).
+pub fn init_genesis_spec(users: &UsersInfo) -> GenesisSpec {
+    GenesisSpec::new(
+        GENESIS_CHAIN_ID.parse().unwrap(),
+        GENESIS_TIMESTAMP,
+        vec![
+            GenesisAllocation {
+                acct_id: users.send.pbkey().into(),
+                balance: 1000,
+            },
+            GenesisAllocation {
+                acct_id: users.main.pbkey().into(),
+                balance: 50,
+            },
+        ],
+        vec![
+            GenesisValidator {
+                pbkey: users.main.pbkey(),
+                power: 2,
+            },
+            GenesisValidator {
+                pbkey: users.test1.pbkey(),
+                power: 1,
+            },
+        ],
+        ChainParams::default(),
+    )
+}
+/// ### Creates an empty directory under the system temp dir, unique to this process and `name`.
+/// Removes whatever a previous run left there.
+pub fn init_temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("posbc-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+/// ### Adds a block with one transfer of `amt` from `send` to `recv`, led by `main`.
+/// Returns the transfer txn.
+pub fn add_transfer_block(
+    blockchain: &mut Blockchain,
+    users: &UsersInfo,
+    recv: &UserInfo,
+    amt: u128,
+) -> Result<Txn> {
+    let txn = Txn::new_signed(
+        &users.send.wallet,
+        blockchain.chain_id().clone(),
+        recv.pbkey(),
+        amt,
+        TxnType::Transfer,
+    );
+    let block = build_block(users, blockchain, std::slice::from_ref(&txn))?;
+    blockchain.add_block(block)?;
+
+    Ok(txn)
+}
+
+/// ### Builds a block on the chain's tip executing the given txns, led and signed by `main`.
+/// The block is not added to the chain.
+pub fn build_block(users: &UsersInfo, blockchain: &mut Blockchain, txns: &[Txn]) -> Result<Block> {
+    let mut txn_pool = TxnPool::new();
+    let mut txn_map = TxnMap::new();
+    for txn in txns {
+        txn_pool.add_txn(txn.clone())?;
+        txn_map.insert(txn.id_key(), txn.clone());
+    }
+
+    let mut block = create_block_from_last(&users.main, blockchain.last_block());
+    blockchain.add_txn_to_blocks(&txn_map, &mut block, &mut txn_pool)?;
+    block.sign(&users.main.wallet);
+
+    Ok(block)
+}
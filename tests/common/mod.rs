@@ -2,6 +2,7 @@ pub mod constants;
 pub mod fxns;
 
 use posbc::ledger::{
+    chain_id::ChainId,
     general::{PbKey, KP},
     txn::{Txn, TxnType},
     wallet::Wallet,
@@ -76,7 +77,13 @@ pub fn create_transfer_txn_default() -> Txn {
     let (send, recv) = init_send_recv();
 
     // turn the raw txn into message
-    let txn = Txn::new(send.pbkey(), recv.pbkey(), 100, TxnType::Transfer);
+    let txn = Txn::new(
+        ChainId::default(),
+        send.pbkey(),
+        recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
 
     txn
 }
@@ -87,6 +94,7 @@ pub fn create_transfer_txn(amt_to_transfer: u128) -> Txn {
 
     // turn the raw txn into message
     let txn = Txn::new(
+        ChainId::default(),
         send.pbkey(),
         recv.pbkey(),
         amt_to_transfer,
@@ -99,6 +107,7 @@ pub fn create_transfer_txn(amt_to_transfer: u128) -> Txn {
 pub fn create_transfer_txn_manual(send: UserInfo, recv: UserInfo, amt_to_transfer: u128) -> Txn {
     // turn the raw txn into message
     let txn = Txn::new(
+        ChainId::default(),
         send.pbkey(),
         recv.pbkey(),
         amt_to_transfer,
@@ -0,0 +1,188 @@
+use posbc::{
+    ledger::{
+        block::{block_header::BlockHeader, block_id::BlockId, types::BlockTxnMap, Block},
+        chain_id::ChainId,
+        general::PbKey,
+        txn::{constants::TXN_SIGNATURE_CTX, txn_header::TxnHeader, Txn, TxnType},
+        validator_set::ValidatorSet,
+    },
+    utils::{encoding::EncodingError, hash::BlakeHash, merkle::MerkleTree},
+};
+
+// Golden vectors: fixed inputs -> fixed bytes/ids.
+// Any change to these values is a consensus-breaking change to the encoding.
+const GOLDEN_TXN_HEADER_HEX: &str = concat!(
+    "01",                                                               // version
+    "0000000c",                                                         // chain_id len
+    "706f7362632d6465766e6574",                                         // chain_id ("posbc-devnet")
+    "000000000000000000000000000003e8",                                 // amt
+    "00000020",                                                         // pbkey_send len
+    "0101010101010101010101010101010101010101010101010101010101010101", // pbkey_send
+    "00000020",                                                         // pbkey_recv len
+    "0202020202020202020202020202020202020202020202020202020202020202", // pbkey_recv
+    "0000018bcfe56800",                                                 // system_time
+    "01",                                                               // txn_type
+);
+const GOLDEN_TXN_ID_HEX: &str = concat!(
+    "553f7d4b110bccadb28baa6cf82a4b921a473fbb08928bb11d262ff6650cf76b",
+    "e3a8bdb85471aef01a7e6dabace66b07bdceb4fa321018f76795d0d8bc6506f6",
+);
+const GOLDEN_TXN_SIGNATURE_CTX_HEX: &str =
+    "b5a04fedba1412415a667980c48dd2e7d1a15509c8c6221cabd4fe399918abf4";
+const GOLDEN_BLOCK_HEADER_HEX: &str = concat!(
+    "01",       // version
+    "0000000c", // chain_id len
+    "706f7362632d6465766e6574",
+    "00000020", // txn_root len
+    "400cdc8af38b6026d979a14e95ef80ff40d70ea841b64f9ef85288c80d3b5378",
+    "00000020", // state_root len
+    "0505050505050505050505050505050505050505050505050505050505050505",
+    "00000020", // receipts_root len
+    "0606060606060606060606060606060606060606060606060606060606060606",
+    "00",       // validator_set_hash: none
+    "00000020", // leader len
+    "0303030303030303030303030303030303030303030303030303030303030303",
+    "00000040", // prev_block_id len
+    "0404040404040404040404040404040404040404040404040404040404040404",
+    "0404040404040404040404040404040404040404040404040404040404040404",
+    "00000000000000000000000000000007", // blockheight
+    "0000018bcfe569f4",                 // system_time
+);
+const GOLDEN_BLOCK_ID_HEX: &str = concat!(
+    "4b50da8f129a6393639b794ac5520d0c5de48163bf547dd51bd1b3e9e505284b",
+    "76e7352b5ddf825e475839836e5224b9482c9ddd48aed56735d53e21a8a56363",
+);
+
+fn golden_txn_header() -> TxnHeader {
+    TxnHeader::new(
+        ChainId::default(),
+        1_000,
+        PbKey([1u8; 32]),
+        PbKey([2u8; 32]),
+        1_700_000_000_000,
+        TxnType::Transfer,
+    )
+}
+
+fn golden_block_header() -> BlockHeader {
+    let txn = Txn::from_header(golden_txn_header());
+    let txn_root = MerkleTree::new([txn.id().0]).root();
+
+    BlockHeader::new_at(
+        ChainId::default(),
+        txn_root,
+        BlakeHash::from_bytes([5u8; 32]),
+        BlakeHash::from_bytes([6u8; 32]),
+        PbKey([3u8; 32]),
+        BlockId::from_bytes([4u8; 64]),
+        6,
+        1_700_000_000_500,
+    )
+}
+
+#[test]
+fn txn_header_golden_vector_pass() {
+    let header = golden_txn_header();
+
+    assert_eq!(hex::encode(header.serialize()), GOLDEN_TXN_HEADER_HEX);
+    assert_eq!(
+        String::from(Txn::from_header(header).id()),
+        GOLDEN_TXN_ID_HEX
+    );
+}
+
+/// Signing context of the default chain - `blake3(TXN_SIGNATURE_CTX || u32 BE len || "posbc-devnet")`.
+#[test]
+fn chain_signing_ctx_golden_vector_pass() {
+    let ctx = ChainId::default().signing_ctx(TXN_SIGNATURE_CTX);
+    assert_eq!(hex::encode(ctx), GOLDEN_TXN_SIGNATURE_CTX_HEX);
+
+    // every chain signs under its own context
+    let other: ChainId = "posbc-testnet".parse().unwrap();
+    assert_ne!(other.signing_ctx(TXN_SIGNATURE_CTX), ctx);
+}
+
+#[test]
+fn chain_id_decode_fail() {
+    assert!("".parse::<ChainId>().is_err());
+    assert!("posbc devnet".parse::<ChainId>().is_err());
+    assert!("x".repeat(65).parse::<ChainId>().is_err());
+    assert!("posbc-devnet_1.0".parse::<ChainId>().is_ok());
+
+    // chain ids in headers are checked when decoding
+    let mut bytes = hex::decode(GOLDEN_TXN_HEADER_HEX).unwrap();
+    bytes[5] = b' ';
+    assert!(TxnHeader::deserialize(&bytes).is_err());
+}
+
+#[test]
+fn block_header_golden_vector_pass() {
+    let header = golden_block_header();
+
+    assert_eq!(hex::encode(header.serialize()), GOLDEN_BLOCK_HEADER_HEX);
+    assert_eq!(header.calc_id().to_str(), GOLDEN_BLOCK_ID_HEX);
+}
+
+#[test]
+fn txn_header_round_trip_pass() {
+    let header = golden_txn_header();
+    let decoded = TxnHeader::deserialize(&header.serialize()).unwrap();
+
+    assert_eq!(decoded, header);
+}
+
+#[test]
+fn block_header_round_trip_pass() {
+    let header = golden_block_header();
+    assert_eq!(
+        BlockHeader::deserialize(&header.serialize()).unwrap(),
+        header
+    );
+
+    // with a validator set hand-over
+    let mut block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        PbKey([3u8; 32]),
+        *header.prev_block_id(),
+        6,
+    );
+    block.set_next_validator_set(&ValidatorSet::new([(PbKey([3u8; 32]), 1)]));
+    let header = block.header();
+    assert_eq!(
+        &BlockHeader::deserialize(&header.serialize()).unwrap(),
+        header
+    );
+}
+
+#[test]
+fn txn_header_decode_fail() {
+    let bytes = hex::decode(GOLDEN_TXN_HEADER_HEX).unwrap();
+
+    // unknown version
+    let mut bad_version = bytes.clone();
+    bad_version[0] = 2;
+    assert_eq!(
+        TxnHeader::deserialize(&bad_version),
+        Err(EncodingError::UnsupportedVersion {
+            expected: 1,
+            found: 2
+        })
+    );
+    // truncated
+    assert_eq!(
+        TxnHeader::deserialize(&bytes[..bytes.len() - 1]),
+        Err(EncodingError::UnexpectedEnd)
+    );
+    // trailing data
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        TxnHeader::deserialize(&trailing),
+        Err(EncodingError::TrailingBytes(1))
+    );
+    // unknown txn type
+    let mut bad_type = bytes;
+    *bad_type.last_mut().unwrap() = 9;
+    assert!(TxnHeader::deserialize(&bad_type).is_err());
+}
@@ -0,0 +1,180 @@
+use posbc::{
+    accounts::account::AccountId,
+    ledger::{
+        blockchain::Blockchain,
+        chain_id::ChainId,
+        chain_params::{
+            ChainParams, DEFAULT_BLOCK_BYTE_LIMIT, DEFAULT_BLOCK_GAS_LIMIT, MAX_GAS_PRICE,
+            TRANSFER_GAS,
+        },
+        general::Result,
+        genesis::{error::GenesisError, GenesisSpec},
+        txn::{Txn, TxnType},
+        txn_pool::{TxnMap, TxnPool},
+    },
+    utils::merkle::empty_root,
+};
+use std::fs;
+// test
+use crate::common::{
+    constants::GENESIS_TIMESTAMP,
+    fxns::{create_block_from_last, init_genesis_spec},
+    init_users,
+};
+
+/// A genesis spec survives a round trip through a file, with keys written as addresses.
+#[test]
+fn genesis_spec_file_pass() -> Result<()> {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+
+    let filepath = std::env::temp_dir().join(format!("genesis-{}.json", std::process::id()));
+    fs::write(&filepath, spec.to_json()?)?;
+    let loaded = GenesisSpec::from_file(&filepath)?;
+    fs::remove_file(&filepath)?;
+    assert_eq!(loaded, spec);
+
+    let json: serde_json::Value = serde_json::from_str(&spec.to_json()?)?;
+    assert_eq!(json["chain_id"], "posbc-test");
+    assert_eq!(
+        json["validators"][0]["pbkey"],
+        users.main.pbkey().to_string()
+    );
+
+    Ok(())
+}
+
+/// Every node builds the same genesis block and initial state from the same spec.
+#[test]
+fn genesis_deterministic_pass() -> Result<()> {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let chain_a = Blockchain::from_genesis(&spec)?;
+    let chain_b = Blockchain::from_genesis(&GenesisSpec::from_json(&spec.to_json()?)?)?;
+
+    let genesis = chain_a.last_block();
+    assert_eq!(chain_a.chain_id(), &"posbc-test".parse::<ChainId>()?);
+    assert_eq!(genesis.chain_id(), chain_a.chain_id());
+    assert_eq!(genesis.id(), chain_b.last_block().id());
+    assert_eq!(*genesis.blockheight(), 1);
+    assert_eq!(*genesis.header().system_time(), GENESIS_TIMESTAMP);
+    assert!(chain_a.is_genesis_block(chain_b.last_block()));
+
+    // the block commits to the allocations and the validators
+    assert_eq!(genesis.state_root(), &chain_a.accounts().state_root());
+    assert_ne!(genesis.state_root(), &empty_root());
+    assert_eq!(
+        genesis.validator_set_hash(),
+        Some(&spec.validator_set().hash())
+    );
+    let send_id = AccountId::from(users.send.pbkey());
+    assert_eq!(chain_a.accounts().acct_balance(&send_id), 1000);
+    assert_eq!(
+        chain_a.accounts().acct_balance(&users.main.pbkey().into()),
+        50
+    );
+
+    // a different spec gives a different genesis
+    let mut json: serde_json::Value = serde_json::from_str(&spec.to_json()?)?;
+    json["timestamp"] = (GENESIS_TIMESTAMP + 1).into();
+    let other_spec = GenesisSpec::from_json(&json.to_string())?;
+    assert_ne!(other_spec.block().id(), genesis.id());
+
+    Ok(())
+}
+
+/// Allocated balances can be spent without setting up accounts by hand.
+#[test]
+fn genesis_transfer_pass() -> Result<()> {
+    let users = init_users();
+    let mut blockchain = Blockchain::from_genesis(&init_genesis_spec(&users))?;
+
+    let txn = Txn::new_signed(
+        &users.send.wallet,
+        blockchain.chain_id().clone(),
+        users.recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
+    let mut txn_pool = TxnPool::new();
+    txn_pool.add_txn(txn.clone())?;
+    let mut txns = TxnMap::new();
+    txns.insert(txn.id_key(), txn);
+
+    let mut block = create_block_from_last(&users.main, blockchain.last_block());
+    blockchain.add_txn_to_blocks(&txns, &mut block, &mut txn_pool)?;
+    block.sign(&users.main.wallet);
+    blockchain.add_block(block)?;
+
+    let accounts = blockchain.accounts();
+    assert_eq!(accounts.acct_balance(&users.send.pbkey().into()), 900);
+    assert_eq!(accounts.acct_balance(&users.recv.pbkey().into()), 100);
+    assert_eq!(blockchain.height(), 2);
+
+    Ok(())
+}
+
+/// Invalid specs are rejected when loaded.
+#[test]
+fn genesis_spec_fail() -> Result<()> {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let json: serde_json::Value = serde_json::from_str(&spec.to_json()?)?;
+
+    let mut no_validators = json.clone();
+    no_validators["validators"] = serde_json::json!([]);
+    let err = GenesisSpec::from_json(&no_validators.to_string()).unwrap_err();
+    assert!(matches!(err, GenesisError::NoValidators));
+
+    let mut duplicate = json.clone();
+    duplicate["allocations"][1] = duplicate["allocations"][0].clone();
+    let err = GenesisSpec::from_json(&duplicate.to_string()).unwrap_err();
+    assert!(
+        matches!(err, GenesisError::DuplicateAllocation(acct_id) if acct_id == AccountId::from(users.send.pbkey()))
+    );
+
+    let mut zero_power = json.clone();
+    zero_power["validators"][1]["power"] = 0.into();
+    let err = GenesisSpec::from_json(&zero_power.to_string()).unwrap_err();
+    assert!(matches!(err, GenesisError::ZeroPower(pbkey) if pbkey == users.test1.pbkey()));
+
+    let mut bad_address = json.clone();
+    bad_address["allocations"][0]["acct_id"] = "not-an-address".into();
+    let err = GenesisSpec::from_json(&bad_address.to_string()).unwrap_err();
+    assert!(matches!(err, GenesisError::Parse(_)));
+
+    for (field, value) in [
+        ("block_gas_limit", 0u64),
+        ("block_byte_limit", 0),
+        ("transfer_gas", DEFAULT_BLOCK_GAS_LIMIT + 1),
+    ] {
+        let mut invalid_params = json.clone();
+        invalid_params["params"][field] = value.into();
+        let err = GenesisSpec::from_json(&invalid_params.to_string()).unwrap_err();
+        assert!(matches!(err, GenesisError::InvalidParams(_)), "{field}");
+    }
+    let params = ChainParams::new(
+        TRANSFER_GAS,
+        DEFAULT_BLOCK_GAS_LIMIT,
+        DEFAULT_BLOCK_BYTE_LIMIT,
+        MAX_GAS_PRICE + 1,
+    );
+    let expensive = GenesisSpec::new(
+        spec.chain_id().clone(),
+        spec.timestamp(),
+        spec.allocations().to_vec(),
+        spec.validators().to_vec(),
+        params,
+    );
+    assert!(matches!(
+        expensive.validate().unwrap_err(),
+        GenesisError::InvalidParams(_)
+    ));
+
+    let mut no_chain_id = json;
+    no_chain_id["chain_id"] = "".into();
+    let err = GenesisSpec::from_json(&no_chain_id.to_string()).unwrap_err();
+    assert!(matches!(err, GenesisError::Parse(_)));
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use posbc::ledger::{
+    block::{
+        block_id::BlockId, error::BlockError, finality::FinalityCertificate, types::BlockTxnMap,
+        Block,
+    },
+    blockchain::Blockchain,
+    chain_id::ChainId,
+    general::Result,
+    light_client::{error::LightClientError, light_block::LightBlock, LightClient},
+    txn::{Txn, TxnType},
+    txn_pool::{TxnMap, TxnPool},
+    validator_set::ValidatorSet,
+};
+// test
+use crate::common::{
+    fxns::{create_block_from_last, init_blockchain_and_accounts},
+    UserInfo, UsersInfo,
+};
+
+fn init_validators(users: &UsersInfo) -> ValidatorSet {
+    ValidatorSet::new([
+        (users.main.pbkey(), 1),
+        (users.test1.pbkey(), 1),
+        (users.test2.pbkey(), 1),
+        (users.test3.pbkey(), 1),
+    ])
+}
+
+/// ### Create the full chain with its genesis block, and a light client trusting that genesis.
+fn init_chains() -> (UsersInfo, Blockchain, Block, LightClient) {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    let genesis = Block::new_genesis(ChainId::default(), &users.main.wallet);
+    blockchain.add_block(genesis.clone()).unwrap();
+    let light_client = LightClient::new(genesis.header().clone(), init_validators(&users)).unwrap();
+
+    (users, blockchain, genesis, light_client)
+}
+
+/// ### Collect finality votes for a block and package it for the light client.
+fn finalize(block: &Block, voters: &[&UserInfo]) -> LightBlock {
+    let mut finality = FinalityCertificate::new(block.id());
+    for voter in voters {
+        finality.sign(&voter.wallet);
+    }
+
+    LightBlock::new(block.header().clone(), block.signature(), finality)
+}
+
+#[test]
+fn verify_light_block_pass() -> Result<()> {
+    let (users, mut blockchain, genesis, mut light_client) = init_chains();
+
+    // full node builds a block with a transfer
+    let txn = Txn::new_signed(
+        &users.send.wallet,
+        ChainId::default(),
+        users.recv.pbkey(),
+        10,
+        TxnType::Transfer,
+    );
+    let mut txn_pool = TxnPool::new();
+    txn_pool.add_txn(txn.clone())?;
+    let txns = TxnMap::from([(txn.id_key(), txn.clone())]);
+    let mut block = create_block_from_last(&users.main, &genesis);
+    blockchain.add_txn_to_blocks(&txns, &mut block, &mut txn_pool)?;
+    block.sign(&users.main.wallet);
+    blockchain.add_block(block.clone())?;
+
+    // 3 of 4 validators finalize it
+    let light_block = finalize(&block, &[&users.main, &users.test1, &users.test2]);
+    let block_id = light_client.verify_light_block(light_block, None)?;
+    assert_eq!(block_id, block.id());
+    assert_eq!(light_client.latest_header(), block.header());
+
+    // txn and account queries are answered with proofs
+    let txn_proof = block.txn_proof(txn.id()).unwrap();
+    assert!(light_client.verify_txn(&block_id, &txn_proof));
+    let acct_proof = blockchain
+        .account_proof(&block_id, &users.recv.pbkey().into())
+        .unwrap();
+    assert!(light_client.verify_account(&block_id, &acct_proof));
+    assert_eq!(acct_proof.balance(), Some(10));
+    // proofs don't hold against unverified blocks
+    assert!(!light_client.verify_txn(&BlockId::from_bytes([9u8; 64]), &txn_proof));
+
+    Ok(())
+}
+
+#[test]
+fn verify_light_block_fail() {
+    let (users, _blockchain, genesis, mut light_client) = init_chains();
+
+    // not enough voting power: 2 of 4
+    let mut block = create_block_from_last(&users.main, &genesis);
+    block.sign(&users.main.wallet);
+    let light_block = finalize(&block, &[&users.main, &users.test1]);
+    assert!(matches!(
+        light_client.verify_light_block(light_block, None),
+        Err(LightClientError::Block(BlockError::InsufficientFinality {
+            found: 2,
+            total: 4
+        }))
+    ));
+
+    // vote from outside the validator set
+    let light_block = finalize(&block, &[&users.main, &users.test1, &users.send]);
+    assert!(matches!(
+        light_client.verify_light_block(light_block, None),
+        Err(LightClientError::Block(BlockError::UnknownValidator(_)))
+    ));
+
+    // leader outside the validator set
+    let mut block = create_block_from_last(&users.send, &genesis);
+    block.sign(&users.send.wallet);
+    let light_block = finalize(&block, &[&users.main, &users.test1, &users.test2]);
+    assert!(matches!(
+        light_client.verify_light_block(light_block, None),
+        Err(LightClientError::UnknownLeader(_))
+    ));
+
+    // header that doesn't extend the latest verified header
+    let mut block = Block::new(
+        ChainId::default(),
+        BlockTxnMap::new(),
+        users.main.pbkey(),
+        BlockId::from_bytes([9u8; 64]),
+        *genesis.blockheight(),
+    );
+    block.sign(&users.main.wallet);
+    let light_block = finalize(&block, &[&users.main, &users.test1, &users.test2]);
+    assert!(matches!(
+        light_client.verify_light_block(light_block, None),
+        Err(LightClientError::UnlinkedHeader)
+    ));
+
+    // header signed by someone other than the leader
+    let mut block = create_block_from_last(&users.main, &genesis);
+    block.sign(&users.test1.wallet);
+    let light_block = finalize(&block, &[&users.main, &users.test1, &users.test2]);
+    assert!(matches!(
+        light_client.verify_light_block(light_block, None),
+        Err(LightClientError::Block(BlockError::InvalidSignature(..)))
+    ));
+
+    // nothing was accepted
+    assert_eq!(light_client.latest_header(), genesis.header());
+}
+
+/// A finalized header hands over to a new validator set, which finalizes the headers after it.
+#[test]
+fn verify_light_block_validator_set_change_pass() -> Result<()> {
+    let (users, _blockchain, genesis, mut light_client) = init_chains();
+    let next_validators = ValidatorSet::new([
+        (users.test1.pbkey(), 1),
+        (users.test2.pbkey(), 1),
+        (users.test3.pbkey(), 1),
+    ]);
+
+    let mut block = create_block_from_last(&users.main, &genesis);
+    block.set_next_validator_set(&next_validators);
+    block.sign(&users.main.wallet);
+    let light_block = finalize(&block, &[&users.main, &users.test1, &users.test2]);
+
+    // the new set must be provided, and match the header
+    assert!(matches!(
+        light_client.verify_light_block(light_block.clone(), None),
+        Err(LightClientError::MissingValidatorSet)
+    ));
+    assert!(matches!(
+        light_client.verify_light_block(light_block.clone(), Some(init_validators(&users))),
+        Err(LightClientError::ValidatorSetMismatch)
+    ));
+    light_client.verify_light_block(light_block, Some(next_validators.clone()))?;
+    assert_eq!(light_client.validators(), &next_validators);
+
+    // the old set can no longer finalize
+    let mut next_block = create_block_from_last(&users.test1, &block);
+    next_block.sign(&users.test1.wallet);
+    let light_block = finalize(&next_block, &[&users.main, &users.test1, &users.test2]);
+    assert!(light_client.verify_light_block(light_block, None).is_err());
+
+    let light_block = finalize(&next_block, &[&users.test1, &users.test2, &users.test3]);
+    light_client.verify_light_block(light_block, None)?;
+    assert_eq!(light_client.latest_header(), next_block.header());
+
+    Ok(())
+}
+
+/// Quorums are exact for any voting power - sums near `u128::MAX` don't overflow.
+#[test]
+fn validator_set_quorum_large_power_pass() -> Result<()> {
+    let users = crate::common::init_users();
+    let (main, test1, test2) = (users.main.pbkey(), users.test1.pbkey(), users.test2.pbkey());
+    let validators = ValidatorSet::new([(main, u128::MAX), (test1, u128::MAX), (test2, u128::MAX)]);
+    assert_eq!(validators.total_power(), u128::MAX);
+    // exactly 2/3 isn't enough
+    assert!(!validators.is_quorum([&main, &test1]));
+    assert!(!validators.is_quorum([&main, &main, &test1]));
+    assert!(validators.is_quorum([&main, &test1, &test2]));
+
+    let validators = ValidatorSet::new([(main, u128::MAX - 1), (test1, 1)]);
+    assert!(validators.is_quorum([&main]));
+    assert!(!validators.is_quorum([&test1]));
+    let validators = ValidatorSet::new([(main, u128::MAX / 3 * 2 + 1), (test1, u128::MAX / 3)]);
+    assert!(validators.is_quorum([&main]));
+
+    // finality is checked the same way
+    let block_id = BlockId::from_bytes([1u8; 64]);
+    let validators = ValidatorSet::new([(main, u128::MAX), (test1, u128::MAX), (test2, u128::MAX)]);
+    let mut finality = FinalityCertificate::new(block_id);
+    finality.sign(&users.main.wallet);
+    finality.sign(&users.test1.wallet);
+    assert!(matches!(
+        finality.verify(&block_id, &validators),
+        Err(BlockError::InsufficientFinality { found, total })
+            if found == u128::MAX && total == u128::MAX
+    ));
+    finality.sign(&users.test2.wallet);
+    finality.verify(&block_id, &validators)?;
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use posbc::utils::{
+    merkle::{empty_root, hash_leaf, hash_node, MerkleTree},
+    sparse_merkle::{hash_smt_leaf, SparseMerkleTree},
+};
+
+// Golden vector: root over leaves `a`, `b`, `c` - `c` is promoted without a pair.
+const GOLDEN_ROOT_ABC_HEX: &str =
+    "6c62dd52a0971b7d00a7cead004e0c3f3c0766e3f5359a0f8297768d2b02d03c";
+
+fn sample_leaves(leaf_ct: u8) -> Vec<[u8; 1]> {
+    (0..leaf_ct).map(|x| [x]).collect()
+}
+
+#[test]
+fn root_pass() {
+    let leaves: [&[u8]; 3] = [b"a", b"b", b"c"];
+    let root = MerkleTree::new(leaves).root();
+
+    assert_eq!(hex::encode(root.as_bytes()), GOLDEN_ROOT_ABC_HEX);
+    assert_eq!(
+        root,
+        hash_node(
+            &hash_node(&hash_leaf(b"a"), &hash_leaf(b"b")),
+            &hash_leaf(b"c")
+        )
+    );
+    assert_eq!(MerkleTree::new(Vec::<Vec<u8>>::new()).root(), empty_root());
+    assert_eq!(MerkleTree::new([b"a"]).root(), hash_leaf(b"a"));
+}
+
+#[test]
+fn proof_pass() {
+    for leaf_ct in 1..10 {
+        let leaves = sample_leaves(leaf_ct);
+        let tree = MerkleTree::new(&leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(
+                proof.verify(&tree.root(), leaf),
+                "{leaf_ct} leaves, index {index}"
+            );
+        }
+    }
+}
+
+#[test]
+fn proof_fail() {
+    let leaves = sample_leaves(7);
+    let tree = MerkleTree::new(&leaves);
+    let proof = tree.proof(2).unwrap();
+
+    // out of range
+    assert!(tree.proof(7).is_none());
+    // wrong leaf
+    assert!(!proof.verify(&tree.root(), &leaves[3]));
+    // wrong root
+    assert!(!proof.verify(&MerkleTree::new(sample_leaves(6)).root(), &leaves[2]));
+}
+
+fn sample_smt() -> SparseMerkleTree {
+    SparseMerkleTree::new(
+        [[0x01u8; 32], [0x02u8; 32], [0x81u8; 32], [0xf0u8; 32]]
+            .into_iter()
+            .map(|key| (key, hash_leaf(&key))),
+    )
+}
+
+#[test]
+fn smt_root_pass() {
+    let smt = sample_smt();
+    assert_eq!(SparseMerkleTree::default().root(), empty_root());
+
+    // root doesn't depend on insertion order
+    let mut smt_rev = SparseMerkleTree::default();
+    for key in [[0xf0u8; 32], [0x81u8; 32], [0x02u8; 32], [0x01u8; 32]] {
+        smt_rev.insert(key, hash_leaf(&key));
+    }
+    assert_eq!(smt_rev.root(), smt.root());
+
+    // a single leaf is its own root
+    let key = [7u8; 32];
+    let value_hash = hash_leaf(b"value");
+    let smt_single = SparseMerkleTree::new([(key, value_hash)]);
+    assert_eq!(smt_single.root(), hash_smt_leaf(&key, &value_hash));
+}
+
+#[test]
+fn smt_proof_pass() {
+    let smt = sample_smt();
+    let root = smt.root();
+
+    // inclusion
+    for key in [[0x01u8; 32], [0x02u8; 32], [0x81u8; 32], [0xf0u8; 32]] {
+        let proof = smt.proof(&key);
+        assert!(proof.verify(&root, &key, Some(&hash_leaf(&key))));
+    }
+    // exclusion - path ends at another key's leaf (0x80.. shares its first bits with 0x81..)
+    let key = [0x80u8; 32];
+    assert!(smt.proof(&key).verify(&root, &key, None));
+    // exclusion - path ends at an empty subtree (0x40.. branches off next to 0x01.. and 0x02..)
+    let key = [0x40u8; 32];
+    assert!(smt.proof(&key).verify(&root, &key, None));
+    // exclusion - empty tree
+    let smt_empty = SparseMerkleTree::default();
+    assert!(smt_empty.proof(&key).verify(&empty_root(), &key, None));
+}
+
+#[test]
+fn smt_proof_fail() {
+    let smt = sample_smt();
+    let root = smt.root();
+    let key = [0x81u8; 32];
+    let proof = smt.proof(&key);
+
+    // wrong value
+    assert!(!proof.verify(&root, &key, Some(&hash_leaf(b"wrong"))));
+    // member claimed absent
+    assert!(!proof.verify(&root, &key, None));
+    // proof for another key
+    let other_key = [0x80u8; 32];
+    assert!(!proof.verify(&root, &other_key, Some(&hash_leaf(&key))));
+    // absent key claimed present
+    assert!(!smt
+        .proof(&other_key)
+        .verify(&root, &other_key, Some(&hash_leaf(&other_key))));
+    // wrong root
+    assert!(!proof.verify(&empty_root(), &key, Some(&hash_leaf(&key))));
+}
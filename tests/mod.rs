@@ -1,8 +1,19 @@
 pub mod accounts;
+pub mod address;
+pub mod archive;
 pub mod block;
 pub mod blockchain;
 pub mod common;
+pub mod encoding;
+pub mod genesis;
+pub mod light_client;
+pub mod merkle;
+pub mod multisig;
 pub mod node;
+pub mod protocol;
+pub mod pruning;
+pub mod receipt;
+pub mod storage;
 pub mod txn;
 pub mod txn_pool;
 pub mod wallet;
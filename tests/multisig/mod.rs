@@ -0,0 +1,173 @@
+use posbc::{
+    accounts::multisig::MultisigPolicy,
+    ledger::{
+        blockchain::Blockchain,
+        chain_id::ChainId,
+        general::{PbKey, Result},
+        txn::{error::TxnError, Txn, TxnType},
+    },
+};
+// test
+use crate::common::{fxns::init_blockchain, UsersInfo};
+
+/// ### Create a 2-of-3 multisig account (test1, test2, test3) with a balance of 1000.
+fn init_multisig_acct(users: &UsersInfo, blockchain: &mut Blockchain) -> PbKey {
+    let policy = MultisigPolicy::new(
+        2,
+        vec![
+            users.test1.pbkey(),
+            users.test2.pbkey(),
+            users.test3.pbkey(),
+        ],
+    )
+    .expect("Error creating multisig policy.");
+    let acct = blockchain
+        .accounts_mut()
+        .add_multisig_acct(policy, Some(1000));
+
+    acct.id_pbkey()
+}
+
+#[test]
+fn create_multisig_policy_fail() {
+    let users = crate::common::init_users();
+    let signers = vec![users.test1.pbkey(), users.test2.pbkey()];
+
+    assert!(MultisigPolicy::new(0, signers.clone()).is_err());
+    assert!(MultisigPolicy::new(3, signers).is_err());
+    assert!(
+        MultisigPolicy::new(1, vec![users.test1.pbkey(), users.test1.pbkey()]).is_err(),
+        "Duplicate signers should be rejected"
+    );
+}
+
+/// Signer order should not change the derived account id.
+#[test]
+fn multisig_account_id_pass() -> Result<()> {
+    let users = crate::common::init_users();
+    let policy_a = MultisigPolicy::new(2, vec![users.test1.pbkey(), users.test2.pbkey()])?;
+    let policy_b = MultisigPolicy::new(2, vec![users.test2.pbkey(), users.test1.pbkey()])?;
+    let policy_c = MultisigPolicy::new(1, vec![users.test2.pbkey(), users.test1.pbkey()])?;
+
+    assert_eq!(policy_a.account_id(), policy_b.account_id());
+    assert_ne!(policy_a.account_id(), policy_c.account_id());
+
+    Ok(())
+}
+
+/// Partial signatures are collected on separate copies of the txn, then merged.
+#[test]
+fn execute_multisig_txn_pass() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain();
+    let multisig_pbkey = init_multisig_acct(&users, &mut blockchain);
+
+    let txn = Txn::new(
+        ChainId::default(),
+        multisig_pbkey,
+        users.recv.pbkey(),
+        10,
+        TxnType::Transfer,
+    );
+    let mut txn_signer1 = txn.clone();
+    let mut txn_signer3 = txn.clone();
+    txn_signer1.sign_multisig(&users.test1.wallet);
+    txn_signer3.sign_multisig(&users.test3.wallet);
+
+    let mut txn_combined = txn;
+    txn_combined.merge_multisig(&txn_signer1)?;
+    txn_combined.merge_multisig(&txn_signer3)?;
+    assert_eq!(txn_combined.multisig().unwrap().len(), 2);
+
+    blockchain.add_txn_to_block(&txn_combined)?;
+
+    let accounts = blockchain.accounts();
+    assert_eq!(accounts.acct_balance(&multisig_pbkey.into()), 990);
+    assert_eq!(accounts.acct_balance(&users.recv.pbkey().into()), 10);
+
+    Ok(())
+}
+
+#[test]
+fn execute_multisig_txn_fail_threshold() {
+    let (users, mut blockchain) = init_blockchain();
+    let multisig_pbkey = init_multisig_acct(&users, &mut blockchain);
+
+    let mut txn = Txn::new(
+        ChainId::default(),
+        multisig_pbkey,
+        users.recv.pbkey(),
+        10,
+        TxnType::Transfer,
+    );
+    txn.sign_multisig(&users.test1.wallet);
+    // the same signer signing twice does not count twice
+    txn.sign_multisig(&users.test1.wallet);
+
+    assert!(blockchain.add_txn_to_block(&txn).is_err());
+    assert_eq!(
+        blockchain.accounts().acct_balance(&multisig_pbkey.into()),
+        1000
+    );
+}
+
+/// A cosignature repeated up to the threshold (i.e. in a txn from a peer) counts once.
+#[test]
+fn execute_multisig_txn_fail_repeated_cosig() -> Result<()> {
+    let (users, mut blockchain) = init_blockchain();
+    let multisig_pbkey = init_multisig_acct(&users, &mut blockchain);
+
+    let mut txn = Txn::new(
+        ChainId::default(),
+        multisig_pbkey,
+        users.recv.pbkey(),
+        10,
+        TxnType::Transfer,
+    );
+    txn.sign_multisig(&users.test1.wallet);
+    // `sign_multisig()` keeps one signature per signer - repeat it in the encoding instead
+    let json = serde_json::to_string(&txn)?;
+    let start = json.find(r#""multisig":["#).unwrap() + r#""multisig":["#.len();
+    let end = start + json[start..].find("}]").unwrap() + 1;
+    let cosig = &json[start..end];
+    let repeated: Txn = serde_json::from_str(&json.replace(cosig, &[cosig, cosig].join(",")))?;
+    assert_eq!(repeated.multisig().unwrap().len(), 2);
+
+    let policy = blockchain
+        .accounts()
+        .multisig_policy(&multisig_pbkey.into())
+        .unwrap()
+        .clone();
+    assert!(matches!(
+        repeated.is_multisig_valid(&policy),
+        Err(TxnError::ThresholdNotMet {
+            required: 2,
+            found: 1
+        })
+    ));
+    assert!(blockchain.add_txn_to_block(&repeated).is_err());
+    assert_eq!(
+        blockchain.accounts().acct_balance(&multisig_pbkey.into()),
+        1000
+    );
+
+    Ok(())
+}
+
+#[test]
+fn execute_multisig_txn_fail_unknown_signer() {
+    let (users, mut blockchain) = init_blockchain();
+    let multisig_pbkey = init_multisig_acct(&users, &mut blockchain);
+
+    let mut txn = Txn::new(
+        ChainId::default(),
+        multisig_pbkey,
+        users.recv.pbkey(),
+        10,
+        TxnType::Transfer,
+    );
+    txn.sign_multisig(&users.test1.wallet);
+    txn.sign_multisig(&users.test2.wallet);
+    txn.sign_multisig(&users.main.wallet);
+
+    assert!(blockchain.add_txn_to_block(&txn).is_err());
+}
@@ -1,7 +1,203 @@
-use posbc::node::{p2p::P2P, Node};
-use std::net::{IpAddr, Ipv4Addr};
+use posbc::{
+    accounts::{accounts::Accounts, multisig::MultisigPolicy},
+    ledger::{
+        block::{block_id::BlockId, Block},
+        blockchain::{pruning::PruningMode, Blockchain},
+        chain_id::ChainId,
+        genesis::GenesisSpec,
+        receipt::BlockReceipts,
+        txn::{Txn, TxnType},
+    },
+    node::{
+        error::NodeError,
+        gossip::SeenCache,
+        p2p::{
+            error::P2PError,
+            message::{
+                BlockRequest, ChainStatus, NetworkEvent, NetworkMessage, MAX_BLOCKS_PER_REQUEST,
+            },
+            P2P,
+        },
+        sync::SyncState,
+        Node,
+    },
+    storage::{
+        backend::{memory::MemoryChainBackend, ChainBackend},
+        block_store::{ChainMeta, StoredBlock},
+        error::StorageError,
+        state_db::StateCheckpoint,
+    },
+};
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::mpsc::UnboundedReceiver;
 // test
-use crate::common::fxns::init_blockchain_and_accounts;
+use crate::common::{
+    fxns::{
+        add_transfer_block, create_block_from_last, init_blockchain_and_accounts, init_genesis_spec,
+    },
+    init_users, UserInfo,
+};
+
+/// ### Start a node for `user`, listening on a free local port - returns the node and its listen address.
+async fn start_node(user: UserInfo) -> (Node, libp2p::Multiaddr) {
+    let p2p = P2P::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0, user.kp);
+    let mut node = Node::new(p2p, user.wallet);
+    node.start_p2p().expect("Error starting p2p");
+    loop {
+        if let Some(NetworkEvent::Listening(addr)) = node.next_network_event().await {
+            return (node, addr);
+        }
+    }
+}
+
+/// ### Connect two nodes - `sender` dials `receiver`, then publishes until one of its messages gets through.
+/// Peers learn each other's subscriptions once connected.
+async fn connect_nodes(sender: &mut Node, receiver: &mut Node, receiver_addr: libp2p::Multiaddr) {
+    let ping = NetworkMessage::BlockRequest(BlockRequest { from: 1, limit: 1 });
+    sender
+        .p2p()
+        .dial(receiver_addr)
+        .expect("Error dialing peer");
+    tokio::time::timeout(Duration::from_secs(20), async {
+        loop {
+            sender.broadcast(&ping).expect("Error publishing message");
+            let wait = tokio::time::sleep(Duration::from_millis(200));
+            tokio::pin!(wait);
+            loop {
+                tokio::select! {
+                    event = receiver.next_network_event() => match event {
+                        Some(NetworkEvent::Message { .. }) => return,
+                        Some(_) => {}
+                        None => panic!("Network event channel closed"),
+                    },
+                    _ = sender.next_network_event() => {}
+                    _ = &mut wait => break,
+                }
+            }
+        }
+    })
+    .await
+    .expect("Nodes not connected");
+}
+
+/// ### Handle `node`'s network events - i.e. answer its peers' sync requests - until its p2p connection stops.
+async fn serve(node: &mut Node) {
+    while node.next_network_event().await.is_some() {}
+}
+
+/// ### Answer sync requests with the `status` given, and without blocks - i.e. a peer lying about its chain.
+async fn serve_lies(p2p: &P2P, events: &mut UnboundedReceiver<NetworkEvent>, status: &ChainStatus) {
+    while let Some(event) = events.recv().await {
+        if let NetworkEvent::Request {
+            request_id,
+            message,
+            ..
+        } = event
+        {
+            let response = match message {
+                NetworkMessage::Status(_) => NetworkMessage::Status(status.clone()),
+                _ => NetworkMessage::BlockResponse(vec![]),
+            };
+            p2p.respond(request_id, response)
+                .expect("Error answering request");
+        }
+    }
+}
+
+/// ## `ChainBackend` in memory that can't store blocks while `full` is set - i.e. a full disk.
+#[derive(Debug, Default)]
+struct FullDiskBackend {
+    backend: MemoryChainBackend,
+    full: Arc<AtomicBool>,
+}
+impl ChainBackend for FullDiskBackend {
+    fn meta(&self) -> Option<&ChainMeta> {
+        self.backend.meta()
+    }
+    fn put_meta(&mut self, meta: ChainMeta) -> Result<(), StorageError> {
+        self.backend.put_meta(meta)
+    }
+    fn height(&self) -> u128 {
+        self.backend.height()
+    }
+    fn block_id_at(&self, height: u128) -> Option<BlockId> {
+        self.backend.block_id_at(height)
+    }
+    fn block_at(&self, height: u128) -> Result<StoredBlock, StorageError> {
+        self.backend.block_at(height)
+    }
+    fn append_block(
+        &mut self,
+        block: &Block,
+        receipts: &BlockReceipts,
+    ) -> Result<(), StorageError> {
+        if self.full.load(Ordering::SeqCst) {
+            return Err(std::io::Error::other("disk full").into());
+        }
+        self.backend.append_block(block, receipts)
+    }
+    fn rollback_to(&mut self, height: u128) -> Result<(), StorageError> {
+        self.backend.rollback_to(height)
+    }
+    fn state_height(&self) -> u128 {
+        self.backend.state_height()
+    }
+    fn load_state(&self) -> Result<Option<StateCheckpoint>, StorageError> {
+        self.backend.load_state()
+    }
+    fn commit_state(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        prev: &Accounts,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError> {
+        self.backend.commit_state(height, block_id, prev, accounts)
+    }
+    fn reset_state(
+        &mut self,
+        height: u128,
+        block_id: BlockId,
+        accounts: &Accounts,
+    ) -> Result<(), StorageError> {
+        self.backend.reset_state(height, block_id, accounts)
+    }
+}
+
+/// ### Open a chain from `spec` on a `FullDiskBackend` - returns the chain and the backend's `full` flag.
+fn init_full_disk_chain(spec: &GenesisSpec) -> (Blockchain, Arc<AtomicBool>) {
+    let backend = FullDiskBackend::default();
+    let full = backend.full.clone();
+    let blockchain =
+        Blockchain::open_with_backend(Box::new(backend), spec, PruningMode::default()).unwrap();
+    full.store(true, Ordering::SeqCst);
+
+    (blockchain, full)
+}
+
+/// ### Handle `node`'s network events until `done` holds - panics after 20 seconds.
+async fn wait_until(node: &mut Node, done: impl Fn(&Node, &NetworkEvent) -> bool) {
+    tokio::time::timeout(Duration::from_secs(20), async {
+        loop {
+            let event = node
+                .next_network_event()
+                .await
+                .expect("Network event channel closed");
+            if done(node, &event) {
+                return;
+            }
+        }
+    })
+    .await
+    .expect("Timed out waiting for network events");
+}
 
 /// Test if the port is available using std lib.
 /// Attempt to open port at `localhost:port`.
@@ -40,14 +236,442 @@ async fn init_node_pass() {
     let mut node2 = Node::new(p2p2, main.wallet);
 
     // 3. connect to node1
-    node2.p2p_mut().discover_peer(&node1_pbkey);
+    assert!(node2.p2p().discover_peer(&node1_pbkey).is_err());
+    node2.start_p2p().expect("Error starting p2p");
+    assert!(node2.p2p().is_running());
+    assert!(node2.start_p2p().is_err());
+    node2
+        .p2p_mut()
+        .discover_peer(&node1_pbkey)
+        .expect("Error discovering peer");
+
+    node2.stop_p2p().await.expect("Error stopping p2p");
+    assert!(!node2.p2p().is_running());
+    assert!(node2.next_network_event().await.is_none());
+}
+
+/// Two nodes connect, and a message published by one is received by the other.
+#[tokio::test]
+async fn network_message_pass() {
+    let users = init_users();
+    let txn = Txn::new_signed(
+        &users.send.wallet,
+        Default::default(),
+        users.recv.pbkey(),
+        10,
+        TxnType::Transfer,
+    );
+    let message = NetworkMessage::NewTxn(Box::new(txn));
+    let (mut sender, _) = start_node(users.test1).await;
+    let sender_id = sender.p2p().peer_id();
+    let (mut receiver, receiver_addr) = start_node(users.test2).await;
+    connect_nodes(&mut sender, &mut receiver, receiver_addr).await;
+
+    sender
+        .broadcast(&message)
+        .expect("Error publishing message");
+    wait_until(&mut receiver, |_, event| {
+        event
+            == &NetworkEvent::Message {
+                source: sender_id,
+                message: message.clone(),
+            }
+    })
+    .await;
+}
+
+/// A txn submitted to one node is gossiped into its peer's txn pool - invalid txns get their sender banned.
+#[tokio::test]
+async fn txn_gossip_pass() {
+    let users = init_users();
+    let new_txn = |chain_id: ChainId, amt| {
+        Txn::new_signed(
+            &users.send.wallet,
+            chain_id,
+            users.recv.pbkey(),
+            amt,
+            TxnType::Transfer,
+        )
+    };
+    let txn = new_txn(ChainId::default(), 10);
+    let (mut sender, _) = start_node(users.test1).await;
+    let sender_id = sender.p2p().peer_id();
+    let (mut receiver, receiver_addr) = start_node(users.test2).await;
+    connect_nodes(&mut sender, &mut receiver, receiver_addr).await;
+
+    sender
+        .submit_txn(txn.clone())
+        .expect("Error submitting txn");
+    assert!(sender.txn_pool().does_txn_exist(&txn));
+    assert!(sender.submit_txn(txn.clone()).is_err());
+    wait_until(&mut receiver, |node, _| {
+        node.txn_pool().does_txn_exist(&txn)
+    })
+    .await;
+
+    // txns are identified by id - published again, they aren't relayed
+    let message = NetworkMessage::NewTxn(Box::new(txn));
+    let marker = NetworkMessage::BlockRequest(BlockRequest { from: 2, limit: 1 });
+    sender
+        .broadcast(&message)
+        .expect("Error publishing message");
+    sender.broadcast(&marker).expect("Error publishing message");
+    wait_until(&mut receiver, |_, event| match event {
+        NetworkEvent::Message { message: m, .. } => {
+            assert_ne!(m, &message, "Txn relayed twice");
+            m == &marker
+        }
+        _ => false,
+    })
+    .await;
+    assert_eq!(receiver.txn_pool().txn_ct(), 1);
+
+    // txns for another chain are rejected, and cost the sender its score
+    let other_chain: ChainId = "other-chain".parse().unwrap();
+    for amt in [20, 30] {
+        let invalid = NetworkMessage::NewTxn(Box::new(new_txn(other_chain.clone(), amt)));
+        sender
+            .broadcast(&invalid)
+            .expect("Error publishing message");
+    }
+    wait_until(&mut receiver, |_, event| {
+        event == &NetworkEvent::PeerBanned(sender_id)
+    })
+    .await;
+    assert_eq!(receiver.txn_pool().txn_ct(), 1);
+}
+
+/// A multisig txn relayed with cosignatures stripped doesn't keep the full txn, arriving later, out of the pool.
+#[tokio::test]
+async fn txn_gossip_stripped_multisig_pass() {
+    let (users, mut blockchain) = init_blockchain_and_accounts();
+    let policy = MultisigPolicy::new(2, vec![users.test1.pbkey(), users.test3.pbkey()]).unwrap();
+    let multisig_pbkey = blockchain
+        .accounts_mut()
+        .add_multisig_acct(policy, Some(1000))
+        .id_pbkey();
+    let mut stripped = Txn::new(
+        ChainId::default(),
+        multisig_pbkey,
+        users.recv.pbkey(),
+        10,
+        TxnType::Transfer,
+    );
+    stripped.sign_multisig(&users.test1.wallet);
+    let mut txn = stripped.clone();
+    txn.sign_multisig(&users.test3.wallet);
+    let (mut sender, _) = start_node(users.test1).await;
+    let (mut receiver, receiver_addr) = start_node(users.test2).await;
+    receiver.set_blockchain(blockchain);
+    connect_nodes(&mut sender, &mut receiver, receiver_addr).await;
+
+    // same txn id, one cosignature short of the threshold
+    let stripped = NetworkMessage::NewTxn(Box::new(stripped));
+    sender
+        .broadcast(&stripped)
+        .expect("Error publishing message");
+    wait_until(
+        &mut receiver,
+        |_, event| matches!(event, NetworkEvent::Message { message, .. } if message == &stripped),
+    )
+    .await;
+    assert_eq!(receiver.txn_pool().txn_ct(), 0);
+
+    sender
+        .broadcast(&NetworkMessage::NewTxn(Box::new(txn.clone())))
+        .expect("Error publishing message");
+    wait_until(&mut receiver, |node, _| {
+        node.txn_pool().does_txn_exist(&txn)
+    })
+    .await;
+}
+
+/// A block propagated by one node is added to its peer's chain - blocks failing pre-validation get their sender banned.
+#[tokio::test]
+async fn block_gossip_pass() {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let mut chain = Blockchain::from_genesis(&spec).unwrap();
+    let txn = add_transfer_block(&mut chain, &users, &users.recv, 10).unwrap();
+    let block = chain.last_block().clone();
+    let (mut sender, _) = start_node(users.test1).await;
+    let sender_id = sender.p2p().peer_id();
+    let (mut receiver, receiver_addr) = start_node(users.test2).await;
+    receiver.set_blockchain(Blockchain::from_genesis(&spec).unwrap());
+    receiver
+        .submit_txn(txn.clone())
+        .expect("Error submitting txn");
+    sender.set_blockchain(chain);
+    connect_nodes(&mut sender, &mut receiver, receiver_addr).await;
+
+    sender
+        .broadcast(&NetworkMessage::NewBlock(Box::new(block.clone())))
+        .expect("Error publishing message");
+    wait_until(&mut receiver, |node, _| node.blockchain().height() == 2).await;
+    assert_eq!(receiver.blockchain().last_block(), &block);
+    assert_eq!(
+        receiver.blockchain().accounts().state_root(),
+        sender.blockchain().accounts().state_root()
+    );
+    // its txns left the txn pool
+    assert!(!receiver.txn_pool().does_txn_exist(&txn));
+
+    // blocks not signed by their leader aren't relayed, and cost the sender its score
+    for leader in [&users.main, &users.send] {
+        let mut invalid = create_block_from_last(leader, &block);
+        invalid.sign(&users.test3.wallet);
+        sender
+            .broadcast(&NetworkMessage::NewBlock(Box::new(invalid)))
+            .expect("Error publishing message");
+    }
+    wait_until(&mut receiver, |_, event| {
+        event == &NetworkEvent::PeerBanned(sender_id)
+    })
+    .await;
+    assert_eq!(receiver.blockchain().height(), 2);
+}
+
+/// A block the node fails to store doesn't cost its sender any score - it's added once it can be stored.
+#[tokio::test]
+async fn block_gossip_storage_error_pass() {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    // blocks at the same height, each a new message
+    let mut blocks = vec![];
+    for amt in [10, 20, 30] {
+        let mut chain = Blockchain::from_genesis(&spec).unwrap();
+        add_transfer_block(&mut chain, &users, &users.recv, amt).unwrap();
+        blocks.push(chain.last_block().clone());
+    }
+    let (mut sender, _) = start_node(users.test1).await;
+    let (mut receiver, receiver_addr) = start_node(users.test2).await;
+    let (blockchain, full) = init_full_disk_chain(&spec);
+    receiver.set_blockchain(blockchain);
+    connect_nodes(&mut sender, &mut receiver, receiver_addr).await;
+
+    // two penalties would get the sender banned
+    for block in &blocks[..2] {
+        let message = NetworkMessage::NewBlock(Box::new(block.clone()));
+        sender
+            .broadcast(&message)
+            .expect("Error publishing message");
+        wait_until(
+            &mut receiver,
+            |_, event| matches!(event, NetworkEvent::Message { message: m, .. } if m == &message),
+        )
+        .await;
+    }
+    assert_eq!(receiver.blockchain().height(), 1);
+
+    full.store(false, Ordering::SeqCst);
+    sender
+        .broadcast(&NetworkMessage::NewBlock(Box::new(blocks[2].clone())))
+        .expect("Error publishing message");
+    wait_until(&mut receiver, |node, _| node.blockchain().height() == 2).await;
+    assert_eq!(receiver.blockchain().last_block(), &blocks[2]);
+}
+
+/// A node behind its peers syncs the missing blocks from both of them, then follows gossip.
+#[tokio::test]
+async fn sync_blockchain_pass() {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    // more blocks than fit in one batch
+    let height = MAX_BLOCKS_PER_REQUEST as u128 + 6;
+    let mut chain = Blockchain::from_genesis(&spec).unwrap();
+    while chain.height() < height {
+        add_transfer_block(&mut chain, &users, &users.recv, 1).unwrap();
+    }
+    let mut copy = Blockchain::from_genesis(&spec).unwrap();
+    for height in 2..=height {
+        copy.replay_block(chain.block_at(height).unwrap().clone())
+            .unwrap();
+    }
+    let tip = chain.last_block().clone();
+
+    let (mut node, _) = start_node(users.test1).await;
+    node.set_blockchain(Blockchain::from_genesis(&spec).unwrap());
+    assert_eq!(node.sync().state(), SyncState::Idle);
+    let mut peers = vec![];
+    for (user, chain) in [(users.test2, chain), (users.test3, copy)] {
+        let (mut peer, addr) = start_node(user).await;
+        peer.set_blockchain(chain);
+        connect_nodes(&mut node, &mut peer, addr).await;
+        peers.push(peer);
+    }
+    let (peer1, peer2) = peers.split_at_mut(1);
+
+    let synced = tokio::time::timeout(Duration::from_secs(30), async {
+        tokio::select! {
+            synced = node.sync_blockchain() => synced,
+            _ = serve(&mut peer1[0]) => unreachable!(),
+            _ = serve(&mut peer2[0]) => unreachable!(),
+        }
+    })
+    .await
+    .expect("Sync timed out")
+    .expect("Error syncing blockchain");
+
+    assert_eq!(synced, height - 1);
+    assert_eq!(node.blockchain().height(), height);
+    assert_eq!(node.blockchain().last_block(), &tip);
+    assert_eq!(node.sync().state(), SyncState::Following);
+    assert_eq!(node.sync().best_height(), height);
+    for peer in &peers {
+        let status = node.sync().peer_status(&peer.p2p().peer_id()).unwrap();
+        assert_eq!(status.tip_id, tip.id());
+        assert_eq!(
+            status.finalized_height,
+            peer.blockchain().finalized_height()
+        );
+    }
+}
+
+/// A peer claiming blocks it doesn't serve neither overflows the sync's requests, nor stalls the sync.
+#[tokio::test]
+async fn sync_blockchain_lying_peer_pass() {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let mut chain = Blockchain::from_genesis(&spec).unwrap();
+    for amt in [10, 20, 30] {
+        add_transfer_block(&mut chain, &users, &users.recv, amt).unwrap();
+    }
+    let tip = chain.last_block().clone();
+    let genesis = Blockchain::from_genesis(&spec).unwrap();
+    let lie = ChainStatus {
+        height: u128::MAX,
+        ..ChainStatus::from(&genesis)
+    };
+
+    let (mut node, _) = start_node(users.test1).await;
+    node.set_blockchain(genesis);
+    let (mut peer, addr) = start_node(users.test2).await;
+    peer.set_blockchain(chain);
+    connect_nodes(&mut node, &mut peer, addr).await;
+    let mut liar = P2P::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0, users.test3.kp);
+    let liar_id = liar.peer_id();
+    let mut liar_events = liar.start_p2p().expect("Error starting p2p");
+    let liar_addr = loop {
+        if let Some(NetworkEvent::Listening(addr)) = liar_events.recv().await {
+            break addr;
+        }
+    };
+    node.p2p().dial(liar_addr).expect("Error dialing peer");
+
+    let synced = tokio::time::timeout(Duration::from_secs(30), async {
+        tokio::select! {
+            _ = wait_until(&mut node, |node, _| node.sync().peer_status(&liar_id).is_some()) => {}
+            _ = serve(&mut peer) => unreachable!(),
+            _ = serve_lies(&liar, &mut liar_events, &lie) => unreachable!(),
+        }
+        assert_eq!(node.sync().best_height(), u128::MAX);
+        tokio::select! {
+            synced = node.sync_blockchain() => synced,
+            _ = serve(&mut peer) => unreachable!(),
+            _ = serve_lies(&liar, &mut liar_events, &lie) => unreachable!(),
+        }
+    })
+    .await
+    .expect("Sync timed out")
+    .expect("Error syncing blockchain");
+
+    assert_eq!(synced, 3);
+    assert_eq!(node.blockchain().last_block(), &tip);
+    assert_eq!(node.sync().state(), SyncState::Following);
+    assert_eq!(node.sync().best_height(), 4);
+}
+
+/// Blocks the node fails to store stop the sync without costing the serving peer its score, or its place in the sync.
+#[tokio::test]
+async fn sync_blockchain_storage_error_pass() {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let mut chain = Blockchain::from_genesis(&spec).unwrap();
+    for amt in [10, 20, 30] {
+        add_transfer_block(&mut chain, &users, &users.recv, amt).unwrap();
+    }
+    let tip = chain.last_block().clone();
+
+    let (mut node, _) = start_node(users.test1).await;
+    let (blockchain, full) = init_full_disk_chain(&spec);
+    node.set_blockchain(blockchain);
+    let (mut peer, addr) = start_node(users.test2).await;
+    peer.set_blockchain(chain);
+    connect_nodes(&mut node, &mut peer, addr).await;
+
+    // two penalties would get the peer banned
+    for _ in 0..2 {
+        let err = tokio::time::timeout(Duration::from_secs(30), async {
+            tokio::select! {
+                synced = node.sync_blockchain() => synced,
+                _ = serve(&mut peer) => unreachable!(),
+            }
+        })
+        .await
+        .expect("Sync timed out")
+        .unwrap_err();
+        match err {
+            NodeError::Ledger(e) => assert!(e.downcast_ref::<StorageError>().is_some()),
+            e => panic!("Unexpected error: {e:?}"),
+        }
+        assert_eq!(node.blockchain().height(), 1);
+        assert_eq!(node.sync().state(), SyncState::Idle);
+        assert_eq!(node.sync().best_height(), 4);
+    }
+
+    full.store(false, Ordering::SeqCst);
+    let synced = tokio::time::timeout(Duration::from_secs(30), async {
+        tokio::select! {
+            synced = node.sync_blockchain() => synced,
+            _ = serve(&mut peer) => unreachable!(),
+        }
+    })
+    .await
+    .expect("Sync timed out")
+    .expect("Error syncing blockchain");
+    assert_eq!(synced, 3);
+    assert_eq!(node.blockchain().last_block(), &tip);
+}
+
+/// Syncing needs a genesis block and a running p2p connection - without peers, the node is caught up.
+#[tokio::test]
+async fn sync_blockchain_fail() {
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let p2p = P2P::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0, users.test1.kp);
+    let mut node = Node::new(p2p, users.test1.wallet);
+    assert!(matches!(
+        node.sync_blockchain().await,
+        Err(NodeError::InitBlockchain)
+    ));
+    node.set_blockchain(Blockchain::from_genesis(&spec).unwrap());
+    assert!(matches!(
+        node.sync_blockchain().await,
+        Err(NodeError::P2PError(P2PError::InitP2P))
+    ));
+
+    node.start_p2p().expect("Error starting p2p");
+    assert_eq!(node.sync_blockchain().await.unwrap(), 0);
+    assert_eq!(node.sync().state(), SyncState::Following);
+}
+
+/// The seen cache drops its oldest keys once full.
+#[test]
+fn seen_cache_pass() {
+    let mut seen = SeenCache::new(2);
+    assert!(seen.is_empty());
+    assert!(seen.insert(1));
+    assert!(!seen.insert(1));
+    assert!(seen.insert(2));
+    assert!(seen.insert(3));
+    assert_eq!(seen.len(), 2);
+    assert!(!seen.contains(&1));
+    assert!(seen.contains(&2) && seen.contains(&3));
+    // a dropped key can be seen again
+    assert!(seen.insert(1));
+    assert!(!seen.contains(&2));
 }
 
 // Other tests:
-// - Sync a arbitrary block with another node.
-// - Sync the full blockchain with another node.
 // - Test block consensus.
-// - Send a transaction.
 // - Propose and submit a block to peers.
-// - (Multiple) test transaction gossip.
-// - (Multiple) test block gossip.
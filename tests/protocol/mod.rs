@@ -0,0 +1,177 @@
+use libp2p::PeerId;
+use posbc::{
+    ledger::{
+        block::{error::BlockError, Block},
+        blockchain::Blockchain,
+        general::Result,
+        txn::{error::TxnError, Txn},
+    },
+    node::p2p::{
+        error::ProtocolError,
+        message::{
+            BlockRequest, ChainStatus, NetworkMessage, Vote, MAX_BLOCKS_PER_REQUEST,
+            MAX_MESSAGE_BYTES, PROTOCOL_VERSION,
+        },
+        peer_score::{PeerScores, Penalty, BAN_SCORE},
+    },
+};
+// test
+use crate::common::{
+    fxns::{add_transfer_block, init_genesis_spec},
+    init_users,
+};
+
+/// Every message kind survives encoding, behind the protocol version byte.
+#[test]
+fn message_round_trip_pass() -> Result<()> {
+    let users = init_users();
+    let mut blockchain = Blockchain::from_genesis(&init_genesis_spec(&users))?;
+    let txn = add_transfer_block(&mut blockchain, &users, &users.recv, 10)?;
+    let block = blockchain.last_block().clone();
+
+    let status = ChainStatus::from(&blockchain);
+    assert_eq!(status.height, 2);
+    assert_eq!(status.tip_id, block.id());
+    assert_eq!(status.genesis_id, blockchain.block_at(1).unwrap().id());
+    assert_eq!(status.finalized_height, 1);
+    let vote = Vote::sign(block.id(), &users.main.wallet);
+    assert_eq!(vote.validator, users.main.pbkey());
+
+    let messages = [
+        NetworkMessage::NewTxn(Box::new(txn)),
+        NetworkMessage::NewBlock(Box::new(block.clone())),
+        NetworkMessage::BlockRequest(BlockRequest { from: 1, limit: 2 }),
+        NetworkMessage::BlockResponse(vec![blockchain.block_at(1).unwrap().clone(), block]),
+        NetworkMessage::Status(status),
+        NetworkMessage::Vote(vote),
+    ];
+    for message in messages {
+        let bytes = message.to_bytes()?;
+        assert_eq!(bytes[0], PROTOCOL_VERSION);
+        assert_eq!(NetworkMessage::from_bytes(&bytes)?, message);
+    }
+
+    Ok(())
+}
+
+/// Messages that don't decode are rejected with an error mapped to a penalty for the sender.
+#[test]
+fn message_decode_fail() -> Result<()> {
+    let request = NetworkMessage::BlockRequest(BlockRequest { from: 1, limit: 1 });
+    let bytes = request.to_bytes()?;
+
+    let err = NetworkMessage::from_bytes(&[]).unwrap_err();
+    assert!(matches!(err, ProtocolError::Empty));
+    assert_eq!(err.penalty(), Penalty::Major);
+
+    let mut other_version = bytes.clone();
+    other_version[0] = PROTOCOL_VERSION + 1;
+    let err = NetworkMessage::from_bytes(&other_version).unwrap_err();
+    assert!(matches!(err, ProtocolError::UnsupportedVersion(v) if v == PROTOCOL_VERSION + 1));
+    assert_eq!(err.penalty(), Penalty::Minor);
+
+    // truncated, trailing bytes and unknown variants
+    for malformed in [
+        bytes[..bytes.len() - 1].to_vec(),
+        [bytes.clone(), vec![0]].concat(),
+        vec![PROTOCOL_VERSION, 200],
+    ] {
+        let err = NetworkMessage::from_bytes(&malformed).unwrap_err();
+        assert!(matches!(err, ProtocolError::Malformed(_)), "{err}");
+        assert_eq!(err.penalty(), Penalty::Major);
+    }
+
+    let err =
+        NetworkMessage::from_bytes(&vec![PROTOCOL_VERSION; MAX_MESSAGE_BYTES + 1]).unwrap_err();
+    assert!(matches!(err, ProtocolError::TooLarge { size, max }
+        if size == MAX_MESSAGE_BYTES + 1 && max == MAX_MESSAGE_BYTES));
+    assert_eq!(err.penalty(), Penalty::Fatal);
+
+    // requests must ask for 1..=MAX_BLOCKS_PER_REQUEST blocks
+    for limit in [0, MAX_BLOCKS_PER_REQUEST + 1] {
+        let bytes = NetworkMessage::BlockRequest(BlockRequest { from: 1, limit }).to_bytes()?;
+        let err = NetworkMessage::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(err, ProtocolError::InvalidMessage(_)));
+        assert_eq!(err.penalty(), Penalty::Major);
+    }
+
+    Ok(())
+}
+
+/// Blocks and txns without ids are rejected when decoded, before anything reads their ids.
+#[test]
+fn message_missing_id_fail() -> Result<()> {
+    let users = init_users();
+    let mut blockchain = Blockchain::from_genesis(&init_genesis_spec(&users))?;
+    let txn = add_transfer_block(&mut blockchain, &users, &users.recv, 10)?;
+    let block = blockchain.last_block().clone();
+
+    // the block's id is serialized last, after its txns' ids
+    let block_json = serde_json::to_string(&block)?;
+    let id_at = block_json.rfind(r#""id":["#).unwrap();
+    let no_block_id: Block = serde_json::from_str(&null_ids(&block_json, id_at, block_json.len()))?;
+    let no_txn_id: Block = serde_json::from_str(&null_ids(&block_json, 0, id_at))?;
+    let txn_json = serde_json::to_string(&txn)?;
+    let no_id_txn: Txn = serde_json::from_str(&null_ids(&txn_json, 0, txn_json.len()))?;
+
+    let messages = [
+        NetworkMessage::NewBlock(Box::new(no_block_id.clone())),
+        NetworkMessage::NewBlock(Box::new(no_txn_id.clone())),
+        NetworkMessage::BlockResponse(vec![block, no_txn_id]),
+        NetworkMessage::NewTxn(Box::new(no_id_txn)),
+    ];
+    for message in messages {
+        let err = NetworkMessage::from_bytes(&message.to_bytes()?).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ProtocolError::InvalidBlock(BlockError::EmptyId | BlockError::EmptyTxnId)
+                    | ProtocolError::InvalidTxn(TxnError::EmptyId)
+            ),
+            "{err}"
+        );
+        assert_eq!(err.penalty(), Penalty::Major);
+    }
+    // validation of blocks built without the wire fails the same way
+    assert!(matches!(
+        no_block_id.is_valid(no_block_id.leader()),
+        Err(BlockError::EmptyId)
+    ));
+
+    Ok(())
+}
+
+/// Replace every `"id"` array in `json[from..to]` with `null`.
+fn null_ids(json: &str, from: usize, to: usize) -> String {
+    let mut nulled = json[..from].to_string();
+    let mut rest = &json[from..to];
+    while let Some(at) = rest.find(r#""id":["#) {
+        let end = at + rest[at..].find(']').unwrap() + 1;
+        nulled.push_str(&rest[..at]);
+        nulled.push_str(r#""id":null"#);
+        rest = &rest[end..];
+    }
+
+    [nulled.as_str(), rest, &json[to..]].concat()
+}
+
+/// Peers are banned once their penalties add up to `BAN_SCORE` - a fatal penalty bans right away.
+#[test]
+fn peer_scores_ban_pass() {
+    let mut scores = PeerScores::new();
+    let peer = PeerId::random();
+    let other = PeerId::random();
+
+    assert!(!scores.penalize(peer, Penalty::Minor));
+    assert_eq!(scores.score(&peer), -Penalty::Minor.points());
+    assert!(!scores.penalize(peer, Penalty::Major));
+    assert!(!scores.is_banned(&peer));
+    assert!(scores.penalize(peer, Penalty::Major));
+    assert!(scores.is_banned(&peer));
+    assert!(scores.score(&peer) <= BAN_SCORE);
+    // already banned
+    assert!(!scores.penalize(peer, Penalty::Major));
+
+    assert_eq!(scores.score(&other), 0);
+    assert!(scores.penalize(other, Penalty::Fatal));
+}
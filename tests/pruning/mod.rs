@@ -0,0 +1,257 @@
+use posbc::{
+    ledger::{
+        blockchain::{
+            address_index::HistoryQuery,
+            error::{ArchiveError, BlockchainError},
+            pruning::{PruningMode, DEFAULT_STATE_HISTORY},
+            Blockchain,
+        },
+        general::Result,
+    },
+    storage::{
+        backend::{disk::DiskChainBackend, ChainBackend},
+        config::StorageConfig,
+        segment_log::DEFAULT_SEGMENT_BYTES,
+        state_db::DEFAULT_SNAPSHOT_INTERVAL,
+    },
+};
+use std::fs;
+// test
+use crate::common::{
+    fxns::{add_transfer_block, init_genesis_spec, init_temp_dir},
+    init_users,
+};
+
+/// A pruned node keeps the state after its last `n` blocks - older states are dropped as blocks are added.
+#[test]
+fn pruned_mode_pass() -> Result<()> {
+    let users = init_users();
+    let mut blockchain = Blockchain::from_genesis(&init_genesis_spec(&users))?;
+    assert_eq!(
+        blockchain.pruning(),
+        PruningMode::Pruned(DEFAULT_STATE_HISTORY)
+    );
+    blockchain.set_pruning(PruningMode::Pruned(2));
+    let mut txns = vec![];
+    for amt in [10, 20, 30] {
+        txns.push(add_transfer_block(
+            &mut blockchain,
+            &users,
+            &users.recv,
+            amt,
+        )?);
+    }
+    let block_id = |height| blockchain.block_at(height).unwrap().id_key();
+    let recv_id = users.recv.pbkey().into();
+
+    // kept states are rebuilt from the lowest one
+    for height in 3..=4 {
+        assert_eq!(
+            &blockchain.state_at(&block_id(height))?.state_root(),
+            blockchain.block_at(height).unwrap().state_root()
+        );
+    }
+    assert!(blockchain.account_proof(&block_id(3), &recv_id).is_ok());
+    for height in 1..=2 {
+        assert!(matches!(
+            blockchain.state_at(&block_id(height)),
+            Err(BlockchainError::HistoryPruned(h)) if h == height
+        ));
+    }
+    assert!(matches!(
+        blockchain.account_proof(&block_id(2), &recv_id),
+        Err(BlockchainError::HistoryPruned(2))
+    ));
+    // bodies and receipts are kept
+    assert_eq!(blockchain.pruned_height(), 0);
+    assert_eq!(blockchain.finalized_height(), 3);
+    assert!(blockchain.receipt(txns[0].id()).is_ok());
+    assert!(blockchain.find_txn(txns[0].id())?.is_some());
+
+    // rollbacks are limited to the kept states
+    let err = blockchain.rollback_to(2).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockchainError>(),
+        Some(BlockchainError::HistoryPruned(2))
+    ));
+    blockchain.rollback_to(3)?;
+    assert_eq!(blockchain.height(), 3);
+    assert_eq!(
+        &blockchain.accounts().state_root(),
+        blockchain.last_block().state_root()
+    );
+
+    // switching to a smaller history prunes right away
+    blockchain.set_pruning(PruningMode::Pruned(1));
+    let tip_id = blockchain.last_block().id_key();
+    assert!(blockchain.state_at(&tip_id).is_ok());
+    assert!(matches!(
+        blockchain.state_at(&blockchain.block_at(2).unwrap().id_key()),
+        Err(BlockchainError::HistoryPruned(2))
+    ));
+
+    Ok(())
+}
+
+/// A headers-only node keeps every header, but only the tip's state, txns and receipts.
+#[test]
+fn headers_only_mode_pass() -> Result<()> {
+    let users = init_users();
+    let mut blockchain = Blockchain::from_genesis(&init_genesis_spec(&users))?;
+    blockchain.set_pruning(PruningMode::HeadersOnly);
+    blockchain.enable_address_index();
+    let first = add_transfer_block(&mut blockchain, &users, &users.recv, 10)?;
+    let block_id = blockchain.last_block().id();
+    let last = add_transfer_block(&mut blockchain, &users, &users.recv, 20)?;
+
+    assert_eq!(blockchain.height(), 3);
+    assert_eq!(blockchain.pruned_height(), 2);
+    assert_eq!(blockchain.finalized_height(), 3);
+    let pruned = blockchain.block_at(2).unwrap();
+    assert_eq!(pruned.id(), block_id);
+    assert!(pruned.txns().is_empty());
+    assert_eq!(blockchain.last_block().txns().len(), 1);
+
+    // the txn's location is still known, its body and receipt are not
+    assert_eq!(blockchain.txn_location(first.id()).unwrap().height, 2);
+    assert!(matches!(
+        blockchain.find_txn(first.id()),
+        Err(BlockchainError::HistoryPruned(2))
+    ));
+    assert!(matches!(
+        blockchain.account_history(&users.recv.pbkey().into(), &HistoryQuery::new(10)),
+        Err(BlockchainError::HistoryPruned(2))
+    ));
+    assert!(matches!(
+        blockchain.receipt(first.id()),
+        Err(BlockchainError::HistoryPruned(2))
+    ));
+    assert!(matches!(
+        blockchain.block_receipts(&block_id),
+        Err(BlockchainError::HistoryPruned(2))
+    ));
+    assert!(matches!(
+        blockchain.state_at(&block_id),
+        Err(BlockchainError::HistoryPruned(2))
+    ));
+    assert!(blockchain.receipt(last.id()).is_ok());
+    assert!(blockchain
+        .state_at(&blockchain.last_block().id_key())
+        .is_ok());
+
+    // pruned blocks can't be exported
+    assert!(matches!(
+        blockchain.export_archive(vec![], 2, 3, false).unwrap_err(),
+        ArchiveError::HistoryPruned(2)
+    ));
+
+    // the chain keeps growing from the tip's state
+    add_transfer_block(&mut blockchain, &users, &users.recv, 30)?;
+    assert_eq!(blockchain.pruned_height(), 3);
+    assert_eq!(
+        blockchain
+            .accounts()
+            .acct_balance(&users.recv.pbkey().into()),
+        60
+    );
+
+    Ok(())
+}
+
+/// A stored headers-only chain drops the bodies of its blocks from disk, and still reopens at its tip.
+#[test]
+fn headers_only_mode_restart_pass() -> Result<()> {
+    let dir = init_temp_dir("pruning-headers-only");
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    // every block record fills a segment
+    let config = StorageConfig::new(1, 2, PruningMode::HeadersOnly);
+
+    let mut blockchain = Blockchain::open_with_config(&dir, &spec, &config)?;
+    for amt in [10, 20, 30, 40] {
+        add_transfer_block(&mut blockchain, &users, &users.recv, amt)?;
+    }
+    let block_ids: Vec<_> = (1..=5)
+        .map(|height| blockchain.block_at(height).unwrap().id())
+        .collect();
+    let state_root = blockchain.accounts().state_root();
+    drop(blockchain);
+
+    // bodies up to the stored state are dropped once their segment is full
+    let backend = DiskChainBackend::open(&dir, &config)?;
+    assert_eq!(backend.height(), 5);
+    for height in 1..=5 {
+        let stored = backend.block_at(height)?;
+        assert_eq!(stored.block.id(), block_ids[height as usize - 1]);
+        assert_eq!(stored.has_body(), height >= 4);
+    }
+    drop(backend);
+
+    let mut blockchain = Blockchain::open_with_config(&dir, &spec, &config)?;
+    assert_eq!(blockchain.height(), 5);
+    assert_eq!(blockchain.accounts().state_root(), state_root);
+    add_transfer_block(&mut blockchain, &users, &users.recv, 50)?;
+    assert_eq!(
+        blockchain
+            .accounts()
+            .acct_balance(&users.recv.pbkey().into()),
+        150
+    );
+    drop(blockchain);
+
+    let backend = DiskChainBackend::open(&dir, &config)?;
+    assert!(!backend.block_at(4)?.has_body());
+    assert!(backend.block_at(6)?.has_body());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/// An archive node replays its stored blocks when reopened, keeping the state after each of them.
+#[test]
+fn archive_mode_restart_pass() -> Result<()> {
+    let dir = init_temp_dir("pruning-archive");
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let config = StorageConfig::new(DEFAULT_SEGMENT_BYTES, 2, PruningMode::Archive);
+
+    let mut blockchain = Blockchain::open_with_config(&dir, &spec, &config)?;
+    for amt in [10, 20, 30] {
+        add_transfer_block(&mut blockchain, &users, &users.recv, amt)?;
+    }
+    let state_root = blockchain.accounts().state_root();
+    drop(blockchain);
+
+    let blockchain = Blockchain::open_with_config(&dir, &spec, &config)?;
+    assert_eq!(blockchain.pruning(), PruningMode::Archive);
+    assert_eq!(blockchain.height(), 4);
+    assert_eq!(blockchain.finalized_height(), 1);
+    assert_eq!(blockchain.accounts().state_root(), state_root);
+    for height in 1..=4 {
+        let block = blockchain.block_at(height).unwrap();
+        assert_eq!(
+            &blockchain.state_at(&block.id_key())?.state_root(),
+            block.state_root()
+        );
+    }
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/// Pruned nodes snapshot their stored state at least once per kept history.
+#[test]
+fn pruning_snapshot_interval_pass() {
+    let config = StorageConfig::new(DEFAULT_SEGMENT_BYTES, 100, PruningMode::Pruned(10));
+    assert_eq!(config.state_snapshot_interval(), 10);
+    let config = StorageConfig::new(DEFAULT_SEGMENT_BYTES, 5, PruningMode::Pruned(10));
+    assert_eq!(config.state_snapshot_interval(), 5);
+    let config = StorageConfig::new(
+        DEFAULT_SEGMENT_BYTES,
+        DEFAULT_SNAPSHOT_INTERVAL,
+        PruningMode::Archive,
+    );
+    assert_eq!(config.state_snapshot_interval(), DEFAULT_SNAPSHOT_INTERVAL);
+    let config = StorageConfig::new(DEFAULT_SEGMENT_BYTES, 100, PruningMode::HeadersOnly);
+    assert_eq!(config.state_snapshot_interval(), 100);
+}
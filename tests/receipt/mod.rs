@@ -0,0 +1,220 @@
+use posbc::{
+    accounts::{account::AccountId, error::AccountError},
+    ledger::{
+        block::Block,
+        blockchain::{error::BlockchainError, Blockchain},
+        chain_id::ChainId,
+        chain_params::{ChainParams, DEFAULT_BLOCK_BYTE_LIMIT, DEFAULT_BLOCK_GAS_LIMIT},
+        general::Result,
+        receipt::{calc_receipts_root, BalanceDelta, ReceiptStatus, TxnEvent},
+        txn::{Txn, TxnType},
+    },
+    utils::merkle::empty_root,
+};
+// test
+use crate::common::{
+    fxns::{build_block, init_account_map},
+    init_users, UsersInfo,
+};
+
+/// 5 gas per transfer at a gas price of 2 - a fee of 10.
+const TRANSFER_GAS: u64 = 5;
+const GAS_PRICE: u128 = 2;
+const FEE: u128 = TRANSFER_GAS as u128 * GAS_PRICE;
+
+/// ### Create a chain with fees, where `send` holds 1000.
+fn init_chain_with_fees(users: &UsersInfo) -> Blockchain {
+    init_chain_from(Block::new_genesis(ChainId::default(), &users.main.wallet))
+}
+/// ### Create a chain with fees on a given genesis block - i.e. a peer's copy of a chain.
+fn init_chain_from(genesis: Block) -> Blockchain {
+    let params = ChainParams::new(
+        TRANSFER_GAS,
+        DEFAULT_BLOCK_GAS_LIMIT,
+        DEFAULT_BLOCK_BYTE_LIMIT,
+        GAS_PRICE,
+    );
+    let mut blockchain = Blockchain::with_params(params);
+    init_account_map(&mut blockchain);
+    blockchain.add_block(genesis).unwrap();
+
+    blockchain
+}
+
+#[test]
+fn receipt_success_pass() -> Result<()> {
+    let users = init_users();
+    let mut blockchain = init_chain_with_fees(&users);
+    let send_id: AccountId = users.send.pbkey().into();
+    let recv_id: AccountId = users.recv.pbkey().into();
+
+    let txn = Txn::new_signed(
+        &users.send.wallet,
+        ChainId::default(),
+        users.recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
+    let block = build_block(&users, &mut blockchain, std::slice::from_ref(&txn))?;
+    let block_id = block.id();
+    blockchain.add_block(block)?;
+
+    let receipt = blockchain.receipt(txn.id()).unwrap();
+    assert_eq!(receipt.txn_id(), txn.id());
+    assert!(receipt.is_success());
+    assert_eq!(receipt.gas_used(), TRANSFER_GAS);
+    assert_eq!(receipt.fee(), FEE);
+    // fee is burned - not credited to anyone
+    assert_eq!(
+        blockchain.accounts().acct_balance(&send_id),
+        1000 - 100 - FEE
+    );
+    assert_eq!(blockchain.accounts().acct_balance(&recv_id), 100);
+
+    let mut expected_deltas = vec![
+        BalanceDelta {
+            acct_id: send_id,
+            before: 1000,
+            after: 1000 - 100 - FEE,
+        },
+        BalanceDelta {
+            acct_id: recv_id,
+            before: 0,
+            after: 100,
+        },
+    ];
+    expected_deltas.sort_by_key(|delta| delta.acct_id);
+    assert_eq!(receipt.balance_deltas(), expected_deltas);
+    assert_eq!(
+        receipt.events(),
+        [
+            TxnEvent::FeeBurned {
+                payer: send_id,
+                amt: FEE
+            },
+            TxnEvent::AccountCreated { acct_id: recv_id },
+            TxnEvent::Transfer {
+                from: send_id,
+                to: recv_id,
+                amt: 100
+            },
+        ]
+    );
+
+    // receipts are stored with the block, and committed to by its header
+    let receipts = blockchain.block_receipts(&block_id).unwrap();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(
+        &calc_receipts_root(receipts),
+        blockchain.block(&block_id).unwrap().receipts_root()
+    );
+
+    Ok(())
+}
+
+/// The sender covers the fee but not the amount: the txn is included, only the fee is charged.
+#[test]
+fn receipt_insufficient_balance_pass() -> Result<()> {
+    let users = init_users();
+    let mut blockchain = init_chain_with_fees(&users);
+    let send_id: AccountId = users.send.pbkey().into();
+
+    let txn = Txn::new_signed(
+        &users.send.wallet,
+        ChainId::default(),
+        users.recv.pbkey(),
+        5000,
+        TxnType::Transfer,
+    );
+    let block = build_block(&users, &mut blockchain, std::slice::from_ref(&txn))?;
+    blockchain.add_block(block)?;
+
+    let receipt = blockchain.receipt(txn.id()).unwrap();
+    assert_eq!(receipt.status(), ReceiptStatus::InsufficientBalance);
+    assert_eq!(receipt.fee(), FEE);
+    assert_eq!(blockchain.accounts().acct_balance(&send_id), 1000 - FEE);
+    assert!(blockchain
+        .accounts()
+        .get_acct(&users.recv.pbkey().into())
+        .is_none());
+
+    Ok(())
+}
+
+/// A sender that can't pay the fee can't have its txn included.
+#[test]
+fn execute_txn_fail_fee() {
+    let users = init_users();
+    let mut blockchain = init_chain_with_fees(&users);
+    let txn = Txn::new_signed(
+        &users.recv.wallet,
+        ChainId::default(),
+        users.send.pbkey(),
+        1,
+        TxnType::Transfer,
+    );
+
+    // `recv` has no account
+    let err = blockchain.add_txn_to_block(&txn).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<AccountError>(),
+        Some(AccountError::UnknownAccount(_))
+    ));
+
+    // `recv` has an account, but can't cover the fee
+    blockchain
+        .accounts_mut()
+        .get_or_init_acct(&users.recv.pbkey().into());
+    let err = blockchain.add_txn_to_block(&txn).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<AccountError>(),
+        Some(AccountError::InsufficientBalance {
+            balance: 0,
+            required: FEE
+        })
+    ));
+}
+
+/// Replaying a block reproduces its receipts; a block committing to other receipts is rejected.
+#[test]
+fn replay_block_receipts() -> Result<()> {
+    let users = init_users();
+    let mut leader_chain = init_chain_with_fees(&users);
+    let mut peer_chain = init_chain_from(leader_chain.block_at(1).unwrap().clone());
+    let txns = [
+        Txn::new_signed(
+            &users.send.wallet,
+            ChainId::default(),
+            users.recv.pbkey(),
+            100,
+            TxnType::Transfer,
+        ),
+        Txn::new_signed(
+            &users.send.wallet,
+            ChainId::default(),
+            users.recv.pbkey(),
+            5000,
+            TxnType::Transfer,
+        ),
+    ];
+    let block = build_block(&users, &mut leader_chain, &txns)?;
+
+    let mut bad_block = block.clone();
+    bad_block.set_receipts_root(empty_root());
+    bad_block.sign(&users.main.wallet);
+    let err = peer_chain.replay_block(bad_block).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockchainError>(),
+        Some(BlockchainError::IncorrectReceiptsRoot)
+    ));
+
+    let block_id = block.id();
+    leader_chain.add_block(block.clone())?;
+    peer_chain.replay_block(block)?;
+    assert_eq!(
+        peer_chain.block_receipts(&block_id)?,
+        leader_chain.block_receipts(&block_id)?
+    );
+
+    Ok(())
+}
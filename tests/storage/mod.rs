@@ -0,0 +1,422 @@
+use posbc::{
+    ledger::{
+        blockchain::{error::BlockchainError, pruning::PruningMode, Blockchain},
+        general::Result,
+        genesis::GenesisSpec,
+        txn::{Txn, TxnType},
+        txn_pool::TxnPool,
+    },
+    storage::{
+        backend::{
+            disk::{DiskChainBackend, DiskTxnPoolBackend},
+            memory::{MemoryChainBackend, MemoryTxnPoolBackend},
+            ChainBackend, TxnPoolBackend,
+        },
+        config::{StorageConfig, STATE_DIR, TXN_POOL_DIR},
+        error::StorageError,
+        segment_log::{SegmentLog, DEFAULT_SEGMENT_BYTES, RECORD_HEADER_LEN},
+    },
+};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+// test
+use crate::common::{
+    fxns::{add_transfer_block, init_genesis_spec, init_temp_dir},
+    init_users,
+};
+
+/// ## Storage backend a test runs against - see `run_all()`.
+enum TestBackend {
+    Memory,
+    Disk(PathBuf),
+}
+
+impl TestBackend {
+    /// ### Run `test` against an in-memory backend, then against a disk backend in a fresh temp dir.
+    fn run_all(name: &str, test: impl Fn(&TestBackend) -> Result<()>) -> Result<()> {
+        test(&TestBackend::Memory)?;
+        let dir = init_temp_dir(name);
+        test(&TestBackend::Disk(dir.clone()))?;
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+    /// ### Open a chain on the backend - `prev` is stopped first, and its backend reused if in memory.
+    fn open_chain(&self, prev: Option<Blockchain>, spec: &GenesisSpec) -> Result<Blockchain> {
+        let backend: Box<dyn ChainBackend> = match self {
+            Self::Memory => prev
+                .and_then(Blockchain::into_backend)
+                .unwrap_or_else(|| Box::new(MemoryChainBackend::new())),
+            Self::Disk(dir) => {
+                drop(prev);
+                Box::new(DiskChainBackend::open(dir, &StorageConfig::default())?)
+            }
+        };
+
+        Blockchain::open_with_backend(backend, spec, PruningMode::default())
+    }
+    /// ### Open a txn pool on the backend - `prev` is stopped first, and its backend reused if in memory.
+    fn open_txn_pool(&self, prev: Option<TxnPool>) -> Result<TxnPool> {
+        let backend: Box<dyn TxnPoolBackend> = match self {
+            Self::Memory => prev
+                .and_then(TxnPool::into_backend)
+                .unwrap_or_else(|| Box::new(MemoryTxnPoolBackend::new())),
+            Self::Disk(dir) => {
+                drop(prev);
+                Box::new(DiskTxnPoolBackend::open(
+                    dir.join(TXN_POOL_DIR),
+                    &StorageConfig::default(),
+                )?)
+            }
+        };
+
+        TxnPool::open(backend)
+    }
+}
+
+/// Records are read back in order after reopening, across segments.
+#[test]
+fn segment_log_round_trip_pass() -> Result<()> {
+    let dir = init_temp_dir("segment-round-trip");
+    let payloads: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 20]).collect();
+
+    // room for 2 records per segment
+    let max_segment_bytes = 2 * (RECORD_HEADER_LEN + 20) as u64;
+    let mut log = SegmentLog::open(&dir, max_segment_bytes)?;
+    for payload in payloads.iter() {
+        log.append(payload)?;
+    }
+    assert_eq!(log.segments(), &[0, 1, 2]);
+    drop(log);
+
+    let log = SegmentLog::open(&dir, max_segment_bytes)?;
+    assert_eq!(log.records().len(), payloads.len());
+    assert_eq!(log.truncated_bytes(), 0);
+    for (pos, payload) in log.records().iter().zip(payloads.iter()) {
+        assert_eq!(&log.read(pos)?, payload);
+    }
+    fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
+/// A partly written record at the end of the log (i.e. from a crash) is dropped on open.
+#[test]
+fn segment_log_torn_tail_pass() -> Result<()> {
+    let dir = init_temp_dir("segment-torn-tail");
+    let mut log = SegmentLog::open(&dir, u64::MAX)?;
+    log.append(b"first")?;
+    log.append(b"second")?;
+    drop(log);
+
+    // header of a 100 byte record, followed by only 5 bytes of it
+    let segment = fs::read_dir(&dir)?.next().unwrap()?.path();
+    let mut file = OpenOptions::new().append(true).open(&segment)?;
+    file.write_all(&100u32.to_be_bytes())?;
+    file.write_all(&[0u8; 8])?;
+    file.write_all(b"third")?;
+    drop(file);
+
+    let mut log = SegmentLog::open(&dir, u64::MAX)?;
+    assert_eq!(log.records().len(), 2);
+    assert_eq!(log.truncated_bytes(), (RECORD_HEADER_LEN + 5) as u64);
+    log.append(b"third")?;
+    drop(log);
+
+    let log = SegmentLog::open(&dir, u64::MAX)?;
+    assert_eq!(log.records().len(), 3);
+    assert_eq!(log.read(&log.records()[2])?, b"third");
+    fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
+/// A damaged record before the last segment can't be from a torn write - the log is corrupt.
+#[test]
+fn segment_log_fail_corrupt() -> Result<()> {
+    let dir = init_temp_dir("segment-corrupt");
+    let max_segment_bytes = (RECORD_HEADER_LEN + 10) as u64;
+    let mut log = SegmentLog::open(&dir, max_segment_bytes)?;
+    log.append(&[1u8; 10])?;
+    log.append(&[2u8; 10])?;
+    assert_eq!(log.segments(), &[0, 1]);
+    drop(log);
+
+    let mut segments: Vec<_> = fs::read_dir(&dir)?.map(|e| e.unwrap().path()).collect();
+    segments.sort();
+    let mut bytes = fs::read(&segments[0])?;
+    bytes[RECORD_HEADER_LEN] ^= 0xff;
+    fs::write(&segments[0], bytes)?;
+
+    let err = SegmentLog::open(&dir, max_segment_bytes).unwrap_err();
+    assert!(matches!(err, StorageError::Corrupt { offset: 0, .. }));
+    fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
+/// A reopened chain restarts at the block it stopped at, with the same state and txn index.
+#[test]
+fn blockchain_open_restart_pass() -> Result<()> {
+    TestBackend::run_all("chain-restart", |backend| {
+        let users = init_users();
+        let spec = init_genesis_spec(&users);
+
+        let mut blockchain = backend.open_chain(None, &spec)?;
+        assert_eq!(blockchain.backend().unwrap().height(), 1);
+        add_transfer_block(&mut blockchain, &users, &users.recv, 100)?;
+        let txn = add_transfer_block(&mut blockchain, &users, &users.test2, 30)?;
+        let tip_id = blockchain.last_block().id();
+        let state_root = blockchain.accounts().state_root();
+
+        let blockchain = backend.open_chain(Some(blockchain), &spec)?;
+        assert_eq!(blockchain.height(), 3);
+        assert_eq!(blockchain.backend().unwrap().height(), 3);
+        assert_eq!(blockchain.backend().unwrap().state_height(), 3);
+        assert_eq!(blockchain.last_block().id(), tip_id);
+        assert_eq!(blockchain.accounts().state_root(), state_root);
+        assert_eq!(
+            blockchain
+                .accounts()
+                .acct_balance(&users.recv.pbkey().into()),
+            100
+        );
+        let found = blockchain.find_txn(txn.id())?.unwrap();
+        assert_eq!(found.location.height, 3);
+
+        Ok(())
+    })
+}
+
+/// A rollback is kept across restarts, and the chain can grow again from the new tip.
+#[test]
+fn blockchain_open_rollback_pass() -> Result<()> {
+    TestBackend::run_all("chain-rollback", |backend| {
+        let users = init_users();
+        let spec = init_genesis_spec(&users);
+
+        let mut blockchain = backend.open_chain(None, &spec)?;
+        add_transfer_block(&mut blockchain, &users, &users.recv, 100)?;
+        let removed = add_transfer_block(&mut blockchain, &users, &users.test2, 30)?;
+        blockchain.rollback_to(2)?;
+
+        let mut blockchain = backend.open_chain(Some(blockchain), &spec)?;
+        assert_eq!(blockchain.height(), 2);
+        assert!(blockchain.find_txn(removed.id())?.is_none());
+        add_transfer_block(&mut blockchain, &users, &users.test3, 10)?;
+        let tip_id = blockchain.last_block().id();
+
+        let blockchain = backend.open_chain(Some(blockchain), &spec)?;
+        assert_eq!(blockchain.height(), 3);
+        assert_eq!(blockchain.last_block().id(), tip_id);
+
+        Ok(())
+    })
+}
+
+/// A backend can only be reopened with the genesis spec it was started from.
+#[test]
+fn blockchain_open_fail_genesis_mismatch() -> Result<()> {
+    TestBackend::run_all("chain-genesis-mismatch", |backend| {
+        let users = init_users();
+        let spec = init_genesis_spec(&users);
+        let blockchain = backend.open_chain(None, &spec)?;
+        let genesis_id = blockchain.last_block().id();
+
+        let mut json: serde_json::Value = serde_json::from_str(&spec.to_json()?)?;
+        json["chain_id"] = "posbc-other".into();
+        let other_spec = serde_json::from_value(json)?;
+        let err = backend
+            .open_chain(Some(blockchain), &other_spec)
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<StorageError>(),
+            Some(StorageError::GenesisMismatch(id)) if *id == genesis_id
+        ));
+
+        Ok(())
+    })
+}
+
+/// Pending txns are kept across restarts, until removed from the pool.
+#[test]
+fn txn_pool_open_restart_pass() -> Result<()> {
+    TestBackend::run_all("txn-pool-restart", |backend| {
+        let users = init_users();
+        let chain_id = init_genesis_spec(&users).chain_id().clone();
+        let txns: Vec<Txn> = [10, 20, 30]
+            .into_iter()
+            .map(|amt| {
+                Txn::new_signed(
+                    &users.send.wallet,
+                    chain_id.clone(),
+                    users.recv.pbkey(),
+                    amt,
+                    TxnType::Transfer,
+                )
+            })
+            .collect();
+
+        let mut txn_pool = backend.open_txn_pool(None)?;
+        for txn in txns.iter() {
+            txn_pool.add_txn(txn.clone())?;
+        }
+        txn_pool.remove_txn(&txns[1])?;
+
+        let mut txn_pool = backend.open_txn_pool(Some(txn_pool))?;
+        assert_eq!(txn_pool.txn_ct(), 2);
+        assert!(txn_pool.does_txn_exist(&txns[0]));
+        assert!(!txn_pool.does_txn_exist(&txns[1]));
+        assert!(txn_pool.add_txn(txns[2].clone()).is_err());
+        txn_pool.remove_txn(&txns[0])?;
+
+        let txn_pool = backend.open_txn_pool(Some(txn_pool))?;
+        assert_eq!(txn_pool.txn_ct(), 1);
+        assert!(txn_pool.does_txn_exist(&txns[2]));
+
+        Ok(())
+    })
+}
+
+/// The account state is restored from its snapshot and log, without executing the blocks below it.
+#[test]
+fn state_db_snapshot_pass() -> Result<()> {
+    let dir = init_temp_dir("state-snapshot");
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+    let config = StorageConfig::new(DEFAULT_SEGMENT_BYTES, 2, PruningMode::default());
+
+    let mut blockchain = Blockchain::open_with_config(&dir, &spec, &config)?;
+    add_transfer_block(&mut blockchain, &users, &users.recv, 100)?;
+    add_transfer_block(&mut blockchain, &users, &users.test2, 30)?;
+    add_transfer_block(&mut blockchain, &users, &users.recv, 5)?;
+    add_transfer_block(&mut blockchain, &users, &users.test3, 10)?;
+    let state_root = blockchain.accounts().state_root();
+    drop(blockchain);
+
+    let backend = DiskChainBackend::open(&dir, &config)?;
+    let state_db = backend.state_db();
+    assert_eq!(state_db.height(), 5);
+    assert_eq!(state_db.snapshot_height(), 4);
+    // the log before the snapshot is deleted
+    assert_eq!(state_db.wal().records().len(), 1);
+
+    let mut blockchain =
+        Blockchain::open_with_backend(Box::new(backend), &spec, PruningMode::default())?;
+    assert_eq!(blockchain.height(), 5);
+    assert_eq!(blockchain.accounts().state_root(), state_root);
+    assert_eq!(
+        blockchain
+            .accounts()
+            .acct_balance(&users.recv.pbkey().into()),
+        105
+    );
+    // blocks below the stored state were restored, not executed - their state is not kept
+    assert!(matches!(
+        blockchain.state_at(&blockchain.block_at(3).unwrap().id_key()),
+        Err(BlockchainError::HistoryPruned(3))
+    ));
+    let err = blockchain.rollback_to(3).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<BlockchainError>(),
+        Some(BlockchainError::HistoryPruned(3))
+    ));
+    // the chain grows from the restored tip
+    add_transfer_block(&mut blockchain, &users, &users.test3, 10)?;
+    drop(blockchain);
+
+    let backend = DiskChainBackend::open(&dir, &config)?;
+    assert_eq!(backend.state_db().snapshot_height(), 6);
+    let blockchain =
+        Blockchain::open_with_backend(Box::new(backend), &spec, PruningMode::default())?;
+    assert_eq!(blockchain.height(), 6);
+    assert_eq!(
+        blockchain
+            .accounts()
+            .acct_balance(&users.recv.pbkey().into()),
+        105
+    );
+    fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
+/// A node killed mid-commit (block stored, state commit torn) restarts with the state after its last block.
+#[test]
+fn state_db_crash_mid_commit_pass() -> Result<()> {
+    let dir = init_temp_dir("state-crash-commit");
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+
+    let mut blockchain = Blockchain::open(&dir, &spec)?;
+    add_transfer_block(&mut blockchain, &users, &users.recv, 100)?;
+    add_transfer_block(&mut blockchain, &users, &users.test2, 30)?;
+    let state_root = blockchain.accounts().state_root();
+    drop(blockchain);
+
+    // cut the last commit short
+    let wal_dir = dir.join(STATE_DIR).join("wal");
+    let mut segments: Vec<_> = fs::read_dir(&wal_dir)?.map(|e| e.unwrap().path()).collect();
+    segments.sort();
+    let segment = segments.last().unwrap();
+    let len = fs::metadata(segment)?.len();
+    OpenOptions::new()
+        .write(true)
+        .open(segment)?
+        .set_len(len - 10)?;
+
+    let backend = DiskChainBackend::open(&dir, &StorageConfig::default())?;
+    assert!(backend.state_db().wal().truncated_bytes() > 0);
+    assert_eq!(backend.state_db().height(), 2);
+    assert_eq!(backend.block_store().height(), 3);
+
+    // the last block is replayed from the block store, and its state committed again
+    let blockchain =
+        Blockchain::open_with_backend(Box::new(backend), &spec, PruningMode::default())?;
+    assert_eq!(blockchain.backend().unwrap().state_height(), 3);
+    assert_eq!(blockchain.height(), 3);
+    assert_eq!(blockchain.accounts().state_root(), state_root);
+    drop(blockchain);
+
+    let blockchain = Blockchain::open(&dir, &spec)?;
+    assert_eq!(blockchain.accounts().state_root(), state_root);
+    fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
+/// A node killed mid-snapshot restarts from the previous snapshot and its log.
+#[test]
+fn state_db_crash_mid_snapshot_pass() -> Result<()> {
+    let dir = init_temp_dir("state-crash-snapshot");
+    let users = init_users();
+    let spec = init_genesis_spec(&users);
+
+    let mut blockchain = Blockchain::open(&dir, &spec)?;
+    add_transfer_block(&mut blockchain, &users, &users.recv, 100)?;
+    let state_root = blockchain.accounts().state_root();
+    drop(blockchain);
+
+    // the log moved on to a new segment, but the snapshot was never renamed into place
+    let snapshots_dir = dir.join(STATE_DIR).join("snapshots");
+    let unfinished = snapshots_dir.join(format!("{:020}.tmp", 1));
+    fs::write(&unfinished, b"{\"height\":")?;
+    fs::write(
+        dir.join(STATE_DIR)
+            .join("wal")
+            .join(format!("{:020}.seg", 1)),
+        b"",
+    )?;
+
+    let blockchain = Blockchain::open(&dir, &spec)?;
+    assert_eq!(blockchain.height(), 2);
+    assert_eq!(blockchain.backend().unwrap().state_height(), 2);
+    assert_eq!(blockchain.accounts().state_root(), state_root);
+    assert!(!unfinished.exists());
+    fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
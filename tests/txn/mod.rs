@@ -1,7 +1,12 @@
 use ed25519_dalek::{Digest, Signer};
 use posbc::ledger::{
-    general::{HashAlgo, KP},
-    txn::{constants::*, txn_id::TxnId, txn_signature::TxnSignature, types::*},
+    chain_id::ChainId,
+    general::{HashAlgo, Result, KP},
+    txn::{
+        constants::*, txn_header::TxnHeader, txn_id::TxnId, txn_signature::TxnSignature, types::*,
+        Txn, TxnType,
+    },
+    txn_pool::TxnPool,
 };
 // test
 use crate::common::{create_transfer_txn_default, init_send_recv};
@@ -38,12 +43,14 @@ fn create_signed_txn_pass() {
     // convert to byte array
     let digest: TxnDigest = prehash.finalize().into();
     let mut digest_buffer: TxnCtxDigest = [0_u8; TXN_DIGEST_LEN + TXN_SIGNATURE_CTX.len()];
-    // add context
-    digest_buffer[..TXN_SIGNATURE_CTX.len()].copy_from_slice(TXN_SIGNATURE_CTX);
+    // add context - derived for the txn's chain
+    let ctx = txn1.chain_id().signing_ctx(TXN_SIGNATURE_CTX);
+    digest_buffer[..TXN_SIGNATURE_CTX.len()].copy_from_slice(&ctx);
     // add digest
     digest_buffer[TXN_SIGNATURE_CTX.len()..digest.len() + TXN_SIGNATURE_CTX.len()]
         .copy_from_slice(&digest);
-    let ctx_digest: TxnCtxDigest = TxnId(digest).to_presigned_digest();
+    let ctx_digest: TxnCtxDigest = TxnId(digest).to_presigned_digest(txn1.chain_id());
+    assert_eq!(digest_buffer, ctx_digest);
 
     // sign msg and return signature
     let msg_signature_manual = kp.sign(&ctx_digest);
@@ -55,7 +62,7 @@ fn create_signed_txn_pass() {
 
     assert_eq!(
         msg_signature_manual,
-        msg_sig_txn_sign.clone().into(),
+        msg_sig_txn_sign.clone().try_into().unwrap(),
         "\n0: \n{msg_signature_manual:?}\n{}",
         msg_sig_txn_sign.to_str()
     );
@@ -72,3 +79,123 @@ fn create_signed_txn_pass() {
         msg_signature_wallet.to_str()
     );
 }
+
+/// Build an unsigned txn "online", sign it "offline", and import the signed result for broadcast.
+///
+/// The id and signature must survive both export/import round trips.
+#[test]
+fn offline_sign_round_trip_pass() -> Result<()> {
+    let (send, recv) = init_send_recv();
+
+    // 1. online: build the unsigned txn with a fixed timestamp and export it
+    let header = TxnHeader::new(
+        ChainId::default(),
+        25,
+        send.pbkey(),
+        recv.pbkey(),
+        1_700_000_000_000,
+        TxnType::Transfer,
+    );
+    let unsigned_txn = Txn::from_header(header);
+    let unsigned_export = unsigned_txn.export();
+
+    // 2. offline: import, sign with the sender's wallet, export
+    let mut offline_txn = Txn::import(&unsigned_export)?;
+    assert_eq!(offline_txn.id(), unsigned_txn.id());
+    let signature = offline_txn.sign(&send.wallet);
+    let signed_export = offline_txn.export();
+
+    // 3. online: import the signed txn and submit it to the pool
+    let signed_txn = Txn::import(&signed_export)?;
+    assert_eq!(signed_txn.id(), unsigned_txn.id());
+    assert_eq!(signed_txn.signature(), &signature);
+    signed_txn.is_signature_valid(&send.pbkey())?;
+
+    let mut txn_pool = TxnPool::new();
+    txn_pool.add_txn(signed_txn)?;
+    assert_eq!(txn_pool.txn_ct(), 1);
+
+    Ok(())
+}
+
+/// The same header always produces the same id, regardless of when it is built.
+#[test]
+fn txn_from_header_deterministic_pass() {
+    let (send, recv) = init_send_recv();
+    let build = || {
+        let header = TxnHeader::new(
+            ChainId::default(),
+            25,
+            send.pbkey(),
+            recv.pbkey(),
+            1_700_000_000_000,
+            TxnType::Transfer,
+        );
+        Txn::from_header(header)
+    };
+
+    assert_eq!(build().id(), build().id());
+}
+
+#[test]
+fn attach_signature_fail_wrong_signer() {
+    let (send, recv) = init_send_recv();
+    let header = TxnHeader::new(
+        ChainId::default(),
+        25,
+        send.pbkey(),
+        recv.pbkey(),
+        1_700_000_000_000,
+        TxnType::Transfer,
+    );
+    let mut txn = Txn::from_header(header);
+
+    // signed by the recipient, not the sender
+    let bad_signature = recv.wallet.sign_txn(&txn);
+    assert!(txn.attach_signature(bad_signature).is_err());
+
+    let good_signature = send.wallet.sign_txn(&txn);
+    assert!(txn.attach_signature(good_signature).is_ok());
+}
+
+/// A signature made for one chain is not valid for the same txn on another chain.
+#[test]
+fn attach_signature_fail_wrong_chain() {
+    let (send, recv) = init_send_recv();
+    let build = |chain_id: ChainId| {
+        Txn::from_header(TxnHeader::new(
+            chain_id,
+            25,
+            send.pbkey(),
+            recv.pbkey(),
+            1_700_000_000_000,
+            TxnType::Transfer,
+        ))
+    };
+    let devnet_txn = build(ChainId::default());
+    let mut testnet_txn = build("posbc-testnet".parse().unwrap());
+    assert_ne!(devnet_txn.id(), testnet_txn.id());
+
+    let devnet_signature = send.wallet.sign_txn(&devnet_txn);
+    assert!(testnet_txn.attach_signature(devnet_signature).is_err());
+
+    // signing the id under the base context, without the chain, is rejected too
+    let kp_send: Vec<u8> = [send.kp.secret.to_bytes(), send.kp.public.to_bytes()].concat();
+    let kp = KP::from_bytes(&kp_send).unwrap();
+    let msg = [TXN_SIGNATURE_CTX.as_slice(), &testnet_txn.id().0].concat();
+    let base_ctx_signature: TxnSignature = kp.sign(&msg).to_bytes().into();
+    assert!(testnet_txn.attach_signature(base_ctx_signature).is_err());
+
+    let testnet_signature = send.wallet.sign_txn(&testnet_txn);
+    assert!(testnet_txn.attach_signature(testnet_signature).is_ok());
+    assert!(testnet_txn.is_for_chain(&ChainId::default()).is_err());
+}
+
+#[test]
+fn import_txn_fail_malformed() {
+    let txn = create_transfer_txn_default();
+    let exported = txn.export();
+
+    assert!(Txn::import("not-a-txn").is_err());
+    assert!(Txn::import(&exported[..exported.len() - 8]).is_err());
+}
@@ -1,4 +1,5 @@
 use posbc::ledger::{
+    chain_id::ChainId,
     general::Result,
     txn::{Txn, TxnType},
     txn_pool::TxnPool,
@@ -21,7 +22,13 @@ pub fn add_txn_pass() -> Result<()> {
     let mut txn_pool = TxnPool::new();
 
     // create txn
-    let txn_1 = Txn::new(send.pbkey(), recv.pbkey(), 100, TxnType::Transfer);
+    let txn_1 = Txn::new_signed(
+        &send.wallet,
+        ChainId::default(),
+        recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
 
     // add to pool
     assert!(txn_pool.txn_ct() == 0);
@@ -39,7 +46,13 @@ fn add_txn_fail_dup() -> Result<()> {
     let mut txn_pool = TxnPool::new();
 
     // create txn
-    let txn_1 = Txn::new(send.pbkey(), recv.pbkey(), 100, TxnType::Transfer);
+    let txn_1 = Txn::new_signed(
+        &send.wallet,
+        ChainId::default(),
+        recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
     let txn_1_copy = txn_1.clone();
     // add to pool
     txn_pool.add_txn(txn_1)?;
@@ -62,7 +75,13 @@ fn remove_txn_pass() -> Result<()> {
     let mut txn_pool = TxnPool::new();
 
     // create txn
-    let txn_1 = Txn::new(send.pbkey(), recv.pbkey(), 100, TxnType::Transfer);
+    let txn_1 = Txn::new_signed(
+        &send.wallet,
+        ChainId::default(),
+        recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
 
     // add to pool
     txn_pool.add_txn(txn_1.clone())?;
@@ -82,7 +101,13 @@ fn does_txn_exist_pass() -> Result<()> {
     let mut txn_pool = TxnPool::new();
 
     // create txn
-    let txn_1 = Txn::new(send.pbkey(), recv.pbkey(), 100, TxnType::Transfer);
+    let txn_1 = Txn::new_signed(
+        &send.wallet,
+        ChainId::default(),
+        recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
 
     // add to pool
     txn_pool.add_txn(txn_1.clone())?;
@@ -92,3 +117,42 @@ fn does_txn_exist_pass() -> Result<()> {
 
     Ok(())
 }
+
+/// Unsigned or incorrectly signed txns are rejected on admission.
+#[test]
+fn add_txn_fail_invalid_signature() {
+    // init
+    let (send, recv) = init_send_recv();
+    let mut txn_pool = TxnPool::new();
+
+    // unsigned
+    let txn_unsigned = Txn::new(
+        ChainId::default(),
+        send.pbkey(),
+        recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
+    assert!(txn_pool.add_txn(txn_unsigned).is_err());
+
+    // received from a peer with a signature from someone other than the sender
+    let txn_valid = Txn::new_signed(
+        &send.wallet,
+        ChainId::default(),
+        recv.pbkey(),
+        100,
+        TxnType::Transfer,
+    );
+    let txn_json = serde_json::to_string(&txn_valid).unwrap();
+    let sig_json = serde_json::to_string(txn_valid.signature()).unwrap();
+    let other_sig_json = serde_json::to_string(&recv.wallet.sign_txn(&txn_valid)).unwrap();
+    let txn_forged: Txn =
+        serde_json::from_str(&txn_json.replace(&sig_json, &other_sig_json)).unwrap();
+    assert!(txn_pool.add_txn(txn_forged).is_err());
+
+    // received from a peer with a truncated signature
+    let txn_malformed: Txn = serde_json::from_str(&txn_json.replace(&sig_json, "[1,2,3]")).unwrap();
+    assert!(txn_pool.add_txn(txn_malformed).is_err());
+
+    assert_eq!(txn_pool.txn_ct(), 0);
+}
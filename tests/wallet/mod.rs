@@ -23,12 +23,14 @@ fn verify_txn_signature_pass() {
     let digest: TxnDigest = prehash.finalize().into();
 
     let mut digest_buffer: TxnCtxDigest = [0_u8; TXN_DIGEST_LEN + TXN_SIGNATURE_CTX.len()];
-    // add context
-    digest_buffer[..TXN_SIGNATURE_CTX.len()].copy_from_slice(TXN_SIGNATURE_CTX);
+    // add context - derived for the txn's chain
+    let ctx = txn1.chain_id().signing_ctx(TXN_SIGNATURE_CTX);
+    digest_buffer[..TXN_SIGNATURE_CTX.len()].copy_from_slice(&ctx);
     // add digest
     digest_buffer[TXN_SIGNATURE_CTX.len()..digest.len() + TXN_SIGNATURE_CTX.len()]
         .copy_from_slice(&digest);
-    let ctx_digest: TxnCtxDigest = TxnId(digest).to_presigned_digest();
+    let ctx_digest: TxnCtxDigest = TxnId(digest).to_presigned_digest(txn1.chain_id());
+    assert_eq!(digest_buffer, ctx_digest);
 
     // load key and sign
     let kp_send: Vec<u8> = [send.kp.secret.to_bytes(), send.kp.public.to_bytes()].concat();